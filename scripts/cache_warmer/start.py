@@ -1,9 +1,14 @@
+import json
 import os
 import requests
 from dotenv import load_dotenv
 
 load_dotenv("../../.env")
-TX_SERVICE_URL = os.getenv("TRANSACTION_SERVICE_URL")
+CHAIN_ID = os.getenv("CHAIN_ID", "1")
+with open(os.path.join("../..", os.getenv("CHAINS_CONFIG_PATH", "chains.json"))) as chains_file:
+    TX_SERVICE_URL = next(
+        chain["transactionService"] for chain in json.load(chains_file) if chain["chainId"] == CHAIN_ID
+    )
 PRINT_FORMAT = "{0:<10} {1:>8}::{2:>8}"
 
 
@@ -26,10 +31,10 @@ def load_safes() -> list[str]:
 
 def warm_up_safes(base_gateway_url: str, safes: list[str]):
     for safe in safes:
-        balance_response = requests.get("%s/v1/safes/%s/balances/USD" % (base_gateway_url, safe))
-        collectibles_response = requests.get("%s/v1/safes/%s/collectibles" % (base_gateway_url, safe))
-        tx_queued_response = requests.get("%s/v1/safes/%s/transactions/queued" % (base_gateway_url, safe))
-        tx_history_response = requests.get("%s/v1/safes/%s/transactions/history" % (base_gateway_url, safe))
+        balance_response = requests.get("%s/v1/chains/%s/safes/%s/balances/USD" % (base_gateway_url, CHAIN_ID, safe))
+        collectibles_response = requests.get("%s/v1/chains/%s/safes/%s/collectibles" % (base_gateway_url, CHAIN_ID, safe))
+        tx_queued_response = requests.get("%s/v1/chains/%s/safes/%s/transactions/queued" % (base_gateway_url, CHAIN_ID, safe))
+        tx_history_response = requests.get("%s/v1/chains/%s/safes/%s/transactions/history" % (base_gateway_url, CHAIN_ID, safe))
 
         print(PRINT_FORMAT.format(str(balance_response.elapsed.total_seconds()), str(
             balance_response.status_code), balance_response.url))
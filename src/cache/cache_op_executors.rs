@@ -1,97 +1,281 @@
 use crate::cache::cache_operations::{CacheResponse, InvalidationPattern, RequestCached};
 use crate::cache::inner_cache::CachedWithCode;
 use crate::cache::Cache;
+use crate::providers::info::TOKENS_KEY;
 use crate::utils::errors::{ApiError, ApiResult};
+use futures::future::join_all;
 use rocket::response::content;
 use serde::Serialize;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+const CACHE_PREFIX: &'static str = "c_";
 const CACHE_REQS_PREFIX: &'static str = "c_reqs";
+const CACHE_REQS_FRESH_PREFIX: &'static str = "c_reqs_fresh";
+const CACHE_REQS_LOCK_PREFIX: &'static str = "c_reqs_lock";
+const CACHE_REQS_FAILED_PREFIX: &'static str = "c_reqs_failed";
 const CACHE_RESP_PREFIX: &'static str = "c_resp";
 const CACHE_REQS_RESP_PREFIX: &'static str = "c_re";
 
-pub(super) fn invalidate(cache: &impl Cache, pattern: &InvalidationPattern) {
-    let pattern_str = match pattern {
-        InvalidationPattern::FlushAll => String::from("*"),
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+// Seconds the failure of an uncached request is served to the workers that waited for it
+const LOCK_FAILURE_DURATION: usize = 1;
+const MAX_CONCURRENT_REQUESTS: usize = 10;
+
+pub(super) async fn invalidate(cache: &dyn Cache, pattern: &InvalidationPattern) {
+    match pattern {
+        // Only cached data is flushed, other entries (e.g. webhook subscriptions) are kept
+        InvalidationPattern::FlushAll => {
+            cache
+                .invalidate_pattern(&format!("{}*", CACHE_PREFIX))
+                .await;
+            cache.invalidate_pattern(&format!("{}*", TOKENS_KEY)).await;
+        }
         InvalidationPattern::RequestsResponses(value) => {
-            format!("{}*{}*", CACHE_REQS_RESP_PREFIX, &value)
+            cache
+                .invalidate_pattern(&format!("{}*{}*", CACHE_REQS_RESP_PREFIX, &value))
+                .await
         }
     };
-
-    cache.invalidate_pattern(pattern_str.as_str());
 }
 
-pub(super) fn cache_response<S>(
-    cache: &impl Cache,
+pub(super) async fn cache_response<S>(
+    cache: &dyn Cache,
     cache_response: &CacheResponse<S>,
 ) -> ApiResult<content::Json<String>>
 where
     S: Serialize,
 {
     let cache_key = format!("{}_{}", CACHE_RESP_PREFIX, cache_response.key);
-    let cached = cache.fetch(&cache_key);
+    let cached = cache.fetch(&cache_key).await;
     match cached {
         Some(value) => Ok(content::Json(value)),
         None => {
-            let resp_string = serde_json::to_string(&cache_response.generate()?)?;
-            cache.create(&cache_key, &resp_string, cache_response.duration);
+            let resp_string = serde_json::to_string(&cache_response.generate().await?)?;
+            cache
+                .create(&cache_key, &resp_string, cache_response.duration)
+                .await;
             Ok(content::Json(resp_string))
         }
     }
 }
 
-pub(super) fn request_cached(
+pub(super) async fn request_cached(
     cache: &dyn Cache,
-    client: &reqwest::blocking::Client,
+    client: &reqwest::Client,
     operation: &RequestCached,
 ) -> ApiResult<String> {
-    let cache_key = format!("{}_{}", CACHE_REQS_PREFIX, &operation.url);
-    match cache.fetch(&cache_key) {
-        Some(cached) => CachedWithCode::split(&cached).to_result(),
-        None => {
-            let mut request = client
-                .get(&operation.url)
-                .timeout(Duration::from_millis(operation.request_timeout));
-
-            let response = request.send().map_err(|err| {
-                if operation.cache_all_errors {
-                    cache.create(
-                        &cache_key,
-                        &CachedWithCode::join(500, &format!("{:?}", &err)),
-                        operation.error_cache_duration,
-                    );
+    let keys = RequestKeys::new(operation);
+    let started = Instant::now();
+    loop {
+        match lookup(cache, operation, &keys).await {
+            Lookup::Cached(result) => return result,
+            Lookup::Request(stale) => {
+                let response = send_request(client, operation).await;
+                return refresh(cache, operation, &keys, response, stale).await;
+            }
+            // Only one worker requests a given url, the others wait for it to populate the cache
+            Lookup::Locked => {
+                if started.elapsed().as_millis() >= operation.request_timeout as u128 {
+                    let response = send_request(client, operation).await;
+                    return cache_request_response(cache, operation, &keys, response).await;
                 }
-                err
-            })?;
-            let status_code = response.status().as_u16();
-
-            // Early return and no caching if the error is a 500 or greater
-            let is_server_error = response.status().is_server_error();
-            if !operation.cache_all_errors && is_server_error {
-                return Err(ApiError::from_backend_error(
-                    42,
-                    &format!("Got server error for {}", response.text()?),
-                ));
+                tokio::time::sleep(LOCK_POLL_INTERVAL).await;
             }
+        }
+    }
+}
 
-            let is_client_error = response.status().is_client_error();
-            let raw_data = response.text()?;
+// Same as `request_cached` for every operation, up to `MAX_CONCURRENT_REQUESTS` of them run
+// concurrently. Operations another worker is requesting are waited for alongside the others.
+pub(super) async fn request_cached_all(
+    cache: &dyn Cache,
+    client: &reqwest::Client,
+    operations: &[RequestCached],
+) -> Vec<ApiResult<String>> {
+    let mut results = Vec::with_capacity(operations.len());
+    for chunk in operations.chunks(MAX_CONCURRENT_REQUESTS) {
+        let requests = chunk
+            .iter()
+            .map(|operation| request_cached(cache, client, operation));
+        results.extend(join_all(requests).await);
+    }
+    results
+}
 
-            if is_client_error || is_server_error {
-                cache.create(
-                    &cache_key,
-                    &CachedWithCode::join(status_code, &raw_data),
-                    operation.error_cache_duration,
-                );
-                Err(ApiError::from_backend_error(status_code, &raw_data))
+enum Lookup {
+    // Fresh value, error or stale value another worker is refreshing
+    Cached(ApiResult<String>),
+    // The worker holds the lock and has to send the request, refreshing the stale value if any
+    Request(Option<CachedWithCode>),
+    // Another worker is sending the request
+    Locked,
+}
+
+async fn lookup(cache: &dyn Cache, operation: &RequestCached, keys: &RequestKeys) -> Lookup {
+    let lock_timeout = (operation.request_timeout / 1000 + 1) as usize;
+    // Corrupted entries are requested again like missing ones
+    let cached = cache
+        .fetch(&keys.cache_key)
+        .await
+        .and_then(|cached| CachedWithCode::split(&cached));
+    match cached {
+        Some(cached) => {
+            if cached.is_error()
+                || operation.stale_duration == 0
+                || cache.has_key(&keys.fresh_key).await
+            {
+                Lookup::Cached(cached.to_result())
+            } else if cache.lock(&keys.lock_key, lock_timeout).await {
+                Lookup::Request(Some(cached))
             } else {
-                cache.create(
-                    &cache_key,
-                    &CachedWithCode::join(status_code, &raw_data),
-                    operation.cache_duration,
-                );
-                Ok(raw_data.to_string())
+                // Stale value: only the worker holding the lock refreshes it
+                Lookup::Cached(cached.to_result())
+            }
+        }
+        None => match cache
+            .fetch(&keys.failed_key)
+            .await
+            .and_then(|failed| CachedWithCode::split(&failed))
+        {
+            Some(failed) => Lookup::Cached(failed.to_result()),
+            None if cache.lock(&keys.lock_key, lock_timeout).await => Lookup::Request(None),
+            None => Lookup::Locked,
+        },
+    }
+}
+
+// Caches the response of a request sent holding the lock and releases it. A stale value is still
+// served if the refresh fails with anything but a client error.
+async fn refresh(
+    cache: &dyn Cache,
+    operation: &RequestCached,
+    keys: &RequestKeys,
+    response: reqwest::Result<(u16, String)>,
+    stale: Option<CachedWithCode>,
+) -> ApiResult<String> {
+    let result = cache_request_response(cache, operation, keys, response).await;
+    let result = release_lock(cache, keys, result).await;
+    match (result, stale) {
+        (Err(err), Some(stale)) if !(400..500).contains(&err.status) => stale.to_result(),
+        (result, _) => result,
+    }
+}
+
+// Errors that were not cached are kept shortly, so that the workers waiting for the lock don't send
+// the failing request again one after the other
+async fn release_lock(
+    cache: &dyn Cache,
+    keys: &RequestKeys,
+    result: ApiResult<String>,
+) -> ApiResult<String> {
+    if let Err(err) = &result {
+        if cache.fetch(&keys.cache_key).await.is_none() {
+            cache
+                .create(
+                    &keys.failed_key,
+                    &CachedWithCode::join(
+                        err.status,
+                        &serde_json::to_string(&err.details).unwrap_or_default(),
+                    ),
+                    LOCK_FAILURE_DURATION,
+                )
+                .await;
+        }
+    }
+    cache.unlock(&keys.lock_key).await;
+    result
+}
+
+struct RequestKeys {
+    cache_key: String,
+    fresh_key: String,
+    lock_key: String,
+    failed_key: String,
+}
+
+impl RequestKeys {
+    fn new(operation: &RequestCached) -> Self {
+        let key = match &operation.chain_id {
+            Some(chain_id) => format!("{}_{}", chain_id, &operation.url),
+            None => operation.url.to_string(),
+        };
+        RequestKeys {
+            cache_key: format!("{}_{}", CACHE_REQS_PREFIX, &key),
+            fresh_key: format!("{}_{}", CACHE_REQS_FRESH_PREFIX, &key),
+            lock_key: format!("{}_{}", CACHE_REQS_LOCK_PREFIX, &key),
+            failed_key: format!("{}_{}", CACHE_REQS_FAILED_PREFIX, &key),
+        }
+    }
+}
+
+async fn send_request(
+    client: &reqwest::Client,
+    operation: &RequestCached,
+) -> reqwest::Result<(u16, String)> {
+    let response = client
+        .get(&operation.url)
+        .timeout(Duration::from_millis(operation.request_timeout))
+        .send()
+        .await?;
+    let status_code = response.status().as_u16();
+    Ok((status_code, response.text().await?))
+}
+
+async fn cache_request_response(
+    cache: &dyn Cache,
+    operation: &RequestCached,
+    keys: &RequestKeys,
+    response: reqwest::Result<(u16, String)>,
+) -> ApiResult<String> {
+    let (status_code, raw_data) = match response {
+        Ok(response) => response,
+        Err(err) => {
+            if operation.cache_all_errors {
+                cache
+                    .create(
+                        &keys.cache_key,
+                        &CachedWithCode::join(500, &format!("{:?}", &err)),
+                        operation.error_cache_duration,
+                    )
+                    .await;
             }
+            return Err(err.into());
+        }
+    };
+
+    // Early return and no caching if the error is a 500 or greater
+    let is_server_error = status_code >= 500;
+    if !operation.cache_all_errors && is_server_error {
+        return Err(ApiError::from_backend_error(
+            42,
+            &format!("Got server error for {}", raw_data),
+        ));
+    }
+
+    let is_client_error = (400..500).contains(&status_code);
+
+    if is_client_error || is_server_error {
+        cache
+            .create(
+                &keys.cache_key,
+                &CachedWithCode::join(status_code, &raw_data),
+                operation.error_cache_duration,
+            )
+            .await;
+        Err(ApiError::from_backend_error(status_code, &raw_data))
+    } else {
+        cache
+            .create(
+                &keys.cache_key,
+                &CachedWithCode::join(status_code, &raw_data),
+                operation.cache_duration + operation.stale_duration,
+            )
+            .await;
+        if operation.stale_duration > 0 {
+            cache
+                .create(&keys.fresh_key, "", operation.cache_duration)
+                .await;
         }
+        Ok(raw_data)
     }
 }
@@ -1,11 +1,16 @@
-use crate::cache::cache_op_executors::{cache_response, invalidate, request_cached};
+use crate::cache::cache_op_executors::{
+    cache_response, invalidate, request_cached, request_cached_all,
+};
 use crate::cache::Cache;
 use crate::config::{
     default_request_timeout, request_cache_duration, request_error_cache_duration,
+    request_stale_duration,
 };
 use crate::utils::errors::ApiResult;
+use futures::future::{FutureExt, LocalBoxFuture};
 use rocket::response::content;
 use serde::Serialize;
+use std::future::Future;
 
 pub enum Database {
     Info = 1,
@@ -35,8 +40,8 @@ impl Invalidate {
         self
     }
 
-    pub fn execute(&self, cache: &impl Cache) {
-        invalidate(cache, &self.pattern)
+    pub async fn execute(&self, cache: &dyn Cache) {
+        invalidate(cache, &self.pattern).await
     }
 }
 
@@ -47,7 +52,7 @@ where
     database: Database,
     pub key: String,
     pub duration: usize,
-    pub resp_generator: Option<Box<dyn Fn() -> ApiResult<R> + 'a>>,
+    pub resp_generator: Option<Box<dyn Fn() -> LocalBoxFuture<'a, ApiResult<R>> + 'a>>,
 }
 
 impl<'a, R> CacheResponse<'a, R>
@@ -73,25 +78,30 @@ where
         self
     }
 
-    pub fn resp_generator(&mut self, resp_generator: impl Fn() -> ApiResult<R> + 'a) -> &mut Self {
-        self.resp_generator = Some(Box::new(resp_generator));
+    pub fn resp_generator<F>(&mut self, resp_generator: impl Fn() -> F + 'a) -> &mut Self
+    where
+        F: Future<Output = ApiResult<R>> + 'a,
+    {
+        self.resp_generator = Some(Box::new(move || resp_generator().boxed_local()));
         self
     }
 
-    pub fn generate(&self) -> ApiResult<R> {
-        (self.resp_generator.as_ref().unwrap())()
+    pub async fn generate(&self) -> ApiResult<R> {
+        (self.resp_generator.as_ref().unwrap())().await
     }
 
-    pub fn execute(&self, cache: &impl Cache) -> ApiResult<content::Json<String>> {
-        cache_response(cache, self)
+    pub async fn execute(&self, cache: &dyn Cache) -> ApiResult<content::Json<String>> {
+        cache_response(cache, self).await
     }
 }
 
 pub struct RequestCached {
     database: Database,
+    pub chain_id: Option<String>,
     pub url: String,
     pub request_timeout: u64,
     pub cache_duration: usize,
+    pub stale_duration: usize,
     pub error_cache_duration: usize,
     pub cache_all_errors: bool,
 }
@@ -100,9 +110,11 @@ impl RequestCached {
     pub fn new(url: String) -> Self {
         RequestCached {
             database: Database::Default,
+            chain_id: None,
             url,
             request_timeout: default_request_timeout(),
             cache_duration: request_cache_duration(),
+            stale_duration: request_stale_duration(),
             error_cache_duration: request_error_cache_duration(),
             cache_all_errors: false,
         }
@@ -113,6 +125,11 @@ impl RequestCached {
         self
     }
 
+    pub fn chain_id(&mut self, chain_id: &str) -> &mut Self {
+        self.chain_id = Some(chain_id.to_string());
+        self
+    }
+
     pub fn request_timeout(&mut self, request_timeout: u64) -> &mut Self {
         self.request_timeout = request_timeout;
         self
@@ -123,6 +140,11 @@ impl RequestCached {
         self
     }
 
+    pub fn stale_duration(&mut self, stale_duration: usize) -> &mut Self {
+        self.stale_duration = stale_duration;
+        self
+    }
+
     pub fn error_cache_duration(&mut self, error_cache_duration: usize) -> &mut Self {
         self.error_cache_duration = error_cache_duration;
         self
@@ -133,12 +155,16 @@ impl RequestCached {
         self
     }
 
-    pub fn execute(
-        &self,
-        client: &reqwest::blocking::Client,
-        cache: &dyn Cache,
-    ) -> ApiResult<String> {
+    pub async fn execute(&self, client: &reqwest::Client, cache: &dyn Cache) -> ApiResult<String> {
         assert!(self.request_timeout > 0);
-        request_cached(cache, &client, self)
+        request_cached(cache, &client, self).await
+    }
+
+    pub async fn execute_all(
+        operations: &[RequestCached],
+        client: &reqwest::Client,
+        cache: &dyn Cache,
+    ) -> Vec<ApiResult<String>> {
+        request_cached_all(cache, client, operations).await
     }
 }
@@ -9,16 +9,12 @@ pub(super) struct CachedWithCode {
 impl CachedWithCode {
     const SEPARATOR: &'static str = ";";
 
-    pub(super) fn split(cached: &str) -> Self {
-        let cached_with_code: Vec<&str> = cached.splitn(2, CachedWithCode::SEPARATOR).collect();
-        CachedWithCode {
-            code: cached_with_code
-                .get(0)
-                .expect("Must have a status code")
-                .parse()
-                .expect("Not a valid Http code"),
-            data: cached_with_code.get(1).expect("Must have data").to_string(),
-        }
+    // `None` for values that were not written by `join`, e.g. corrupted entries
+    pub(super) fn split(cached: &str) -> Option<Self> {
+        let mut cached_with_code = cached.splitn(2, CachedWithCode::SEPARATOR);
+        let code = cached_with_code.next()?.parse().ok()?;
+        let data = cached_with_code.next()?.to_string();
+        Some(CachedWithCode { code, data })
     }
 
     pub(super) fn join(code: u16, data: &str) -> String {
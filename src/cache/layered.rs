@@ -0,0 +1,154 @@
+use crate::cache::memory::InMemoryCache;
+use crate::cache::redis::{self, ServiceCache};
+use crate::cache::Cache;
+use crate::config::local_cache_duration;
+use async_trait::async_trait;
+use futures::executor::block_on;
+use std::collections::HashMap;
+use std::future::Future;
+
+const INVALIDATION_CHANNEL: &'static str = "c_invalidation";
+
+// Keeps a short lived process local copy of the entries read from or written to redis.
+// Every write is published on `INVALIDATION_CHANNEL` so that the other instances drop their
+// local copy.
+pub struct LayeredCache {
+    local: InMemoryCache,
+    remote: ServiceCache,
+}
+
+impl LayeredCache {
+    pub fn new(local: InMemoryCache, remote: ServiceCache) -> Self {
+        LayeredCache { local, remote }
+    }
+
+    async fn publish_invalidation(&self, pattern: &str) {
+        self.remote.publish(INVALIDATION_CHANNEL, pattern).await;
+    }
+
+    // Reads from redis and keeps a local copy, unless an invalidation arrived during the read
+    async fn fetch_remote(
+        &self,
+        local_key: &str,
+        fetch: impl Future<Output = Option<String>>,
+    ) -> Option<String> {
+        let invalidation_count = self.local.invalidation_count();
+        let value = fetch.await?;
+        self.local.create_unless_invalidated_since(
+            invalidation_count,
+            local_key,
+            &value,
+            local_cache_duration(),
+        );
+        Some(value)
+    }
+}
+
+// Hash fields are stored as single entries locally so that each one expires on its own
+fn local_hash_key(hash: &str, id: &str) -> String {
+    format!("{}/{}", hash, id)
+}
+
+#[async_trait(?Send)]
+impl Cache for LayeredCache {
+    async fn fetch(&self, id: &str) -> Option<String> {
+        match self.local.fetch(id).await {
+            Some(value) => Some(value),
+            None => self.fetch_remote(id, self.remote.fetch(id)).await,
+        }
+    }
+
+    async fn create(&self, id: &str, dest: &str, timeout: usize) {
+        self.remote.create(id, dest, timeout).await;
+        self.local
+            .create(id, dest, timeout.min(local_cache_duration()))
+            .await;
+        self.publish_invalidation(id).await;
+    }
+
+    async fn insert_in_hash(&self, hash: &str, id: &str, dest: &str) {
+        let local_key = local_hash_key(hash, id);
+        self.remote.insert_in_hash(hash, id, dest).await;
+        self.local
+            .create(&local_key, dest, local_cache_duration())
+            .await;
+        self.publish_invalidation(&local_key).await;
+    }
+
+    async fn get_from_hash(&self, hash: &str, id: &str) -> Option<String> {
+        let local_key = local_hash_key(hash, id);
+        match self.local.fetch(&local_key).await {
+            Some(value) => Some(value),
+            None => {
+                self.fetch_remote(&local_key, self.remote.get_from_hash(hash, id))
+                    .await
+            }
+        }
+    }
+
+    async fn get_all_from_hash(&self, hash: &str) -> HashMap<String, String> {
+        self.remote.get_all_from_hash(hash).await
+    }
+
+    async fn remove_from_hash(&self, hash: &str, id: &str) {
+        let local_key = local_hash_key(hash, id);
+        self.remote.remove_from_hash(hash, id).await;
+        self.local.invalidate(&local_key).await;
+        self.publish_invalidation(&local_key).await;
+    }
+
+    async fn has_key(&self, id: &str) -> bool {
+        self.local.has_key(id).await || self.remote.has_key(id).await
+    }
+
+    async fn expire_entity(&self, id: &str, timeout: usize) {
+        let local_hash_keys = local_hash_key(id, "*");
+        self.remote.expire_entity(id, timeout).await;
+        self.local.invalidate(id).await;
+        self.local.invalidate_pattern(&local_hash_keys).await;
+        self.publish_invalidation(id).await;
+        self.publish_invalidation(&local_hash_keys).await;
+    }
+
+    // Locks are only kept in redis, there is no local copy to invalidate on other instances
+    async fn lock(&self, id: &str, timeout: usize) -> bool {
+        self.remote.lock(id, timeout).await
+    }
+
+    async fn unlock(&self, id: &str) {
+        self.remote.unlock(id).await;
+    }
+
+    async fn invalidate_pattern(&self, pattern: &str) {
+        self.remote.invalidate_pattern(pattern).await;
+        self.local.invalidate_pattern(pattern).await;
+        self.publish_invalidation(pattern).await;
+    }
+
+    async fn invalidate(&self, id: &str) {
+        self.remote.invalidate(id).await;
+        self.local.invalidate(id).await;
+        self.publish_invalidation(id).await;
+    }
+
+    async fn publish(&self, channel: &str, message: &str) {
+        self.remote.publish(channel, message).await;
+    }
+
+    async fn info(&self) -> Option<String> {
+        self.remote.info().await
+    }
+}
+
+// Applies the invalidations published by any instance to the local tier of this instance
+pub fn subscribe_to_invalidations(redis_url: &str, local: InMemoryCache) {
+    let flushed = local.clone();
+    redis::subscribe(
+        redis_url,
+        INVALIDATION_CHANNEL,
+        // Invalidations could have been missed while not subscribed. The in memory cache never
+        // waits, its futures complete on the first poll.
+        move || block_on(flushed.invalidate_pattern("*")),
+        move |pattern| block_on(local.invalidate_pattern(&pattern)),
+    );
+}
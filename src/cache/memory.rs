@@ -0,0 +1,363 @@
+use crate::cache::Cache;
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+// Process local implementation of the `Cache` trait, mirroring the subset of Redis semantics
+// that the gateway relies on. Expired entries are dropped when they are read and, once
+// `capacity` is reached, entries are evicted in least recently used order.
+#[derive(Clone)]
+pub struct InMemoryCache {
+    store: Arc<Mutex<Store>>,
+}
+
+struct Store {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<String, Entry>,
+    // Last access tick -> key, the first item is always the least recently used entry
+    usage: BTreeMap<u64, String>,
+    // Channel name -> senders of the listeners of that channel
+    subscribers: HashMap<String, Vec<Sender<String>>>,
+    // Incremented by every `invalidate`/`invalidate_pattern`
+    invalidations: u64,
+}
+
+struct Entry {
+    value: Value,
+    expires_at: Option<Instant>,
+    last_access: u64,
+}
+
+enum Value {
+    Single(String),
+    Hash(HashMap<String, String>),
+}
+
+impl InMemoryCache {
+    pub fn new(capacity: usize) -> Self {
+        InMemoryCache {
+            store: Arc::new(Mutex::new(Store {
+                capacity: capacity.max(1),
+                tick: 0,
+                entries: HashMap::new(),
+                usage: BTreeMap::new(),
+                subscribers: HashMap::new(),
+                invalidations: 0,
+            })),
+        }
+    }
+
+    // Receives the messages published on `channel` until the receiver is dropped
+    pub fn subscribe(&self, channel_name: &str) -> Receiver<String> {
+        let (sender, receiver) = channel();
+        self.store()
+            .subscribers
+            .entry(channel_name.to_string())
+            .or_insert_with(Vec::new)
+            .push(sender);
+        receiver
+    }
+
+    // Lets a caller that fetches a value from somewhere else detect that an invalidation happened
+    // in the meantime, see `create_unless_invalidated_since`
+    pub fn invalidation_count(&self) -> u64 {
+        self.store().invalidations
+    }
+
+    // Same as `create`, but skipped when an invalidation happened after `invalidation_count`
+    // returned `count`, the value could be older than the invalidation
+    pub fn create_unless_invalidated_since(
+        &self,
+        count: u64,
+        id: &str,
+        dest: &str,
+        timeout: usize,
+    ) {
+        let mut store = self.store();
+        if store.invalidations == count {
+            store.insert(
+                id,
+                Value::Single(dest.to_string()),
+                Some(Instant::now() + Duration::from_secs(timeout as u64)),
+            );
+        }
+    }
+
+    fn store(&self) -> MutexGuard<Store> {
+        // A poisoned lock only means another thread panicked while holding it, the data is still usable
+        self.store
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Store {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn entry(&mut self, id: &str) -> Option<&mut Entry> {
+        let is_expired = match self.entries.get(id) {
+            Some(entry) => entry
+                .expires_at
+                .map_or(false, |expires_at| expires_at <= Instant::now()),
+            None => return None,
+        };
+        if is_expired {
+            self.remove(id);
+            return None;
+        }
+        let tick = self.next_tick();
+        let entry = self.entries.get_mut(id)?;
+        self.usage.remove(&entry.last_access);
+        self.usage.insert(tick, id.to_string());
+        entry.last_access = tick;
+        Some(entry)
+    }
+
+    fn insert(&mut self, id: &str, value: Value, expires_at: Option<Instant>) {
+        self.remove(id);
+        self.evict();
+        let tick = self.next_tick();
+        self.usage.insert(tick, id.to_string());
+        self.entries.insert(
+            id.to_string(),
+            Entry {
+                value,
+                expires_at,
+                last_access: tick,
+            },
+        );
+    }
+
+    fn remove(&mut self, id: &str) {
+        if let Some(entry) = self.entries.remove(id) {
+            self.usage.remove(&entry.last_access);
+        }
+    }
+
+    // Makes room for one more entry in least recently used order, expired entries are only
+    // dropped when they are read so that a write never has to scan the whole store
+    fn evict(&mut self) {
+        while self.entries.len() >= self.capacity {
+            let least_recently_used = match self.usage.values().next() {
+                Some(key) => key.to_owned(),
+                None => break,
+            };
+            self.remove(&least_recently_used);
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Cache for InMemoryCache {
+    async fn fetch(&self, id: &str) -> Option<String> {
+        match self.store().entry(id).map(|entry| &entry.value) {
+            Some(Value::Single(value)) => Some(value.to_owned()),
+            _ => None,
+        }
+    }
+
+    async fn create(&self, id: &str, dest: &str, timeout: usize) {
+        self.store().insert(
+            id,
+            Value::Single(dest.to_string()),
+            Some(Instant::now() + Duration::from_secs(timeout as u64)),
+        );
+    }
+
+    async fn insert_in_hash(&self, hash: &str, id: &str, dest: &str) {
+        let mut store = self.store();
+        if let Some(entry) = store.entry(hash) {
+            if let Value::Hash(values) = &mut entry.value {
+                values.insert(id.to_string(), dest.to_string());
+                return;
+            }
+        }
+        let mut values = HashMap::new();
+        values.insert(id.to_string(), dest.to_string());
+        store.insert(hash, Value::Hash(values), None);
+    }
+
+    async fn get_from_hash(&self, hash: &str, id: &str) -> Option<String> {
+        match self.store().entry(hash).map(|entry| &entry.value) {
+            Some(Value::Hash(values)) => values.get(id).cloned(),
+            _ => None,
+        }
+    }
+
+    async fn get_all_from_hash(&self, hash: &str) -> HashMap<String, String> {
+        match self.store().entry(hash).map(|entry| &entry.value) {
+            Some(Value::Hash(values)) => values.clone(),
+            _ => HashMap::new(),
+        }
+    }
+
+    async fn remove_from_hash(&self, hash: &str, id: &str) {
+        let mut store = self.store();
+        let is_empty = match store.entry(hash).map(|entry| &mut entry.value) {
+            Some(Value::Hash(values)) => {
+                values.remove(id);
+                values.is_empty()
+            }
+            _ => false,
+        };
+        // Same as redis, a hash without fields does not exist
+        if is_empty {
+            store.remove(hash);
+        }
+    }
+
+    async fn has_key(&self, id: &str) -> bool {
+        self.store().entry(id).is_some()
+    }
+
+    async fn expire_entity(&self, id: &str, timeout: usize) {
+        let mut store = self.store();
+        if timeout == 0 {
+            store.remove(id);
+        } else if let Some(entry) = store.entry(id) {
+            entry.expires_at = Some(Instant::now() + Duration::from_secs(timeout as u64));
+        }
+    }
+
+    async fn lock(&self, id: &str, timeout: usize) -> bool {
+        let mut store = self.store();
+        if store.entry(id).is_some() {
+            return false;
+        }
+        store.insert(
+            id,
+            Value::Single(String::from("1")),
+            Some(Instant::now() + Duration::from_secs(timeout as u64)),
+        );
+        true
+    }
+
+    async fn unlock(&self, id: &str) {
+        self.store().remove(id);
+    }
+
+    async fn invalidate_pattern(&self, pattern: &str) {
+        let mut store = self.store();
+        store.invalidations += 1;
+        let keys: Vec<String> = store
+            .entries
+            .keys()
+            .filter(|key| glob_match(pattern, key))
+            .cloned()
+            .collect();
+        keys.iter().for_each(|key| store.remove(key));
+    }
+
+    async fn invalidate(&self, id: &str) {
+        let mut store = self.store();
+        store.invalidations += 1;
+        store.remove(id);
+    }
+
+    async fn publish(&self, channel: &str, message: &str) {
+        if let Some(senders) = self.store().subscribers.get_mut(channel) {
+            // Listeners that dropped their receiver are removed
+            senders.retain(|sender| sender.send(message.to_string()).is_ok());
+        }
+    }
+
+    async fn info(&self) -> Option<String> {
+        let store = self.store();
+        Some(format!(
+            "# In memory cache\r\nkeys:{}\r\ncapacity:{}\r\n",
+            store.entries.len(),
+            store.capacity
+        ))
+    }
+}
+
+// Glob style matching with the same rules as the Redis `KEYS`/`SCAN MATCH` patterns:
+// `*`, `?`, `[...]` (with `^` negation and `a-z` ranges) and `\` escapes
+pub(super) fn glob_match(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+    glob_match_chars(&pattern, &value)
+}
+
+fn glob_match_chars(pattern: &[char], value: &[char]) -> bool {
+    let (mut p, mut v) = (0, 0);
+    // Position to resume from when the last `*` has to consume one more character
+    let mut backtrack: Option<(usize, usize)> = None;
+    while v < value.len() {
+        let step = match pattern.get(p).copied() {
+            Some('*') => {
+                backtrack = Some((p, v));
+                p += 1;
+                continue;
+            }
+            Some('?') => Some(1),
+            Some('[') => match_class(&pattern[p..], value[v]),
+            Some('\\') if p + 1 < pattern.len() => {
+                if pattern[p + 1] == value[v] {
+                    Some(2)
+                } else {
+                    None
+                }
+            }
+            Some(c) if c == value[v] => Some(1),
+            _ => None,
+        };
+        match step {
+            Some(pattern_len) => {
+                p += pattern_len;
+                v += 1;
+            }
+            None => match backtrack {
+                Some((star_p, star_v)) => {
+                    backtrack = Some((star_p, star_v + 1));
+                    p = star_p + 1;
+                    v = star_v + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+// Returns the length of the `[...]` class in the pattern if `c` matches it
+fn match_class(pattern: &[char], c: char) -> Option<usize> {
+    let mut i = 1;
+    let negate = pattern.get(i) == Some(&'^');
+    if negate {
+        i += 1;
+    }
+    let mut matched = false;
+    while i < pattern.len() && pattern[i] != ']' {
+        if pattern[i] == '\\' && i + 1 < pattern.len() {
+            matched |= pattern[i + 1] == c;
+            i += 2;
+        } else if i + 2 < pattern.len() && pattern[i + 1] == '-' && pattern[i + 2] != ']' {
+            let (start, end) = if pattern[i] <= pattern[i + 2] {
+                (pattern[i], pattern[i + 2])
+            } else {
+                (pattern[i + 2], pattern[i])
+            };
+            matched |= start <= c && c <= end;
+            i += 3;
+        } else {
+            matched |= pattern[i] == c;
+            i += 1;
+        }
+    }
+    if i >= pattern.len() {
+        // Unterminated class, Redis treats the rest of the pattern as the class
+        return if matched != negate { Some(i) } else { None };
+    }
+    if matched != negate {
+        Some(i + 1)
+    } else {
+        None
+    }
+}
@@ -1,22 +1,36 @@
 mod cache_op_executors;
 pub mod cache_operations;
 mod inner_cache;
+pub mod layered;
+pub mod memory;
 pub mod redis;
 
 #[cfg(test)]
 mod tests;
 
+use async_trait::async_trait;
 use mockall::automock;
+use std::collections::HashMap;
 
+// Futures are not `Send`, they run on the thread of the request (see `Context::block_on`)
 #[automock]
+#[async_trait(?Send)]
 pub trait Cache {
-    fn fetch(&self, id: &str) -> Option<String>;
-    fn create(&self, id: &str, dest: &str, timeout: usize);
-    fn insert_in_hash(&self, hash: &str, id: &str, dest: &str);
-    fn get_from_hash(&self, hash: &str, id: &str) -> Option<String>;
-    fn has_key(&self, id: &str) -> bool;
-    fn expire_entity(&self, id: &str, timeout: usize);
-    fn invalidate_pattern(&self, pattern: &str);
-    fn invalidate(&self, id: &str);
-    fn info(&self) -> Option<String>;
+    async fn fetch(&self, id: &str) -> Option<String>;
+    async fn create(&self, id: &str, dest: &str, timeout: usize);
+    async fn insert_in_hash(&self, hash: &str, id: &str, dest: &str);
+    async fn get_from_hash(&self, hash: &str, id: &str) -> Option<String>;
+    async fn get_all_from_hash(&self, hash: &str) -> HashMap<String, String>;
+    async fn remove_from_hash(&self, hash: &str, id: &str);
+    async fn has_key(&self, id: &str) -> bool;
+    async fn expire_entity(&self, id: &str, timeout: usize);
+    // Creates the entry only if it does not exist yet, returns whether it was created
+    async fn lock(&self, id: &str, timeout: usize) -> bool;
+    // Releases a lock taken with `lock`, unlike `invalidate` nothing is broadcast
+    async fn unlock(&self, id: &str);
+    async fn invalidate_pattern(&self, pattern: &str);
+    async fn invalidate(&self, id: &str);
+    // Sends the message to every listener of the channel, on every instance when backed by redis
+    async fn publish(&self, channel: &str, message: &str);
+    async fn info(&self) -> Option<String>;
 }
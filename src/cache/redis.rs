@@ -1,67 +1,126 @@
 use crate::cache::Cache;
 use crate::config::redis_scan_count;
+use async_trait::async_trait;
 use rocket_contrib::databases::redis::{
     self, pipe, Commands, FromRedisValue, Iter, PipelineCommands, ToRedisArgs,
 };
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::thread;
+use std::time::Duration;
 
 #[database("service_cache")]
 pub struct ServiceCache(redis::Connection);
 
-impl Cache for ServiceCache {
-    fn fetch(&self, id: &str) -> Option<String> {
-        match self.get(id) {
-            Ok(value) => Some(value),
-            _ => None,
-        }
-    }
+// Connection that is not managed by rocket, for work done outside of a request
+pub struct RedisCache(redis::Connection);
 
-    fn create(&self, id: &str, dest: &str, timeout: usize) {
-        let _: () = self.set_ex(id, dest, timeout).unwrap();
+impl RedisCache {
+    pub fn open(url: &str) -> redis::RedisResult<Self> {
+        Ok(RedisCache(redis::Client::open(url)?.get_connection()?))
     }
 
-    fn insert_in_hash(&self, hash: &str, id: &str, dest: &str) {
-        let _: () = self.hset(hash, id, dest).unwrap();
+    pub fn is_open(&self) -> bool {
+        redis::cmd("PING").query::<String>(&self.0).is_ok()
     }
+}
 
-    fn get_from_hash(&self, hash: &str, id: &str) -> Option<String> {
-        self.hget(hash, id).ok()
-    }
+impl Deref for RedisCache {
+    type Target = redis::Connection;
 
-    fn has_key(&self, id: &str) -> bool {
-        let result: Option<usize> = self.exists(id).ok();
-        result.map(|it| it != 0).unwrap_or(false)
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
+}
 
-    fn expire_entity(&self, id: &str, timeout: usize) {
-        let _: () = self.expire(id, timeout).unwrap();
-    }
+// Commands are sent on the blocking connection of the pool, they complete in a fraction of the time
+// of the upstream requests the other futures of the request wait for
+macro_rules! impl_redis_cache {
+    ($cache:ty) => {
+        #[async_trait(?Send)]
+        impl Cache for $cache {
+            async fn fetch(&self, id: &str) -> Option<String> {
+                match self.get(id) {
+                    Ok(value) => Some(value),
+                    _ => None,
+                }
+            }
 
-    fn invalidate_pattern(&self, pattern: &str) {
-        pipeline_delete(self, scan_match_count(self, pattern, redis_scan_count()));
-    }
+            async fn create(&self, id: &str, dest: &str, timeout: usize) {
+                log_failure("SETEX", id, self.set_ex(id, dest, timeout));
+            }
 
-    fn invalidate(&self, id: &str) {
-        let _: () = self.del(id).unwrap();
-    }
+            async fn insert_in_hash(&self, hash: &str, id: &str, dest: &str) {
+                log_failure("HSET", hash, self.hset(hash, id, dest));
+            }
 
-    fn info(&self) -> Option<String> {
-        info(self)
-    }
+            async fn get_from_hash(&self, hash: &str, id: &str) -> Option<String> {
+                self.hget(hash, id).ok()
+            }
+
+            async fn get_all_from_hash(&self, hash: &str) -> HashMap<String, String> {
+                self.hgetall(hash).unwrap_or_default()
+            }
+
+            async fn remove_from_hash(&self, hash: &str, id: &str) {
+                log_failure("HDEL", hash, self.hdel(hash, id));
+            }
+
+            async fn has_key(&self, id: &str) -> bool {
+                let result: Option<usize> = self.exists(id).ok();
+                result.map(|it| it != 0).unwrap_or(false)
+            }
+
+            async fn expire_entity(&self, id: &str, timeout: usize) {
+                log_failure("EXPIRE", id, self.expire(id, timeout));
+            }
+
+            async fn lock(&self, id: &str, timeout: usize) -> bool {
+                set_if_absent(self, id, timeout)
+            }
+
+            async fn unlock(&self, id: &str) {
+                log_failure("DEL", id, self.del(id));
+            }
+
+            async fn invalidate_pattern(&self, pattern: &str) {
+                match scan_match_count(self, pattern, redis_scan_count()) {
+                    Ok(keys) => pipeline_delete(self, keys),
+                    Err(err) => log::error!("Redis SCAN of {} failed: {}", pattern, err),
+                }
+            }
+
+            async fn invalidate(&self, id: &str) {
+                log_failure("DEL", id, self.del(id));
+            }
+
+            async fn publish(&self, channel: &str, message: &str) {
+                publish(self, channel, message)
+            }
+
+            async fn info(&self) -> Option<String> {
+                info(self)
+            }
+        }
+    };
 }
 
+impl_redis_cache!(ServiceCache);
+impl_redis_cache!(RedisCache);
+
 fn pipeline_delete(con: &redis::Connection, keys: Iter<String>) {
     let pipeline = &mut pipe();
     for key in keys {
         pipeline.del(key);
     }
-    pipeline.execute(con);
+    log_failure("DEL", "pipeline", pipeline.query(con));
 }
 
 fn scan_match_count<P: ToRedisArgs, C: ToRedisArgs, RV: FromRedisValue>(
     con: &redis::Connection,
     pattern: P,
     count: C,
-) -> redis::Iter<RV> {
+) -> redis::RedisResult<redis::Iter<RV>> {
     redis::cmd("SCAN")
         .cursor_arg(0)
         .arg("MATCH")
@@ -69,9 +128,75 @@ fn scan_match_count<P: ToRedisArgs, C: ToRedisArgs, RV: FromRedisValue>(
         .arg("COUNT")
         .arg(count)
         .iter(con)
-        .unwrap()
+}
+
+// A failed cache write only costs a cache miss later on, so it doesn't fail the request
+fn log_failure(command: &str, key: &str, result: redis::RedisResult<()>) {
+    if let Err(err) = result {
+        log::error!("Redis {} of {} failed: {}", command, key, err);
+    }
+}
+
+fn publish(con: &redis::Connection, channel: &str, message: &str) {
+    let result: redis::RedisResult<()> = con.publish(channel, message);
+    if let Err(err) = result {
+        log::error!("Could not publish to {}: {}", channel, err);
+    }
+}
+
+// Passes every message published on `channel` to `on_message`, resubscribing when the connection
+// drops. `on_subscribe` is called on every (re)subscription, as messages could have been missed.
+pub fn subscribe<S, M>(redis_url: &str, channel: &'static str, on_subscribe: S, on_message: M)
+where
+    S: Fn() + Send + 'static,
+    M: Fn(String) + Send + 'static,
+{
+    let redis_url = redis_url.to_string();
+    thread::spawn(move || loop {
+        if let Err(err) = listen(&redis_url, channel, &on_subscribe, &on_message) {
+            log::error!("Subscription to {} failed: {}", channel, err);
+        }
+        thread::sleep(Duration::from_secs(1));
+    });
+}
+
+fn listen(
+    redis_url: &str,
+    channel: &str,
+    on_subscribe: &dyn Fn(),
+    on_message: &dyn Fn(String),
+) -> redis::RedisResult<()> {
+    let mut pubsub = redis::Client::open(redis_url)?.get_pubsub()?;
+    pubsub.subscribe(channel)?;
+    on_subscribe();
+    loop {
+        on_message(pubsub.get_message()?.get_payload()?);
+    }
 }
 
 fn info(con: &redis::Connection) -> Option<String> {
     redis::cmd("INFO").query(con).ok()
 }
+
+fn set_if_absent(con: &redis::Connection, id: &str, timeout: usize) -> bool {
+    let result = redis::cmd("SET")
+        .arg(id)
+        .arg(1)
+        .arg("NX")
+        .arg("EX")
+        .arg(timeout)
+        .query(con);
+    is_lock_acquired(id, result)
+}
+
+// A failing SET is treated as acquired, otherwise every worker would wait for a lock that nobody
+// holds and the request would not be sent until the request timeout
+pub(super) fn is_lock_acquired(id: &str, result: redis::RedisResult<Option<String>>) -> bool {
+    match result {
+        Ok(value) => value.is_some(),
+        Err(err) => {
+            log::error!("Redis SET NX of {} failed: {}", id, err);
+            true
+        }
+    }
+}
@@ -11,19 +11,20 @@ fn cache_with_code_split_success() {
         data: String::from("123"),
     };
 
-    assert_eq!(cached_with_code, expected);
+    assert_eq!(cached_with_code, Some(expected));
 }
 
 #[test]
-#[should_panic]
 fn cache_with_code_split_failure_parse() {
-    CachedWithCode::split("400A;123");
+    assert_eq!(CachedWithCode::split("400A;123"), None);
 }
 
 #[test]
-#[should_panic]
 fn cache_with_code_split_failure_not_enough_parts() {
-    CachedWithCode::split("400MissingSeparatorForSomeReason");
+    assert_eq!(
+        CachedWithCode::split("400MissingSeparatorForSomeReason"),
+        None
+    );
 }
 
 #[test]
@@ -34,7 +35,7 @@ fn cache_with_code_split_multiple_separators() {
     };
     let actual = CachedWithCode::split("404;foo;bar");
 
-    assert_eq!(actual, expected);
+    assert_eq!(actual, Some(expected));
 }
 
 #[test]
@@ -45,7 +46,7 @@ fn cache_with_code_split_data_is_only_separators() {
     };
     let actual = CachedWithCode::split("404;;;;;;");
 
-    assert_eq!(actual, expected);
+    assert_eq!(actual, Some(expected));
 }
 
 #[test]
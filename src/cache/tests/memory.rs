@@ -0,0 +1,311 @@
+use crate::cache::cache_operations::{
+    CacheResponse, Invalidate, InvalidationPattern, RequestCached,
+};
+use crate::cache::memory::{glob_match, InMemoryCache};
+use crate::cache::Cache;
+use crate::utils::errors::{ApiError, ErrorDetails};
+use std::cell::Cell;
+
+#[tokio::test]
+async fn in_memory_cache_create_and_fetch() {
+    let cache = InMemoryCache::new(10);
+    cache.create("key", "value", 60).await;
+
+    assert_eq!(cache.fetch("key").await, Some(String::from("value")));
+    assert!(cache.has_key("key").await);
+    assert_eq!(cache.fetch("missing").await, None);
+    assert!(!cache.has_key("missing").await);
+}
+
+#[tokio::test]
+async fn in_memory_cache_expired_entries_are_not_returned() {
+    let cache = InMemoryCache::new(10);
+    cache.create("key", "value", 0).await;
+
+    assert_eq!(cache.fetch("key").await, None);
+    assert!(!cache.has_key("key").await);
+}
+
+#[tokio::test]
+async fn in_memory_cache_expire_entity_with_zero_removes_entry() {
+    let cache = InMemoryCache::new(10);
+    cache.insert_in_hash("hash", "id", "value").await;
+    cache.expire_entity("hash", 0).await;
+
+    assert_eq!(cache.get_from_hash("hash", "id").await, None);
+}
+
+#[tokio::test]
+async fn in_memory_cache_expire_entity_updates_timeout() {
+    let cache = InMemoryCache::new(10);
+    cache.insert_in_hash("hash", "id", "value").await;
+    cache.expire_entity("hash", 60).await;
+
+    assert_eq!(
+        cache.get_from_hash("hash", "id").await,
+        Some(String::from("value"))
+    );
+}
+
+#[tokio::test]
+async fn in_memory_cache_hash_operations() {
+    let cache = InMemoryCache::new(10);
+    cache.insert_in_hash("hash", "first", "1").await;
+    cache.insert_in_hash("hash", "second", "2").await;
+    cache.insert_in_hash("hash", "first", "3").await;
+
+    assert_eq!(
+        cache.get_from_hash("hash", "first").await,
+        Some(String::from("3"))
+    );
+    assert_eq!(
+        cache.get_from_hash("hash", "second").await,
+        Some(String::from("2"))
+    );
+    assert_eq!(cache.get_from_hash("hash", "third").await, None);
+    assert_eq!(cache.fetch("hash").await, None);
+}
+
+#[tokio::test]
+async fn in_memory_cache_evicts_least_recently_used() {
+    let cache = InMemoryCache::new(2);
+    cache.create("first", "1", 60).await;
+    cache.create("second", "2", 60).await;
+    cache.fetch("first").await;
+    cache.create("third", "3", 60).await;
+
+    assert_eq!(cache.fetch("first").await, Some(String::from("1")));
+    assert_eq!(cache.fetch("second").await, None);
+    assert_eq!(cache.fetch("third").await, Some(String::from("3")));
+}
+
+#[tokio::test]
+async fn in_memory_cache_invalidate_pattern() {
+    let cache = InMemoryCache::new(10);
+    cache.create("c_reqs_0x1234_details", "1", 60).await;
+    cache.create("c_resp_0x1234", "2", 60).await;
+    cache.create("c_resp_0x5678", "3", 60).await;
+
+    Invalidate::new(InvalidationPattern::RequestsResponses(String::from(
+        "0x1234",
+    )))
+    .execute(&cache)
+    .await;
+
+    assert_eq!(cache.fetch("c_reqs_0x1234_details").await, None);
+    assert_eq!(cache.fetch("c_resp_0x1234").await, None);
+    assert_eq!(cache.fetch("c_resp_0x5678").await, Some(String::from("3")));
+
+    cache.invalidate("c_resp_0x5678").await;
+    assert_eq!(cache.fetch("c_resp_0x5678").await, None);
+}
+
+#[tokio::test]
+async fn in_memory_cache_skips_values_read_before_an_invalidation() {
+    let cache = InMemoryCache::new(10);
+    let invalidation_count = cache.invalidation_count();
+    cache.invalidate_pattern("c_reqs_*").await;
+    cache.create_unless_invalidated_since(invalidation_count, "c_reqs_stale", "1", 60);
+    cache.create_unless_invalidated_since(cache.invalidation_count(), "c_reqs_fresh", "2", 60);
+
+    assert_eq!(cache.fetch("c_reqs_stale").await, None);
+    assert_eq!(cache.fetch("c_reqs_fresh").await, Some(String::from("2")));
+}
+
+#[test]
+fn glob_match_patterns() {
+    assert!(glob_match("*", ""));
+    assert!(glob_match("*", "anything"));
+    assert!(glob_match("c_re*0x12*", "c_resp_0x1234"));
+    assert!(!glob_match("c_re*0x12*", "c_resp_0x5678"));
+    assert!(glob_match("h?llo", "hello"));
+    assert!(!glob_match("h?llo", "hllo"));
+    assert!(glob_match("h[ae]llo", "hallo"));
+    assert!(!glob_match("h[ae]llo", "hillo"));
+    assert!(glob_match("h[^e]llo", "hallo"));
+    assert!(!glob_match("h[^e]llo", "hello"));
+    assert!(glob_match("h[a-c]llo", "hbllo"));
+    assert!(!glob_match("h[a-c]llo", "hdllo"));
+    assert!(glob_match("h\\*llo", "h*llo"));
+    assert!(!glob_match("h\\*llo", "hello"));
+}
+
+#[tokio::test]
+async fn cache_response_generates_once() {
+    let cache = InMemoryCache::new(10);
+    let calls = Cell::new(0);
+    let mut cache_response = CacheResponse::new(String::from("key"));
+    cache_response.resp_generator(|| {
+        calls.set(calls.get() + 1);
+        async { Ok(vec![1, 2, 3]) }
+    });
+
+    let first = cache_response.execute(&cache).await.unwrap();
+    let second = cache_response.execute(&cache).await.unwrap();
+
+    assert_eq!(first.0, "[1,2,3]");
+    assert_eq!(second.0, "[1,2,3]");
+    assert_eq!(calls.get(), 1);
+    assert_eq!(
+        cache.fetch("c_resp_key").await,
+        Some(String::from("[1,2,3]"))
+    );
+}
+
+#[tokio::test]
+async fn request_cached_served_from_cache() {
+    let cache = InMemoryCache::new(10);
+    let client = reqwest::Client::new();
+    let url = "http://localhost:0/api/v1/safes/";
+    cache
+        .create(&format!("c_reqs_4_{}", url), "200;cached", 60)
+        .await;
+
+    let actual = RequestCached::new(String::from(url))
+        .chain_id("4")
+        .execute(&client, &cache)
+        .await
+        .unwrap();
+
+    assert_eq!(actual, "cached");
+}
+
+#[tokio::test]
+async fn request_cached_error_served_from_cache() {
+    let cache = InMemoryCache::new(10);
+    let client = reqwest::Client::new();
+    let url = "http://localhost:0/api/v1/safes/";
+    cache
+        .create(&format!("c_reqs_{}", url), "404;Not found", 60)
+        .await;
+    let expected = ApiError {
+        status: 404,
+        details: ErrorDetails {
+            code: 42,
+            message: Some(String::from("Not found")),
+            arguments: None,
+        },
+    };
+
+    let actual = RequestCached::new(String::from(url))
+        .execute(&client, &cache)
+        .await;
+
+    assert_eq!(actual.expect_err(""), expected);
+}
+
+#[tokio::test]
+async fn in_memory_cache_lock() {
+    let cache = InMemoryCache::new(10);
+
+    assert!(cache.lock("lock", 60).await);
+    assert!(!cache.lock("lock", 60).await);
+    cache.unlock("lock").await;
+    assert!(cache.lock("lock", 60).await);
+}
+
+#[tokio::test]
+async fn request_cached_stale_served_while_locked() {
+    let cache = InMemoryCache::new(10);
+    let client = reqwest::Client::new();
+    let url = "http://localhost:0/api/v1/safes/";
+    cache
+        .create(&format!("c_reqs_{}", url), "200;stale", 60)
+        .await;
+    cache.lock(&format!("c_reqs_lock_{}", url), 60).await;
+
+    let actual = RequestCached::new(String::from(url))
+        .stale_duration(60)
+        .execute(&client, &cache)
+        .await
+        .unwrap();
+
+    assert_eq!(actual, "stale");
+}
+
+#[tokio::test]
+async fn request_cached_stale_served_when_refresh_fails() {
+    let cache = InMemoryCache::new(10);
+    let client = reqwest::Client::new();
+    let url = "http://localhost:0/api/v1/safes/";
+    cache
+        .create(&format!("c_reqs_{}", url), "200;stale", 60)
+        .await;
+
+    let actual = RequestCached::new(String::from(url))
+        .stale_duration(60)
+        .execute(&client, &cache)
+        .await
+        .unwrap();
+
+    assert_eq!(actual, "stale");
+    assert!(!cache.has_key(&format!("c_reqs_lock_{}", url)).await);
+}
+
+#[tokio::test]
+async fn request_cached_corrupted_entry_requested_again() {
+    let cache = InMemoryCache::new(10);
+    let client = reqwest::Client::new();
+    let url = "http://localhost:0/api/v1/safes/";
+    cache
+        .create(&format!("c_reqs_{}", url), "corrupted", 60)
+        .await;
+
+    let actual = RequestCached::new(String::from(url))
+        .execute(&client, &cache)
+        .await;
+
+    assert!(actual.is_err());
+    assert!(!cache.has_key(&format!("c_reqs_lock_{}", url)).await);
+}
+
+#[tokio::test]
+async fn request_cached_failure_served_to_waiters() {
+    let cache = InMemoryCache::new(10);
+    let client = reqwest::Client::new();
+    let url = "http://localhost:0/api/v1/safes/";
+    let mut operation = RequestCached::new(String::from(url));
+    operation.request_timeout(10000);
+
+    let holder_error = operation.execute(&client, &cache).await.unwrap_err();
+    assert!(!cache.has_key(&format!("c_reqs_lock_{}", url)).await);
+    assert!(cache.has_key(&format!("c_reqs_failed_{}", url)).await);
+
+    // Another worker holds the lock, the failure is served instead of waiting for it
+    cache.lock(&format!("c_reqs_lock_{}", url), 60).await;
+    let started = std::time::Instant::now();
+    let waiter_error = operation.execute(&client, &cache).await.unwrap_err();
+
+    assert!(started.elapsed().as_millis() < 10000);
+    assert_eq!(waiter_error.status, holder_error.status);
+    assert_eq!(waiter_error.details, holder_error.details);
+}
+
+#[tokio::test]
+async fn request_cached_all_stale_served_while_locked_or_refresh_fails() {
+    let cache = InMemoryCache::new(10);
+    let client = reqwest::Client::new();
+    let locked_url = "http://localhost:0/api/v1/safes/";
+    let failing_url = "http://localhost:0/api/v1/tokens/";
+    cache
+        .create(&format!("c_reqs_{}", locked_url), "200;locked", 60)
+        .await;
+    cache.lock(&format!("c_reqs_lock_{}", locked_url), 60).await;
+    cache
+        .create(&format!("c_reqs_{}", failing_url), "200;failing", 60)
+        .await;
+    let mut operations = vec![
+        RequestCached::new(String::from(locked_url)),
+        RequestCached::new(String::from(failing_url)),
+    ];
+    for operation in operations.iter_mut() {
+        operation.stale_duration(60);
+    }
+
+    let actual = RequestCached::execute_all(&operations, &client, &cache).await;
+
+    assert_eq!(actual[0].as_ref().unwrap(), "locked");
+    assert_eq!(actual[1].as_ref().unwrap(), "failing");
+    assert!(cache.has_key(&format!("c_reqs_lock_{}", locked_url)).await);
+    assert!(!cache.has_key(&format!("c_reqs_lock_{}", failing_url)).await);
+}
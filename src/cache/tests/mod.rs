@@ -1 +1,3 @@
 mod cache;
+mod memory;
+mod redis;
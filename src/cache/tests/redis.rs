@@ -0,0 +1,53 @@
+use crate::cache::cache_operations::RequestCached;
+use crate::cache::redis::is_lock_acquired;
+use crate::cache::MockCache;
+use mockall::predicate::*;
+use rocket_contrib::databases::redis::{ErrorKind, RedisError};
+use std::time::{Duration, Instant};
+
+#[test]
+fn lock_acquired_when_set_fails() {
+    let error = RedisError::from((ErrorKind::IoError, "Connection refused"));
+
+    assert!(is_lock_acquired("c_reqs_lock_key", Err(error)));
+    assert!(is_lock_acquired(
+        "c_reqs_lock_key",
+        Ok(Some(String::from("OK")))
+    ));
+    assert!(!is_lock_acquired("c_reqs_lock_key", Ok(None)));
+}
+
+#[tokio::test]
+async fn request_cached_sent_when_lock_fails() {
+    let url = "http://localhost:0/api/v1/safes/";
+    let error = RedisError::from((ErrorKind::IoError, "Connection refused"));
+    let lock_acquired = is_lock_acquired("c_reqs_lock_key", Err(error));
+
+    let mut mock_cache = MockCache::new();
+    mock_cache.expect_fetch().returning(|_| None);
+    mock_cache
+        .expect_lock()
+        .times(1)
+        .with(eq(format!("c_reqs_lock_{}", url)), always())
+        .return_const(lock_acquired);
+    // The failed request is kept for the other workers and the lock is released
+    mock_cache
+        .expect_create()
+        .times(1)
+        .with(eq(format!("c_reqs_failed_{}", url)), always(), always())
+        .return_const(());
+    mock_cache
+        .expect_unlock()
+        .times(1)
+        .with(eq(format!("c_reqs_lock_{}", url)))
+        .return_const(());
+
+    let started = Instant::now();
+    let actual = RequestCached::new(String::from(url))
+        .request_timeout(10000)
+        .execute(&reqwest::Client::new(), &mock_cache)
+        .await;
+
+    assert!(actual.is_err());
+    assert!(started.elapsed() < Duration::from_millis(10000));
+}
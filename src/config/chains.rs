@@ -0,0 +1,33 @@
+use crate::config::chains_config_path;
+use crate::models::service::chains::ChainInfo;
+use crate::utils::errors::ApiResult;
+use lazy_static::lazy_static;
+use std::fs;
+
+lazy_static! {
+    // Loading failures are reported by `config::validate` on boot
+    static ref CHAINS: Vec<ChainInfo> = load_chains().unwrap_or_default();
+}
+
+pub fn chain_config(chain_id: &str) -> ApiResult<&'static ChainInfo> {
+    CHAINS
+        .iter()
+        .find(|chain| chain.chain_id == chain_id)
+        .ok_or(client_error!(404, "Chain not supported"))
+}
+
+pub fn chains() -> &'static Vec<ChainInfo> {
+    &CHAINS
+}
+
+pub(super) fn check_chains() -> Result<(), String> {
+    load_chains().map(|_| ())
+}
+
+fn load_chains() -> Result<Vec<ChainInfo>, String> {
+    let path = chains_config_path();
+    let content = fs::read_to_string(&path)
+        .map_err(|err| format!("Could not read chains config {}: {}", &path, err))?;
+    serde_json::from_str(&content)
+        .map_err(|err| format!("Invalid chains config {}: {}", &path, err))
+}
@@ -1,44 +1,161 @@
+use crate::config::chains::{chain_config, check_chains};
+use crate::utils::errors::{ApiError, ApiResult};
+use rocket_contrib::databases::database_config;
 use std::env;
+use std::fmt::Display;
+use std::str::FromStr;
+
+pub mod chains;
+
+#[cfg(test)]
+mod tests;
+
+// Name of the redis database in the rocket config (`ROCKET_DATABASES`)
+const SERVICE_CACHE_DATABASE: &str = "service_cache";
+const REQUIRED_VARS: &[&str] = &["WEBHOOK_TOKEN", "EXCHANGE_API_BASE_URL", "EXCHANGE_API_KEY"];
+const USIZE_VARS: &[&str] = &[
+    "INDEFINITE_TIMEOUT",
+    "SHORT_ERROR_DURATION",
+    "LONG_ERROR_DURATION",
+    "SAFE_INFO_CACHE_DURATION",
+    "ADDRESS_INFO_CACHE_DURATION",
+    "TOKEN_INFO_CACHE_DURATION",
+    "EXCHANGE_API_CACHE_DURATION",
+    "LOCAL_CACHE_DURATION",
+    "REQUEST_CACHE_DURATION",
+    "REQUEST_STALE_DURATION",
+    "ABOUT_CACHE_DURATION",
+    "BALANCES_REQUEST_CACHE_DURATION",
+    "SAFE_APP_MANIFEST_CACHE_DURATION",
+    "WEBHOOK_DELIVERIES_DURATION",
+    "WEBHOOK_DELIVERY_BACKOFF",
+    "REQS_ERROR_CACHE_DURATION",
+    "IN_MEMORY_CACHE_CAPACITY",
+    "REDIS_SCAN_COUNT",
+    "RPC_CACHE_DURATION",
+    "OWNER_SAFES_CACHE_DURATION",
+    "MAX_EVENT_SUBSCRIBERS",
+];
+const U64_VARS: &[&str] = &[
+    "INTERNAL_CLIENT_CONNECT_TIMEOUT",
+    "SAFE_APP_INFO_REQUEST_TIMEOUT",
+    "TRANSACTION_REQUEST_TIMEOUT",
+    "SAFE_INFO_REQUEST_TIMEOUT",
+    "TOKEN_INFO_REQUEST_TIMEOUT",
+    "BALANCES_REQUEST_TIMEOUT",
+    "COLLECTIBLES_REQUEST_TIMEOUT",
+    "WEBHOOK_DELIVERY_TIMEOUT",
+    "PUSH_NOTIFICATION_REQUEST_TIMEOUT",
+    "RPC_REQUEST_TIMEOUT",
+    "DEFAULT_REQUEST_TIMEOUT",
+    "WEBHOOK_DELIVERY_MAX_ATTEMPTS",
+];
+const BOOL_VARS: &[&str] = &[
+    "LOG_ALL_ERROR_RESPONSES",
+    "IN_MEMORY_CACHE",
+    "LOCAL_CACHE",
+    "SAFE_INFO_RPC_CHECK",
+    "SAFE_INFO_FROM_RPC",
+];
+
+// Lists every problem of the configuration, so that the gateway refuses to start with all of them
+// reported at once instead of failing on the first request that reads a broken value
+pub fn validate(rocket_config: &rocket::Config) -> Result<(), Vec<String>> {
+    let mut problems = vec![];
+    for key in REQUIRED_VARS {
+        if env::var(key).is_err() {
+            problems.push(format!("{} is not set", key));
+        }
+    }
+    check_vars::<usize>(USIZE_VARS, &mut problems);
+    check_vars::<u64>(U64_VARS, &mut problems);
+    check_vars::<bool>(BOOL_VARS, &mut problems);
+    if let Err(problem) = check_chains() {
+        problems.push(problem);
+    }
+    // Redis is optional with the in memory cache, it only keeps the webhook subscriptions then
+    if !in_memory_cache() && redis_url(rocket_config).is_none() {
+        problems.push(format!(
+            "The {} database is not configured",
+            SERVICE_CACHE_DATABASE
+        ));
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems)
+    }
+}
+
+fn check_vars<T>(keys: &[&str], problems: &mut Vec<String>)
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    for key in keys {
+        if let Ok(value) = env::var(key) {
+            if let Err(err) = value.parse::<T>() {
+                problems.push(format!("{} has an invalid value {:?}: {}", key, value, err));
+            }
+        }
+    }
+}
 
-pub fn base_transaction_service_url() -> String {
-    format!("{}{}", env::var("TRANSACTION_SERVICE_URL").unwrap(), "/api")
+pub fn base_transaction_service_url(chain_id: &str) -> ApiResult<String> {
+    Ok(format!(
+        "{}{}",
+        chain_config(chain_id)?.transaction_service,
+        "/api"
+    ))
 }
 
-pub fn base_exchange_api_url() -> String {
-    format!(
+pub fn base_exchange_api_url() -> ApiResult<String> {
+    Ok(format!(
         "{}?access_key={}",
-        env::var("EXCHANGE_API_BASE_URL").unwrap(),
-        env::var("EXCHANGE_API_KEY").unwrap()
-    )
+        required_var("EXCHANGE_API_BASE_URL")?,
+        required_var("EXCHANGE_API_KEY")?
+    ))
 }
 
 pub fn webhook_token() -> String {
-    env::var("WEBHOOK_TOKEN").unwrap()
+    env::var("WEBHOOK_TOKEN").unwrap_or_default()
+}
+
+pub fn chains_config_path() -> String {
+    env::var("CHAINS_CONFIG_PATH").unwrap_or(String::from("chains.json"))
 }
 
 pub fn scheme() -> String {
     env::var("SCHEME").unwrap_or(String::from("https"))
 }
 
+// Host of the links returned by the gateway when the request has no `Host` header
+pub fn gateway_host() -> Option<String> {
+    env::var("GATEWAY_HOST").ok()
+}
+
+fn required_var(key: &str) -> ApiResult<String> {
+    env::var(key).map_err(|_| ApiError::new_from_message(format!("{} is not set", key)))
+}
+
+// Malformed values are reported by `validate` on boot
 fn usize_with_default(key: &str, default: usize) -> usize {
-    match env::var(key) {
-        Ok(value) => value.parse().unwrap(),
-        Err(_) => default,
-    }
+    parse_with_default(key, default)
 }
 
 fn u64_with_default(key: &str, default: u64) -> u64 {
-    match env::var(key) {
-        Ok(value) => value.parse().unwrap(),
-        Err(_) => default,
-    }
+    parse_with_default(key, default)
 }
 
 fn bool_with_default(key: &str, default: bool) -> bool {
-    match env::var(key) {
-        Ok(value) => value.parse().unwrap(),
-        Err(_) => default,
-    }
+    parse_with_default(key, default)
+}
+
+fn parse_with_default<T: FromStr>(key: &str, default: T) -> T {
+    env::var(key)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
 }
 
 // TIME DURATION VALUES
@@ -71,10 +188,18 @@ pub fn exchange_api_cache_duration() -> usize {
     usize_with_default("EXCHANGE_API_CACHE_DURATION", 60 * 60 * 12)
 }
 
+pub fn local_cache_duration() -> usize {
+    usize_with_default("LOCAL_CACHE_DURATION", 5)
+}
+
 pub fn request_cache_duration() -> usize {
     usize_with_default("REQUEST_CACHE_DURATION", indefinite_timeout())
 }
 
+pub fn request_stale_duration() -> usize {
+    usize_with_default("REQUEST_STALE_DURATION", 0)
+}
+
 pub fn about_cache_duration() -> usize {
     usize_with_default("ABOUT_CACHE_DURATION", 60 * 15)
 }
@@ -87,6 +212,23 @@ pub fn safe_app_manifest_cache_duration() -> usize {
     usize_with_default("SAFE_APP_MANIFEST_CACHE_DURATION", indefinite_timeout())
 }
 
+pub fn webhook_deliveries_duration() -> usize {
+    usize_with_default("WEBHOOK_DELIVERIES_DURATION", 60 * 60 * 24 * 7)
+}
+
+pub fn webhook_delivery_backoff() -> usize {
+    usize_with_default("WEBHOOK_DELIVERY_BACKOFF", 10)
+}
+
+pub fn rpc_cache_duration() -> usize {
+    usize_with_default("RPC_CACHE_DURATION", 10)
+}
+
+// Safes created for an owner are only picked up after it, webhooks refresh the known safes
+pub fn owner_safes_cache_duration() -> usize {
+    usize_with_default("OWNER_SAFES_CACHE_DURATION", 60 * 5)
+}
+
 // REQUEST TIMEOUTS
 pub fn internal_client_connect_timeout() -> u64 {
     u64_with_default("INTERNAL_CLIENT_CONNECT_TIMEOUT", 1000)
@@ -116,6 +258,18 @@ pub fn collectibles_request_timeout() -> u64 {
     u64_with_default("COLLECTIBLES_REQUEST_TIMEOUT", 20000)
 }
 
+pub fn webhook_delivery_timeout() -> u64 {
+    u64_with_default("WEBHOOK_DELIVERY_TIMEOUT", 5000)
+}
+
+pub fn push_notification_request_timeout() -> u64 {
+    u64_with_default("PUSH_NOTIFICATION_REQUEST_TIMEOUT", 5000)
+}
+
+pub fn rpc_request_timeout() -> u64 {
+    u64_with_default("RPC_REQUEST_TIMEOUT", 10000)
+}
+
 pub fn default_request_timeout() -> u64 {
     u64_with_default("DEFAULT_REQUEST_TIMEOUT", 10000)
 }
@@ -130,28 +284,54 @@ pub fn log_all_error_responses() -> bool {
 }
 
 // OTHERS
-pub fn redis_scan_count() -> usize {
-    usize_with_default("REDIS_SCAN_COUNT", 300)
+pub fn redis_url(rocket_config: &rocket::Config) -> Option<String> {
+    database_config(SERVICE_CACHE_DATABASE, rocket_config)
+        .ok()
+        .map(|config| config.url.to_string())
 }
 
-pub fn build_number() -> Option<String> {
-    option_env!("BUILD_NUMBER").map(|it| it.to_string())
+pub fn in_memory_cache() -> bool {
+    bool_with_default("IN_MEMORY_CACHE", false)
 }
 
-pub fn native_coin_decimals() -> u64 {
-    u64_with_default("NATIVE_COIN_DECIMALS", 18)
+pub fn in_memory_cache_capacity() -> usize {
+    usize_with_default("IN_MEMORY_CACHE_CAPACITY", 10000)
 }
 
-pub fn native_coin_symbol() -> String {
-    env::var("NATIVE_COIN_SYMBOL")
-        .unwrap_or(String::from("ETH"))
-        .to_string()
+pub fn local_cache() -> bool {
+    bool_with_default("LOCAL_CACHE", false)
 }
 
-pub fn native_coin_name() -> String {
-    env::var("NATIVE_COIN_NAME")
-        .unwrap_or(String::from("Ether"))
-        .to_string()
+// Logs the fields of the safe info for which the transaction service and the RPC node disagree
+pub fn safe_info_rpc_check() -> bool {
+    bool_with_default("SAFE_INFO_RPC_CHECK", false)
+}
+
+// Nonce, threshold, owners, modules and version of the safe info are read from the RPC node
+pub fn safe_info_from_rpc() -> bool {
+    bool_with_default("SAFE_INFO_FROM_RPC", false)
+}
+
+pub fn webhook_delivery_max_attempts() -> u64 {
+    u64_with_default("WEBHOOK_DELIVERY_MAX_ATTEMPTS", 8)
+}
+
+// Firebase cloud messaging server key, push notifications are only logged without it
+pub fn push_notifications_fcm_key() -> Option<String> {
+    env::var("PUSH_NOTIFICATIONS_FCM_KEY").ok()
+}
+
+// Each waiting event subscription holds a Rocket worker thread, requests beyond it get a 503
+pub fn max_event_subscribers() -> usize {
+    usize_with_default("MAX_EVENT_SUBSCRIBERS", 10)
+}
+
+pub fn redis_scan_count() -> usize {
+    usize_with_default("REDIS_SCAN_COUNT", 300)
+}
+
+pub fn build_number() -> Option<String> {
+    option_env!("BUILD_NUMBER").map(|it| it.to_string())
 }
 
 pub fn version() -> String {
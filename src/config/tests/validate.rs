@@ -0,0 +1,47 @@
+use crate::config::check_vars;
+use std::env;
+
+#[test]
+fn check_vars_lists_all_invalid_values() {
+    env::set_var("CHECK_VARS_TEST_VALID", "42");
+    env::set_var("CHECK_VARS_TEST_NEGATIVE", "-1");
+    env::set_var("CHECK_VARS_TEST_TEXT", "ten");
+    let mut problems = vec![];
+
+    check_vars::<usize>(
+        &[
+            "CHECK_VARS_TEST_VALID",
+            "CHECK_VARS_TEST_NEGATIVE",
+            "CHECK_VARS_TEST_TEXT",
+            "CHECK_VARS_TEST_UNSET",
+        ],
+        &mut problems,
+    );
+
+    assert_eq!(
+        problems,
+        vec![
+            String::from(
+                "CHECK_VARS_TEST_NEGATIVE has an invalid value \"-1\": invalid digit found in string"
+            ),
+            String::from(
+                "CHECK_VARS_TEST_TEXT has an invalid value \"ten\": invalid digit found in string"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn check_vars_bool() {
+    env::set_var("CHECK_VARS_TEST_BOOL", "yes");
+    let mut problems = vec![];
+
+    check_vars::<bool>(&["CHECK_VARS_TEST_BOOL"], &mut problems);
+
+    assert_eq!(
+        problems,
+        vec![String::from(
+            "CHECK_VARS_TEST_BOOL has an invalid value \"yes\": provided string was not `true` or `false`"
+        )]
+    );
+}
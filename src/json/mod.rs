@@ -10,6 +10,8 @@ pub const MULTISIG_TX_ERC721_TRANSFER: &str =
     include_str!("transactions/multisig_erc721_transfer.json");
 pub const MULTISIG_TX_ERC721_TRANSFER_CANCELLED: &str =
     include_str!("transactions/multisig_erc721_transfer_cancelled.json");
+pub const MULTISIG_TX_ERC1155_BATCH_TRANSFER: &str =
+    include_str!("transactions/multisig_erc1155_batch_transfer.json");
 pub const MULTISIG_TX_ERC721_TRANSFER_INVALID_TO_AND_FROM: &str =
     include_str!("transactions/multisig_erc721_transfer_invalid_to_and_from.json");
 pub const MULTISIG_TX_ETHER_TRANSFER: &str =
@@ -65,6 +67,8 @@ pub const ERC_721_TRANSFER_WITH_TOKEN_INFO_INCOMING: &str =
     include_str!("transfers/erc_721_transfer_with_token_info_incoming.json");
 pub const ERC_721_TRANSFER_WITH_TOKEN_INFO_OUTGOING: &str =
     include_str!("transfers/erc_721_transfer_with_token_info_outgoing.json");
+pub const ERC_1155_TRANSFER_WITH_TOKEN_INFO_INCOMING: &str =
+    include_str!("transfers/erc_1155_transfer_with_token_info_incoming.json");
 
 pub const BACKEND_HISTORY_TRANSACTION_LIST_PAGE: &str =
     include_str!("transactions/backend_history_transaction_list_page.json");
@@ -104,6 +108,17 @@ pub const DATA_DECODED_SWAP_ARRAY_VALUES: &str =
     include_str!("commons/data_decoded_swap_array_values.json");
 pub const DATA_DECODED_MULTI_SEND_SINGLE_INNER_TRANSACTION: &str =
     include_str!("commons/data_decoded_multi_send_single_inner_transaction.json");
+pub const DATA_DECODED_NESTED_MULTI_SEND: &str =
+    include_str!("commons/data_decoded_nested_multi_send.json");
+pub const DATA_DECODED_APPROVE: &str = include_str!("commons/data_decoded_approve.json");
+pub const DATA_DECODED_APPROVE_UNLIMITED: &str =
+    include_str!("commons/data_decoded_approve_unlimited.json");
+pub const DATA_DECODED_APPROVE_REVOKED: &str =
+    include_str!("commons/data_decoded_approve_revoked.json");
+pub const DATA_DECODED_SET_APPROVAL_FOR_ALL: &str =
+    include_str!("commons/data_decoded_set_approval_for_all.json");
+pub const DATA_DECODED_MULTI_SEND_APPROVALS: &str =
+    include_str!("commons/data_decoded_multi_send_approvals.json");
 
 pub const BALANCE_ETHER: &str = include_str!("balances/balance_ether.json");
 pub const BALANCE_COMPOUND_ETHER: &str = include_str!("balances/balance_compound_ether.json");
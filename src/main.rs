@@ -26,9 +26,14 @@ mod utils;
 mod json;
 
 use crate::routes::error_catchers;
-use cache::redis::ServiceCache;
+use cache::layered::subscribe_to_invalidations;
+use cache::memory::InMemoryCache;
+use cache::redis::{self as redis_cache, ServiceCache};
 use dotenv::dotenv;
+use providers::push::push_provider;
 use routes::active_routes;
+use services::events::{SafeEventsBroadcaster, SAFE_EVENTS_CHANNEL};
+use services::webhooks::start_delivery_worker;
 use std::time::Duration;
 use utils::cors::CORS;
 
@@ -36,19 +41,77 @@ fn main() {
     dotenv().ok();
     env_logger::init();
 
-    let client = reqwest::blocking::Client::builder()
+    let rocket = rocket::ignite();
+    if let Err(problems) = config::validate(rocket.config()) {
+        for problem in &problems {
+            log::error!("Invalid configuration: {}", problem);
+        }
+        std::process::exit(1);
+    }
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let client = reqwest::Client::builder()
         .connect_timeout(Duration::from_millis(
             config::internal_client_connect_timeout(),
         ))
         .build()
         .unwrap();
 
-    rocket::ignite()
+    // Redirects of the callback urls could point to hosts that are not public
+    let delivery_client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_millis(
+            config::internal_client_connect_timeout(),
+        ))
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+    let broadcaster = SafeEventsBroadcaster::new(config::max_event_subscribers());
+    let redis_url = config::redis_url(rocket.config());
+    let rocket = rocket
         .mount("/", active_routes())
         .manage(client)
+        .manage(runtime)
+        .manage(broadcaster.clone())
+        .manage(push_provider())
         .attach(monitoring::performance::PerformanceMonitor())
         .attach(CORS())
-        .attach(ServiceCache::fairing())
-        .register(error_catchers())
-        .launch();
+        .register(error_catchers());
+
+    if config::in_memory_cache() {
+        let cache = InMemoryCache::new(config::in_memory_cache_capacity());
+        broadcaster.forward(cache.subscribe(SAFE_EVENTS_CHANNEL));
+        // Webhook subscriptions are only kept in redis, they are not available without it
+        let rocket = match redis_url {
+            Some(redis_url) => {
+                start_delivery_worker(delivery_client, redis_url);
+                rocket.attach(ServiceCache::fairing())
+            }
+            None => rocket,
+        };
+        rocket.manage(cache).launch();
+        return;
+    }
+
+    // Checked by `config::validate` without the in memory cache
+    let redis_url = redis_url.unwrap_or_default();
+    start_delivery_worker(delivery_client, redis_url.clone());
+    redis_cache::subscribe(
+        &redis_url,
+        SAFE_EVENTS_CHANNEL,
+        || {},
+        move |message| broadcaster.broadcast(&message),
+    );
+    if config::local_cache() {
+        let local_cache = InMemoryCache::new(config::in_memory_cache_capacity());
+        subscribe_to_invalidations(&redis_url, local_cache.clone());
+        rocket
+            .manage(local_cache)
+            .attach(ServiceCache::fairing())
+            .launch();
+    } else {
+        rocket.attach(ServiceCache::fairing()).launch();
+    }
 }
@@ -1,5 +1,6 @@
 pub mod about;
 pub mod balances;
+pub mod owners;
 pub mod transactions;
 pub mod transfers;
 pub mod webhooks;
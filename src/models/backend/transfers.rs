@@ -10,6 +10,8 @@ pub enum Transfer {
     Erc721(Erc721Transfer),
     #[serde(rename(deserialize = "ERC20_TRANSFER"))]
     Erc20(Erc20Transfer),
+    #[serde(rename(deserialize = "ERC1155_TRANSFER"))]
+    Erc1155(Erc1155Transfer),
     #[serde(rename(deserialize = "ETHER_TRANSFER"))]
     Ether(EtherTransfer),
     #[serde(other)]
@@ -46,6 +48,22 @@ pub struct Erc20Transfer {
     pub from: String,
 }
 
+#[derive(Derivative, Deserialize, Debug, Clone)]
+#[derivative(Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Erc1155Transfer {
+    pub execution_date: DateTime<Utc>,
+    pub block_number: u64,
+    pub transaction_hash: String,
+    pub to: String,
+    pub token_id: String,
+    pub value: String,
+    pub token_address: String,
+    #[derivative(Hash = "ignore")]
+    pub token_info: Option<TokenInfo>,
+    pub from: String,
+}
+
 #[derive(Derivative, Deserialize, Debug, Clone)]
 #[derivative(Hash)]
 #[serde(rename_all = "camelCase")]
@@ -1,14 +1,27 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Debug, Hash)]
 #[serde(tag = "type")]
 pub struct Payload {
     pub address: String,
+    // Only sent by transaction services that know their chain
+    #[serde(rename = "chainId")]
+    pub chain_id: Option<String>,
     #[serde(flatten)]
     pub details: Option<PayloadDetails>,
 }
 
-#[derive(Deserialize, Debug, Hash)]
+impl Payload {
+    // The chain of the webhook route overrides the one of the payload
+    pub fn with_chain_id(self, chain_id: String) -> Self {
+        Payload {
+            chain_id: Some(chain_id),
+            ..self
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Hash)]
 #[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PayloadDetails {
     NewConfirmation(NewConfirmation),
@@ -20,34 +33,34 @@ pub enum PayloadDetails {
     Unknown,
 }
 
-#[derive(Deserialize, Debug, Hash)]
+#[derive(Serialize, Deserialize, Clone, Debug, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct NewConfirmation {
     pub owner: String,
     pub safe_tx_hash: String,
 }
 
-#[derive(Deserialize, Debug, Hash)]
+#[derive(Serialize, Deserialize, Clone, Debug, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct ExecutedMultisigTransaction {
     pub safe_tx_hash: String,
     pub tx_hash: String,
 }
 
-#[derive(Deserialize, Debug, Hash)]
+#[derive(Serialize, Deserialize, Clone, Debug, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct PendingMultisigTransaction {
     pub safe_tx_hash: String,
 }
 
-#[derive(Deserialize, Debug, Hash)]
+#[derive(Serialize, Deserialize, Clone, Debug, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct IncomingEther {
     pub tx_hash: String,
     pub value: String,
 }
 
-#[derive(Deserialize, Debug, Hash)]
+#[derive(Serialize, Deserialize, Clone, Debug, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct IncomingToken {
     pub tx_hash: String,
@@ -0,0 +1,84 @@
+use crate::models::backend::transactions::Transaction;
+use crate::models::commons::{DataDecoded, ValueDecodedType};
+use crate::models::service::allowances::Allowance;
+use crate::providers::info::InfoProvider;
+use crate::utils::MULTI_SEND_TRANSACTIONS_PARAM;
+use std::collections::HashSet;
+
+// Folds the approvals of the executed transactions into the allowances that are still granted.
+// `transactions` are expected newest first, as returned by the transaction service.
+pub async fn to_allowances(
+    transactions: &[Transaction],
+    info_provider: &mut dyn InfoProvider,
+) -> Vec<Allowance> {
+    let mut seen = HashSet::new();
+    let mut allowances = vec![];
+    for transaction in transactions {
+        let (calls, tx_hash, approved_at) = match transaction {
+            Transaction::Multisig(transaction)
+                if transaction.is_executed && transaction.is_successful.unwrap_or(false) =>
+            {
+                (
+                    safe_calls(&transaction.to, &transaction.data_decoded),
+                    transaction.transaction_hash.to_owned(),
+                    transaction
+                        .execution_date
+                        .map(|date| date.timestamp_millis()),
+                )
+            }
+            Transaction::Module(transaction) if transaction.is_successful => (
+                safe_calls(&transaction.to, &transaction.data_decoded),
+                Some(transaction.transaction_hash.to_owned()),
+                Some(transaction.execution_date.timestamp_millis()),
+            ),
+            _ => continue,
+        };
+        // Within a batch the last call is the one in effect
+        for (token_address, data_decoded) in calls.into_iter().rev() {
+            let change = match data_decoded.to_allowance_change() {
+                Some(change) => change,
+                None => continue,
+            };
+            let key = (token_address.to_lowercase(), change.spender.to_lowercase());
+            if !seen.insert(key) || change.revoked {
+                continue;
+            }
+            let token_info = info_provider.token_info(&token_address).await.ok();
+            if !change.is_allowance_of(token_info.as_ref()) {
+                continue;
+            }
+            let spender_info = info_provider
+                .full_address_info_search(&change.spender)
+                .await
+                .ok();
+            allowances.push(Allowance {
+                token_address,
+                token_info,
+                spender_info,
+                spender: change.spender,
+                amount: change.amount,
+                unlimited: change.unlimited,
+                tx_hash: tx_hash.to_owned(),
+                approved_at,
+            });
+        }
+    }
+    allowances
+}
+
+// The call made by the safe followed by the ones batched in it with multiSend
+fn safe_calls(to: &str, data_decoded: &Option<DataDecoded>) -> Vec<(String, DataDecoded)> {
+    let data_decoded = match data_decoded {
+        Some(data_decoded) => data_decoded,
+        None => return vec![],
+    };
+    let mut calls = vec![(to.to_owned(), data_decoded.to_owned())];
+    if let Some(ValueDecodedType::InternalTransaction(transactions)) =
+        data_decoded.get_parameter_value_decoded(MULTI_SEND_TRANSACTIONS_PARAM)
+    {
+        for transaction in transactions.iter() {
+            calls.extend(safe_calls(&transaction.to, &transaction.data_decoded));
+        }
+    }
+    calls
+}
@@ -1,13 +1,59 @@
 use crate::models::commons::DataDecoded;
 use crate::models::service::transactions::SettingsInfo;
-use crate::providers::info::InfoProvider;
+use crate::providers::info::{InfoProvider, TokenInfo, TokenType};
 use crate::utils::{
     ADD_OWNER_WITH_THRESHOLD, CHANGE_MASTER_COPY, CHANGE_THRESHOLD, DISABLE_MODULE, ENABLE_MODULE,
-    REMOVE_OWNER, SET_FALLBACK_HANDLER, SWAP_OWNER,
+    REMOVE_OWNER, SET_APPROVAL_FOR_ALL_METHOD, SET_FALLBACK_HANDLER, SWAP_OWNER,
 };
+use ethabi::Uint;
+
+// Allowances from 2^255 on are treated as unlimited, wallets approve `uint256` max or close to it
+const UNLIMITED_ALLOWANCE_BIT: usize = 255;
+
+// Allowance set by an `approve` or `setApprovalForAll` call
+pub(super) struct AllowanceChange {
+    pub(super) spender: String,
+    // `setApprovalForAll` has no amount, it grants access to all the tokens
+    pub(super) amount: Option<String>,
+    pub(super) unlimited: bool,
+    pub(super) revoked: bool,
+}
+
+impl AllowanceChange {
+    // ERC721 shares the `approve` signature for a single token, so it is only an allowance for
+    // tokens known to be ERC20
+    pub(super) fn is_allowance_of(&self, token: Option<&TokenInfo>) -> bool {
+        self.amount.is_none() || token.map_or(false, |it| it.token_type == TokenType::Erc20)
+    }
+}
 
 impl DataDecoded {
-    pub(super) fn to_settings_info(
+    pub(super) fn to_allowance_change(&self) -> Option<AllowanceChange> {
+        if !self.is_approval_method() {
+            return None;
+        }
+        let spender = self.get_parameter_single_value_at(0)?;
+        let value = self.get_parameter_single_value_at(1)?;
+        if self.method == SET_APPROVAL_FOR_ALL_METHOD {
+            let approved = value.eq_ignore_ascii_case("true");
+            Some(AllowanceChange {
+                spender,
+                amount: None,
+                unlimited: approved,
+                revoked: !approved,
+            })
+        } else {
+            let amount = Uint::from_dec_str(&value).ok()?;
+            Some(AllowanceChange {
+                spender,
+                amount: Some(value),
+                unlimited: amount.bit(UNLIMITED_ALLOWANCE_BIT),
+                revoked: amount.is_zero(),
+            })
+        }
+    }
+
+    pub(super) async fn to_settings_info(
         &self,
         info_provider: &mut dyn InfoProvider,
     ) -> Option<SettingsInfo> {
@@ -15,7 +61,7 @@ impl DataDecoded {
             SET_FALLBACK_HANDLER => {
                 let handler = self.get_parameter_single_value_at(0)?;
                 Some(SettingsInfo::SetFallbackHandler {
-                    handler_info: info_provider.contract_info(&handler).ok(),
+                    handler_info: info_provider.contract_info(&handler).await.ok(),
                     handler,
                 })
             }
@@ -48,21 +94,21 @@ impl DataDecoded {
             CHANGE_MASTER_COPY => {
                 let implementation = self.get_parameter_single_value_at(0)?;
                 Some(SettingsInfo::ChangeImplementation {
-                    implementation_info: info_provider.contract_info(&implementation).ok(),
+                    implementation_info: info_provider.contract_info(&implementation).await.ok(),
                     implementation,
                 })
             }
             ENABLE_MODULE => {
                 let module = self.get_parameter_single_value_at(0)?;
                 Some(SettingsInfo::EnableModule {
-                    module_info: info_provider.contract_info(&module).ok(),
+                    module_info: info_provider.contract_info(&module).await.ok(),
                     module,
                 })
             }
             DISABLE_MODULE => {
                 let module = self.get_parameter_single_value_at(1)?;
                 Some(SettingsInfo::DisableModule {
-                    module_info: info_provider.contract_info(&module).ok(),
+                    module_info: info_provider.contract_info(&module).await.ok(),
                     module,
                 })
             }
@@ -0,0 +1,35 @@
+use crate::models::backend::webhooks::{Payload, PayloadDetails};
+use crate::models::service::events::{ActivityType, SafeActivity};
+
+impl Payload {
+    // Only the payloads that change what clients display are turned into activity
+    pub fn to_safe_activity(&self) -> Option<SafeActivity> {
+        let activity = match self.details.as_ref()? {
+            PayloadDetails::PendingMultisigTransaction(data) => ActivityType::NewProposal {
+                safe_tx_hash: data.safe_tx_hash.to_owned(),
+            },
+            PayloadDetails::NewConfirmation(data) => ActivityType::NewConfirmation {
+                safe_tx_hash: data.safe_tx_hash.to_owned(),
+                owner: data.owner.to_owned(),
+            },
+            PayloadDetails::ExecutedMultisigTransaction(data) => ActivityType::Execution {
+                safe_tx_hash: data.safe_tx_hash.to_owned(),
+                tx_hash: data.tx_hash.to_owned(),
+            },
+            PayloadDetails::IncomingEther(data) => ActivityType::IncomingTransfer {
+                tx_hash: data.tx_hash.to_owned(),
+                token_address: None,
+            },
+            PayloadDetails::IncomingToken(data) => ActivityType::IncomingTransfer {
+                tx_hash: data.tx_hash.to_owned(),
+                token_address: Some(data.token_address.to_owned()),
+            },
+            PayloadDetails::Unknown => return None,
+        };
+        Some(SafeActivity {
+            address: self.address.to_owned(),
+            chain_id: self.chain_id.to_owned(),
+            activity,
+        })
+    }
+}
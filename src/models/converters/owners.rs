@@ -0,0 +1,29 @@
+use crate::models::backend::transactions::MultisigTransaction;
+use crate::models::service::owners::OwnerSafe;
+use crate::providers::info::SafeInfo;
+
+impl SafeInfo {
+    pub fn to_owner_safe(
+        &self,
+        chain_id: &str,
+        owner: &str,
+        queued_transactions: &[MultisigTransaction],
+    ) -> OwnerSafe {
+        let awaiting_confirmation = queued_transactions
+            .iter()
+            .filter(|transaction| {
+                transaction
+                    .awaiting_signers(self)
+                    .iter()
+                    .any(|signer| signer.eq_ignore_ascii_case(owner))
+            })
+            .count();
+        OwnerSafe {
+            chain_id: chain_id.to_string(),
+            address: self.address.to_owned(),
+            threshold: self.threshold,
+            nonce: self.nonce,
+            awaiting_confirmation: awaiting_confirmation as u64,
+        }
+    }
+}
@@ -1,9 +1,30 @@
-use crate::models::service::safes::{AddressEx, SafeInfoEx};
+use crate::models::commons::Operation;
+use crate::models::service::safes::{AddressEx, SafeInfoEx, SafeUpgrade};
 use crate::providers::info::{InfoProvider, SafeInfo};
+use crate::utils::errors::ApiResult;
+use crate::utils::master_copies::{
+    migration_data, upgrade_target, version_state, MIGRATION_MULTI_SEND,
+};
+use ethereum_types::Address;
 
 // AddressInfo for `address` and `owners` was deferred for a later version if necessary as it adds little value
 impl SafeInfo {
-    pub fn to_safe_info_ex(&self, info_provider: &mut dyn InfoProvider) -> SafeInfoEx {
+    pub async fn to_safe_info_ex(&self, info_provider: &mut dyn InfoProvider) -> SafeInfoEx {
+        let implementation = to_address_ex(&self.master_copy, info_provider).await;
+        let modules = match &self.modules {
+            Some(modules) => {
+                let mut modules_ex = Vec::with_capacity(modules.len());
+                for module_address in modules {
+                    modules_ex.push(to_address_ex(module_address, info_provider).await);
+                }
+                Some(modules_ex)
+            }
+            None => None,
+        };
+        let fallback_handler = match &self.fallback_handler {
+            Some(fallback_handler) => Some(to_address_ex(fallback_handler, info_provider).await),
+            None => None,
+        };
         SafeInfoEx {
             address: AddressEx {
                 value: self.address.to_owned(),
@@ -12,7 +33,8 @@ impl SafeInfo {
             },
             nonce: self.nonce,
             threshold: self.threshold,
-            implementation: to_address_ex(&self.master_copy, info_provider),
+            implementation,
+            implementation_version_state: version_state(&self.master_copy),
             owners: self
                 .owners
                 .iter()
@@ -22,23 +44,33 @@ impl SafeInfo {
                     logo_url: None,
                 })
                 .collect(),
-            modules: self.modules.as_ref().map(|modules| {
-                modules
-                    .iter()
-                    .map(|module_address| to_address_ex(module_address, info_provider))
-                    .collect()
-            }),
-            fallback_handler: self
-                .fallback_handler
-                .as_ref()
-                .map(|it| to_address_ex(&it, info_provider)),
+            modules,
+            fallback_handler,
             version: self.version.to_owned(),
         }
     }
+
+    // Delegate call to MultiSend changing the master copy and the fallback handler of the Safe to
+    // the ones of the latest version of the same kind (L1 or L2) deployed for the chain
+    pub fn to_safe_upgrade(&self, eip155_deployments: bool) -> ApiResult<SafeUpgrade> {
+        let target = upgrade_target(&self.master_copy, eip155_deployments)
+            .ok_or_else(|| client_error!(422, "No upgrade available for the Safe"))?;
+        let safe_address: Address =
+            serde_json::from_value(serde_json::Value::String(self.address.to_owned()))?;
+        Ok(SafeUpgrade {
+            to: String::from(MIGRATION_MULTI_SEND),
+            value: String::from("0"),
+            data: to_hex_string!(migration_data(safe_address, target)),
+            operation: Operation::DELEGATE,
+            implementation: String::from(target.address),
+            fallback_handler: target.fallback_handler.map(String::from),
+            version: String::from(target.version),
+        })
+    }
 }
 
-fn to_address_ex(address: &str, info_provider: &mut dyn InfoProvider) -> AddressEx {
-    let address_info = info_provider.contract_info(&address).ok();
+async fn to_address_ex(address: &str, info_provider: &mut dyn InfoProvider) -> AddressEx {
+    let address_info = info_provider.contract_info(&address).await.ok();
     AddressEx {
         value: address.to_owned(),
         name: address_info.as_ref().map(|it| it.name.to_owned()),
@@ -0,0 +1,190 @@
+use crate::models::backend::transactions::Transaction;
+use crate::models::converters::allowances::to_allowances;
+use crate::models::service::allowances::Allowance;
+use crate::providers::address_info::AddressInfo;
+use crate::providers::info::*;
+
+const TOKEN: &str = "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02";
+const NFT: &str = "0x7E9dB9C2e8C1E2BE5Da4a9a4dbE25AdD3D4EfF1A";
+const SPENDER: &str = "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D";
+const MULTI_SEND: &str = "0x8D29bE29923b68abfDD21e541b9374737B49cdAD";
+const TX_HASH: &str = "0x0ebb2c317f55c96469e0ed2014f5833dc02a70b42f0ac52f4630938900caa698";
+const MAX_UINT: &str =
+    "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+fn multisig_transaction(to: &str, data_decoded: &str, is_successful: bool) -> Transaction {
+    let mut transaction =
+        serde_json::from_str::<Transaction>(crate::json::MULTISIG_TX_CUSTOM).unwrap();
+    if let Transaction::Multisig(multisig_transaction) = &mut transaction {
+        multisig_transaction.to = String::from(to);
+        multisig_transaction.data_decoded = Some(serde_json::from_str(data_decoded).unwrap());
+        multisig_transaction.is_successful = Some(is_successful);
+    }
+    transaction
+}
+
+fn token_info(token_type: TokenType) -> TokenInfo {
+    TokenInfo {
+        token_type,
+        address: String::from(TOKEN),
+        decimals: 18,
+        symbol: String::from("RIN"),
+        name: String::from("Rinkeby Token"),
+        logo_uri: None,
+    }
+}
+
+#[tokio::test]
+async fn to_allowances_single_approval() {
+    let transactions = vec![multisig_transaction(
+        TOKEN,
+        crate::json::DATA_DECODED_APPROVE,
+        true,
+    )];
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_token_info()
+        .times(1)
+        .return_once(move |_| Ok(token_info(TokenType::Erc20)));
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(1)
+        .return_once(move |_| {
+            Ok(AddressInfo {
+                name: String::from("Spender"),
+                logo_uri: None,
+            })
+        });
+
+    let expected = vec![Allowance {
+        token_address: String::from(TOKEN),
+        token_info: Some(token_info(TokenType::Erc20)),
+        spender: String::from(SPENDER),
+        spender_info: Some(AddressInfo {
+            name: String::from("Spender"),
+            logo_uri: None,
+        }),
+        amount: Some(String::from("500000000000000")),
+        unlimited: false,
+        tx_hash: Some(String::from(TX_HASH)),
+        approved_at: Some(1592848854000),
+    }];
+
+    let actual = to_allowances(&transactions, &mut mock_info_provider).await;
+
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn to_allowances_revoked_by_newer_approval() {
+    let transactions = vec![
+        multisig_transaction(TOKEN, crate::json::DATA_DECODED_APPROVE_REVOKED, true),
+        multisig_transaction(TOKEN, crate::json::DATA_DECODED_APPROVE_UNLIMITED, true),
+    ];
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider.expect_token_info().times(0);
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(0);
+
+    let actual = to_allowances(&transactions, &mut mock_info_provider).await;
+
+    assert!(actual.is_empty());
+}
+
+#[tokio::test]
+async fn to_allowances_multi_send_last_call_wins() {
+    let transactions = vec![
+        // Failed transactions don't change the allowances
+        multisig_transaction(TOKEN, crate::json::DATA_DECODED_APPROVE_REVOKED, false),
+        multisig_transaction(
+            MULTI_SEND,
+            crate::json::DATA_DECODED_MULTI_SEND_APPROVALS,
+            true,
+        ),
+    ];
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_token_info()
+        .times(2)
+        .returning(move |token| {
+            if token == TOKEN {
+                Ok(token_info(TokenType::Erc20))
+            } else {
+                bail!("No token info")
+            }
+        });
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(2)
+        .returning(move |_| bail!("No address info"));
+
+    let expected = vec![
+        Allowance {
+            token_address: String::from(NFT),
+            token_info: None,
+            spender: String::from(SPENDER),
+            spender_info: None,
+            amount: None,
+            unlimited: true,
+            tx_hash: Some(String::from(TX_HASH)),
+            approved_at: Some(1592848854000),
+        },
+        Allowance {
+            token_address: String::from(TOKEN),
+            token_info: Some(token_info(TokenType::Erc20)),
+            spender: String::from(SPENDER),
+            spender_info: None,
+            amount: Some(String::from(MAX_UINT)),
+            unlimited: true,
+            tx_hash: Some(String::from(TX_HASH)),
+            approved_at: Some(1592848854000),
+        },
+    ];
+
+    let actual = to_allowances(&transactions, &mut mock_info_provider).await;
+
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn to_allowances_skips_erc721_approve() {
+    let transactions = vec![multisig_transaction(
+        TOKEN,
+        crate::json::DATA_DECODED_APPROVE,
+        true,
+    )];
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_token_info()
+        .times(1)
+        .return_once(move |_| Ok(token_info(TokenType::Erc721)));
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(0);
+
+    let actual = to_allowances(&transactions, &mut mock_info_provider).await;
+
+    assert!(actual.is_empty());
+}
+
+#[tokio::test]
+async fn to_allowances_skips_approve_of_unknown_token() {
+    let transactions = vec![multisig_transaction(
+        TOKEN,
+        crate::json::DATA_DECODED_APPROVE,
+        true,
+    )];
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_token_info()
+        .times(1)
+        .return_once(move |_| bail!("No token info"));
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(0);
+
+    let actual = to_allowances(&transactions, &mut mock_info_provider).await;
+
+    assert!(actual.is_empty());
+}
@@ -1,6 +1,7 @@
 use crate::json::{BALANCE_COMPOUND_ETHER, BALANCE_ETHER};
 use crate::models::backend::balances::Balance as BalanceDto;
 use crate::models::service::balances::Balance;
+use crate::models::service::chains::NativeCurrency;
 use crate::providers::info::{TokenInfo, TokenType};
 
 #[test]
@@ -22,7 +23,7 @@ fn ether_balance() {
     };
 
     let usd_to_fiat = 1.0;
-    let actual = balance_dto.to_balance(usd_to_fiat);
+    let actual = balance_dto.to_balance(usd_to_fiat, &ether());
 
     assert_eq!(actual, expected);
 }
@@ -46,7 +47,7 @@ fn erc20_token_balance_usd_balance() {
     };
 
     let usd_to_fiat = 1.0;
-    let actual = balance_dto.to_balance(usd_to_fiat);
+    let actual = balance_dto.to_balance(usd_to_fiat, &ether());
 
     assert_eq!(actual, expected);
 }
@@ -70,7 +71,16 @@ fn erc20_token_balance_fiat_is_twice_usd() {
     };
 
     let usd_to_fiat = 2.0;
-    let actual = balance_dto.to_balance(usd_to_fiat);
+    let actual = balance_dto.to_balance(usd_to_fiat, &ether());
 
     assert_eq!(actual, expected);
 }
+
+fn ether() -> NativeCurrency {
+    NativeCurrency {
+        name: "Ether".to_string(),
+        symbol: "ETH".to_string(),
+        decimals: 18,
+        logo_uri: None,
+    }
+}
@@ -0,0 +1,88 @@
+use crate::models::backend::webhooks::{
+    ExecutedMultisigTransaction, IncomingToken, NewConfirmation, Payload, PayloadDetails,
+};
+use crate::models::service::events::{ActivityType, SafeActivity};
+use serde_json::json;
+
+const CHAIN_ID: &str = "4";
+const SAFE: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const SAFE_TX_HASH: &str = "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621";
+const TX_HASH: &str = "0x08cf4bb6fe2a7e77e86e7679ec3c266516155cdc9900cdd47afef791169d6e21";
+
+fn payload(details: Option<PayloadDetails>) -> Payload {
+    Payload {
+        address: SAFE.to_string(),
+        chain_id: Some(CHAIN_ID.to_string()),
+        details,
+    }
+}
+
+#[test]
+fn new_confirmation_to_safe_activity() {
+    let payload = payload(Some(PayloadDetails::NewConfirmation(NewConfirmation {
+        owner: "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23".to_string(),
+        safe_tx_hash: SAFE_TX_HASH.to_string(),
+    })));
+    let expected = SafeActivity {
+        address: SAFE.to_string(),
+        chain_id: Some(CHAIN_ID.to_string()),
+        activity: ActivityType::NewConfirmation {
+            safe_tx_hash: SAFE_TX_HASH.to_string(),
+            owner: "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23".to_string(),
+        },
+    };
+
+    assert_eq!(payload.to_safe_activity(), Some(expected));
+}
+
+#[test]
+fn executed_multisig_transaction_to_safe_activity() {
+    let payload = payload(Some(PayloadDetails::ExecutedMultisigTransaction(
+        ExecutedMultisigTransaction {
+            safe_tx_hash: SAFE_TX_HASH.to_string(),
+            tx_hash: TX_HASH.to_string(),
+        },
+    )));
+
+    let actual = serde_json::to_value(payload.to_safe_activity().unwrap()).unwrap();
+
+    assert_eq!(
+        actual,
+        json!({
+            "address": SAFE,
+            "chainId": CHAIN_ID,
+            "type": "EXECUTION",
+            "safeTxHash": SAFE_TX_HASH,
+            "txHash": TX_HASH
+        })
+    );
+}
+
+#[test]
+fn incoming_token_to_safe_activity() {
+    let payload = payload(Some(PayloadDetails::IncomingToken(IncomingToken {
+        tx_hash: TX_HASH.to_string(),
+        token_address: "0x63704B63Ac04f3a173Dfe677C7e3D330c347CD88".to_string(),
+        token_id: None,
+        value: Some("1000".to_string()),
+    })));
+    let expected = SafeActivity {
+        address: SAFE.to_string(),
+        chain_id: Some(CHAIN_ID.to_string()),
+        activity: ActivityType::IncomingTransfer {
+            tx_hash: TX_HASH.to_string(),
+            token_address: Some("0x63704B63Ac04f3a173Dfe677C7e3D330c347CD88".to_string()),
+        },
+    };
+
+    assert_eq!(payload.to_safe_activity(), Some(expected));
+}
+
+#[test]
+fn unknown_payload_to_safe_activity() {
+    assert_eq!(
+        payload(Some(PayloadDetails::Unknown)).to_safe_activity(),
+        None
+    );
+    assert_eq!(payload(None).to_safe_activity(), None);
+}
@@ -1,7 +1,10 @@
+mod allowances;
 pub(super) mod balances;
 mod data_decoded;
+mod events;
 mod get_address_info;
 mod get_transfer_direction;
+mod owners;
 mod page_metadata;
 mod safes;
 mod transfer_erc20;
@@ -0,0 +1,65 @@
+use crate::models::backend::transactions::MultisigTransaction;
+use crate::models::service::owners::OwnerSafe;
+use crate::providers::info::SafeInfo;
+
+const MISSING_OWNER: &str = "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23";
+const CONFIRMING_OWNER: &str = "0xF2CeA96575d6b10f51d9aF3b10e3e4E5738aa6bd";
+
+fn queued_safe_info() -> SafeInfo {
+    let mut safe_info = serde_json::from_str::<SafeInfo>(crate::json::SAFE_WITH_MODULES).unwrap();
+    safe_info.nonce = 140;
+    safe_info
+}
+
+#[test]
+fn to_owner_safe_missing_confirmation() {
+    let transaction = serde_json::from_str::<MultisigTransaction>(
+        crate::json::MULTISIG_TX_AWAITING_CONFIRMATIONS,
+    )
+    .unwrap();
+    let safe_info = queued_safe_info();
+    let expected = OwnerSafe {
+        chain_id: String::from("4"),
+        address: String::from("0x1230B3d59858296A31053C1b8562Ecf89A2f888b"),
+        threshold: 3,
+        nonce: 140,
+        awaiting_confirmation: 1,
+    };
+
+    let actual = safe_info.to_owner_safe("4", MISSING_OWNER, &[transaction]);
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn to_owner_safe_counts_by_owner() {
+    let transaction = serde_json::from_str::<MultisigTransaction>(
+        crate::json::MULTISIG_TX_AWAITING_CONFIRMATIONS,
+    )
+    .unwrap();
+    let transactions = vec![transaction];
+    let safe_info = queued_safe_info();
+
+    let count = |owner: &str| {
+        safe_info
+            .to_owner_safe("4", owner, &transactions)
+            .awaiting_confirmation
+    };
+
+    assert_eq!(count(&MISSING_OWNER.to_lowercase()), 1);
+    assert_eq!(count(CONFIRMING_OWNER), 0);
+    assert_eq!(count("0x1230B3d59858296A31053C1b8562Ecf89A2f888b"), 0);
+}
+
+#[test]
+fn to_owner_safe_ignores_executed_nonces() {
+    let transaction = serde_json::from_str::<MultisigTransaction>(
+        crate::json::MULTISIG_TX_AWAITING_CONFIRMATIONS,
+    )
+    .unwrap();
+    let safe_info = serde_json::from_str::<SafeInfo>(crate::json::SAFE_WITH_MODULES).unwrap();
+
+    let actual = safe_info.to_owner_safe("4", MISSING_OWNER, &[transaction]);
+
+    assert_eq!(actual.awaiting_confirmation, 0);
+}
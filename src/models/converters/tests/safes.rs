@@ -1,9 +1,12 @@
-use crate::models::service::safes::{AddressEx, SafeInfoEx};
+use crate::models::commons::Operation;
+use crate::models::service::safes::{
+    AddressEx, ImplementationVersionState, SafeInfoEx, SafeUpgrade,
+};
 use crate::providers::address_info::AddressInfo;
 use crate::providers::info::*;
 
-#[test]
-fn to_safe_info_ex_no_address_info() {
+#[tokio::test]
+async fn to_safe_info_ex_no_address_info() {
     let safe_info = serde_json::from_str::<SafeInfo>(crate::json::SAFE_WITH_MODULES).unwrap();
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider
@@ -50,6 +53,7 @@ fn to_safe_info_ex_no_address_info() {
             name: None,
             logo_url: None,
         },
+        implementation_version_state: ImplementationVersionState::Outdated,
         modules: Some(vec![
             AddressEx {
                 value: "0x25F73b24B866963B0e560fFF9bbA7908be0263E8".to_string(),
@@ -75,13 +79,13 @@ fn to_safe_info_ex_no_address_info() {
         version: Some("1.1.1".to_string()),
     };
 
-    let actual = safe_info.to_safe_info_ex(&mut mock_info_provider);
+    let actual = safe_info.to_safe_info_ex(&mut mock_info_provider).await;
 
     assert_eq!(actual, expected);
 }
 
-#[test]
-fn to_safe_info_ex_address_info() {
+#[tokio::test]
+async fn to_safe_info_ex_address_info() {
     let safe_info = serde_json::from_str::<SafeInfo>(crate::json::SAFE_WITH_MODULES).unwrap();
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider
@@ -133,6 +137,7 @@ fn to_safe_info_ex_address_info() {
             name: Some("name_0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string()),
             logo_url: Some("logo_uri_0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string()),
         },
+        implementation_version_state: ImplementationVersionState::Outdated,
         modules: Some(vec![
             AddressEx {
                 value: "0x25F73b24B866963B0e560fFF9bbA7908be0263E8".to_string(),
@@ -158,13 +163,13 @@ fn to_safe_info_ex_address_info() {
         version: Some("1.1.1".to_string()),
     };
 
-    let actual = safe_info.to_safe_info_ex(&mut mock_info_provider);
+    let actual = safe_info.to_safe_info_ex(&mut mock_info_provider).await;
 
     assert_eq!(actual, expected);
 }
 
-#[test]
-fn to_safe_info_ex_nullable_fields_are_all_null() {
+#[tokio::test]
+async fn to_safe_info_ex_nullable_fields_are_all_null() {
     let safe_info = serde_json::from_str::<SafeInfo>(
         &json!({
             "address": "0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
@@ -200,12 +205,41 @@ fn to_safe_info_ex_nullable_fields_are_all_null() {
             name: None,
             logo_url: None,
         },
+        implementation_version_state: ImplementationVersionState::Outdated,
         modules: None,
         fallback_handler: None,
         version: None,
     };
 
-    let actual = safe_info.to_safe_info_ex(&mut mock_info_provider);
+    let actual = safe_info.to_safe_info_ex(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn to_safe_upgrade_outdated_safe() {
+    let safe_info = serde_json::from_str::<SafeInfo>(crate::json::SAFE_WITH_MODULES).unwrap();
+    let expected = SafeUpgrade {
+        to: String::from("0x8D29bE29923b68abfDD21e541b9374737B49cdAD"),
+        value: String::from("0"),
+        data: String::from("0x8d80ff0a000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000f2001230b3d59858296a31053c1b8562ecf89a2f888b000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000247de7edef000000000000000000000000d9db270c1b5e3bd161e8c8503c55ceabee709552001230b3d59858296a31053c1b8562ecf89a2f888b00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000024f08a0323000000000000000000000000f48f2b2d2a534e402487b3ee7c18c33aec0fe5e40000000000000000000000000000"),
+        operation: Operation::DELEGATE,
+        implementation: String::from("0xd9Db270c1B5E3Bd161E8c8503c55cEABeE709552"),
+        fallback_handler: Some(String::from("0xf48f2B2d2a534e402487b3ee7C18c33Aec0Fe5e4")),
+        version: String::from("1.3.0"),
+    };
+
+    let actual = safe_info.to_safe_upgrade(false);
+
+    assert_eq!(actual, Ok(expected));
+}
+
+#[test]
+fn to_safe_upgrade_up_to_date_safe() {
+    let mut safe_info = serde_json::from_str::<SafeInfo>(crate::json::SAFE_WITH_MODULES).unwrap();
+    safe_info.master_copy = String::from("0xd9Db270c1B5E3Bd161E8c8503c55cEABeE709552");
+
+    let actual = safe_info.to_safe_upgrade(false);
+
+    assert_eq!(actual.unwrap_err().status, 422);
+}
@@ -6,8 +6,8 @@ use crate::models::service::transactions::{Erc721Transfer, Transfer, TransferDir
 use crate::providers::address_info::AddressInfo;
 use crate::providers::info::*;
 
-#[test]
-fn erc721_transfer_dto_to_incoming_transfer_transaction() {
+#[tokio::test]
+async fn erc721_transfer_dto_to_incoming_transfer_transaction() {
     let safe_address = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
     let erc721_transfer = serde_json::from_str::<Erc721TransferDto>(
         crate::json::ERC_721_TRANSFER_WITH_TOKEN_INFO_INCOMING,
@@ -42,13 +42,15 @@ fn erc721_transfer_dto_to_incoming_transfer_transaction() {
         &erc721_transfer,
         &mut mock_info_provider,
         safe_address,
-    );
+    )
+    .await
+    .unwrap();
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn erc721_transfer_dto_to_incoming_transfer_transaction_with_address_info() {
+#[tokio::test]
+async fn erc721_transfer_dto_to_incoming_transfer_transaction_with_address_info() {
     let safe_address = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
     let erc721_transfer = serde_json::from_str::<Erc721TransferDto>(
         crate::json::ERC_721_TRANSFER_WITH_TOKEN_INFO_INCOMING,
@@ -88,13 +90,15 @@ fn erc721_transfer_dto_to_incoming_transfer_transaction_with_address_info() {
         &erc721_transfer,
         &mut mock_info_provider,
         safe_address,
-    );
+    )
+    .await
+    .unwrap();
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn erc721_transfer_dto_to_outgoing_transfer_transaction_with_address_info() {
+#[tokio::test]
+async fn erc721_transfer_dto_to_outgoing_transfer_transaction_with_address_info() {
     let safe_address = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
     let erc721_transfer = serde_json::from_str::<Erc721TransferDto>(
         crate::json::ERC_721_TRANSFER_WITH_TOKEN_INFO_OUTGOING,
@@ -134,13 +138,15 @@ fn erc721_transfer_dto_to_outgoing_transfer_transaction_with_address_info() {
         &erc721_transfer,
         &mut mock_info_provider,
         safe_address,
-    );
+    )
+    .await
+    .unwrap();
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn erc721_transfer_dto_to_transfer_info_token_available() {
+#[tokio::test]
+async fn erc721_transfer_dto_to_transfer_info_token_available() {
     let erc721_transfer = serde_json::from_str::<Erc721TransferDto>(
         crate::json::ERC_721_TRANSFER_WITH_TOKEN_INFO_INCOMING,
     )
@@ -159,13 +165,15 @@ fn erc721_transfer_dto_to_transfer_info_token_available() {
         }
     );
 
-    let actual = Erc721TransferDto::to_transfer_info(&erc721_transfer, &mut mock_info_provider);
+    let actual = Erc721TransferDto::to_transfer_info(&erc721_transfer, &mut mock_info_provider)
+        .await
+        .unwrap();
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn erc721_transfer_dto_to_transfer_info_token_unavailable() {
+#[tokio::test]
+async fn erc721_transfer_dto_to_transfer_info_token_unavailable() {
     let erc721_transfer =
         serde_json::from_str::<Erc721TransferDto>(crate::json::ERC_721_TRANSFER_WITHOUT_TOKEN_INFO)
             .unwrap();
@@ -184,13 +192,15 @@ fn erc721_transfer_dto_to_transfer_info_token_unavailable() {
         logo_uri: None,
     });
 
-    let actual = Erc721TransferDto::to_transfer_info(&erc721_transfer, &mut mock_info_provider);
+    let actual = Erc721TransferDto::to_transfer_info(&erc721_transfer, &mut mock_info_provider)
+        .await
+        .unwrap();
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn erc721_transfer_dto_get_token_info_present() {
+#[tokio::test]
+async fn erc721_transfer_dto_get_token_info_present() {
     let erc721_transfer = serde_json::from_str::<Erc721TransferDto>(
         crate::json::ERC_721_TRANSFER_WITH_TOKEN_INFO_INCOMING,
     )
@@ -207,13 +217,15 @@ fn erc721_transfer_dto_get_token_info_present() {
         logo_uri:  Some("https://gnosis-safe-token-logos.s3.amazonaws.com/0x8979D84FF2c2B797dFEc02469d3a5322cBEf4b98.png".to_string())
     }) ;
 
-    let actual = Erc721TransferDto::to_transfer_info(&erc721_transfer, &mut mock_info_provider);
+    let actual = Erc721TransferDto::to_transfer_info(&erc721_transfer, &mut mock_info_provider)
+        .await
+        .unwrap();
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn erc721_transfer_dto_get_token_info_not_present() {
+#[tokio::test]
+async fn erc721_transfer_dto_get_token_info_not_present() {
     let erc721_transfer =
         serde_json::from_str::<Erc721TransferDto>(crate::json::ERC_721_TRANSFER_WITHOUT_TOKEN_INFO)
             .unwrap();
@@ -234,13 +246,15 @@ fn erc721_transfer_dto_get_token_info_not_present() {
         logo_uri: Some("https://gnosis-safe-token-logos.s3.amazonaws.com/0x8979D84FF2c2B797dFEc02469d3a5322cBEf4b98.png".to_string()),
     });
 
-    let actual = Erc721TransferDto::to_transfer_info(&erc721_transfer, &mut mock_info_provider);
+    let actual = Erc721TransferDto::to_transfer_info(&erc721_transfer, &mut mock_info_provider)
+        .await
+        .unwrap();
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn erc721_transfer_dto_get_info_provider_error() {
+#[tokio::test]
+async fn erc721_transfer_dto_get_info_provider_error() {
     let erc721_transfer =
         serde_json::from_str::<Erc721TransferDto>(crate::json::ERC_721_TRANSFER_WITHOUT_TOKEN_INFO)
             .unwrap();
@@ -258,7 +272,9 @@ fn erc721_transfer_dto_get_info_provider_error() {
         logo_uri: None,
     });
 
-    let actual = Erc721TransferDto::to_transfer_info(&erc721_transfer, &mut mock_info_provider);
+    let actual = Erc721TransferDto::to_transfer_info(&erc721_transfer, &mut mock_info_provider)
+        .await
+        .unwrap();
 
     assert_eq!(expected, actual);
 }
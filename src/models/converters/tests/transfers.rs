@@ -1,16 +1,17 @@
 use crate::models::backend::transfers::{
-    Erc20Transfer as Erc20TransferDto, Erc721Transfer as Erc721TransferDto,
-    EtherTransfer as EtherTransferDto, Transfer as TransferDto,
+    Erc1155Transfer as Erc1155TransferDto, Erc20Transfer as Erc20TransferDto,
+    Erc721Transfer as Erc721TransferDto, EtherTransfer as EtherTransferDto,
+    Transfer as TransferDto,
 };
 use crate::models::service::transactions::details::TransactionDetails;
 use crate::models::service::transactions::{
-    Erc20Transfer, Erc721Transfer, EtherTransfer, TransactionInfo, TransactionStatus, Transfer,
-    TransferDirection, TransferInfo,
+    Erc1155Transfer, Erc20Transfer, Erc721Transfer, EtherTransfer, TransactionInfo,
+    TransactionStatus, Transfer, TransferDirection, TransferInfo,
 };
 use crate::providers::info::*;
 
-#[test]
-fn erc_20_transfer_dto_to_transaction_info() {
+#[tokio::test]
+async fn erc_20_transfer_dto_to_transaction_info() {
     let safe_address = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
     let erc_20_transfer = TransferDto::Erc20(
         serde_json::from_str::<Erc20TransferDto>(
@@ -45,13 +46,16 @@ fn erc_20_transfer_dto_to_transaction_info() {
         ),
     });
 
-    let actual = erc_20_transfer.to_transfer(&mut mock_info_provider, safe_address);
+    let actual = erc_20_transfer
+        .to_transfer(&mut mock_info_provider, safe_address)
+        .await
+        .unwrap();
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn erc_721_transfer_dto_to_transaction_info() {
+#[tokio::test]
+async fn erc_721_transfer_dto_to_transaction_info() {
     let safe_address = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
     let erc_721_transfer = TransferDto::Erc721(
         serde_json::from_str::<Erc721TransferDto>(
@@ -85,13 +89,86 @@ fn erc_721_transfer_dto_to_transaction_info() {
         ),
     });
 
-    let actual = erc_721_transfer.to_transfer(&mut mock_info_provider, safe_address);
+    let actual = erc_721_transfer
+        .to_transfer(&mut mock_info_provider, safe_address)
+        .await
+        .unwrap();
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn ether_transfer_dto_to_transaction_info() {
+#[tokio::test]
+async fn erc_1155_transfer_dto_to_transaction_info() {
+    let safe_address = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+    let erc_1155_transfer = serde_json::from_str::<TransferDto>(
+        crate::json::ERC_1155_TRANSFER_WITH_TOKEN_INFO_INCOMING,
+    )
+    .unwrap();
+
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider.expect_safe_info().times(0);
+    mock_info_provider.expect_token_info().times(0);
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(1)
+        .return_once(move |_| bail!("No address info"));
+
+    let expected = TransactionInfo::Transfer(Transfer {
+        sender: "0x938bae50a210b80EA233112800Cd5Bc2e7644300".to_string(),
+        sender_info: None,
+        recipient: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        recipient_info: None,
+        direction: TransferDirection::Incoming,
+        transfer_info: TransferInfo::Erc1155(Erc1155Transfer {
+            token_address: "0x7E9dB9C2e8C1E2BE5Da4a9a4dbE25AdD3D4EfF1A".to_string(),
+            token_ids: vec!["1".to_string()],
+            amounts: vec!["10".to_string()],
+            token_name: Some("Safe Collectibles".to_string()),
+            token_symbol: Some("SCB".to_string()),
+            logo_uri: None,
+        }),
+    });
+
+    let actual = erc_1155_transfer
+        .to_transfer(&mut mock_info_provider, safe_address)
+        .await
+        .unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[tokio::test]
+async fn erc_1155_transfer_dto_without_token_info() {
+    let mut erc_1155_transfer = serde_json::from_str::<Erc1155TransferDto>(
+        crate::json::ERC_1155_TRANSFER_WITH_TOKEN_INFO_INCOMING,
+    )
+    .unwrap();
+    erc_1155_transfer.token_info = None;
+
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_token_info()
+        .times(1)
+        .return_once(move |_| bail!("No token info"));
+
+    let expected = TransferInfo::Erc1155(Erc1155Transfer {
+        token_address: "0x7E9dB9C2e8C1E2BE5Da4a9a4dbE25AdD3D4EfF1A".to_string(),
+        token_ids: vec!["1".to_string()],
+        amounts: vec!["10".to_string()],
+        token_name: None,
+        token_symbol: None,
+        logo_uri: None,
+    });
+
+    let actual = erc_1155_transfer
+        .to_transfer_info(&mut mock_info_provider)
+        .await;
+
+    assert_eq!(expected, actual);
+}
+
+#[tokio::test]
+async fn ether_transfer_dto_to_transaction_info() {
     let safe_address = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
     let ether_transfer_dto = TransferDto::Ether(
         serde_json::from_str::<EtherTransferDto>(crate::json::ETHER_TRANSFER_INCOMING).unwrap(),
@@ -116,13 +193,16 @@ fn ether_transfer_dto_to_transaction_info() {
         })),
     });
 
-    let actual = ether_transfer_dto.to_transfer(&mut mock_info_provider, safe_address);
+    let actual = ether_transfer_dto
+        .to_transfer(&mut mock_info_provider, safe_address)
+        .await
+        .unwrap();
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn unknown_transfer_dto_to_transaction_info() {
+#[tokio::test]
+async fn unknown_transfer_dto_to_transaction_info() {
     let unknown_transfer_dto = TransferDto::Unknown;
     let safe_address = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
 
@@ -131,7 +211,10 @@ fn unknown_transfer_dto_to_transaction_info() {
     mock_info_provider.expect_token_info().times(0);
     mock_info_provider.expect_contract_info().times(0);
 
-    let actual = unknown_transfer_dto.to_transfer(&mut mock_info_provider, safe_address);
+    let actual = unknown_transfer_dto
+        .to_transfer(&mut mock_info_provider, safe_address)
+        .await
+        .unwrap();
 
     assert_eq!(TransactionInfo::Unknown, actual);
 }
@@ -154,8 +237,8 @@ fn unknown_transfer_dto_get_transaction_hash() {
     assert_eq!(None, actual);
 }
 
-#[test]
-fn transfer_dto_to_transaction_details() {
+#[tokio::test]
+async fn transfer_dto_to_transaction_details() {
     let safe_address = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
     let ether_transfer_dto = TransferDto::Ether(
         serde_json::from_str::<EtherTransferDto>(crate::json::ETHER_TRANSFER_INCOMING).unwrap(),
@@ -188,17 +271,19 @@ fn transfer_dto_to_transaction_details() {
             "0x41b610e8cce50bbe3aa06d6953ecc5f92a838aedc024a265c0afca7ec4f33bdf".to_string(),
         ),
         safe_app_info: None,
+        actions: None,
     };
 
     let actual = ether_transfer_dto
         .to_transaction_details(&mut mock_info_provider, safe_address)
+        .await
         .unwrap();
 
     assert_eq!(expected, actual)
 }
 
-#[test]
-fn transfer_erc20_transfer_with_erc721_token_info_returns_transfer_tx() {
+#[tokio::test]
+async fn transfer_erc20_transfer_with_erc721_token_info_returns_transfer_tx() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -228,10 +313,13 @@ fn transfer_erc20_transfer_with_erc721_token_info_returns_transfer_tx() {
         }),
     });
 
-    let actual = transfer.to_transfer(
-        &mut mock_info_provider,
-        "0xBc79855178842FDBA0c353494895DEEf509E26bB",
-    );
+    let actual = transfer
+        .to_transfer(
+            &mut mock_info_provider,
+            "0xBc79855178842FDBA0c353494895DEEf509E26bB",
+        )
+        .await
+        .unwrap();
 
     assert_eq!(expected, actual)
 }
@@ -1,32 +1,34 @@
 extern crate chrono;
 
 use crate::models::backend::transactions::{ModuleTransaction, MultisigTransaction};
-use crate::models::commons::Operation;
+use crate::models::commons::{DataDecoded, InternalTransaction, Operation, ValueDecodedType};
 use crate::models::converters::transactions::safe_app_info::safe_app_info_from;
+use crate::models::converters::transactions::SafeCall;
 use crate::models::service::transactions::details::{
     DetailedExecutionInfo, ModuleExecutionDetails, MultisigConfirmation, MultisigExecutionDetails,
-    TransactionData, TransactionDetails,
+    TransactionAction, TransactionData, TransactionDetails,
 };
 use crate::providers::info::{InfoProvider, SafeInfo, TokenInfo};
 use crate::utils::errors::ApiResult;
+use crate::utils::MULTI_SEND_TRANSACTIONS_PARAM;
+use futures::future::{FutureExt, LocalBoxFuture};
 
 impl MultisigTransaction {
-    pub fn to_transaction_details(
+    pub async fn to_transaction_details(
         &self,
         rejections: Option<Vec<String>>,
         info_provider: &mut dyn InfoProvider,
     ) -> ApiResult<TransactionDetails> {
-        let safe_info = info_provider.safe_info(&self.safe.to_string())?;
-        let gas_token = self
-            .gas_token
-            .as_ref()
-            .map(|it| info_provider.token_info(it).ok())
-            .flatten();
+        let safe_info = info_provider.safe_info(&self.safe.to_string()).await?;
+        let gas_token = match &self.gas_token {
+            Some(gas_token) => info_provider.token_info(gas_token).await.ok(),
+            None => None,
+        };
 
         Ok(TransactionDetails {
             executed_at: self.execution_date.map(|data| data.timestamp_millis()),
             tx_status: self.map_status(&safe_info),
-            tx_info: self.transaction_info(info_provider),
+            tx_info: self.transaction_info(info_provider).await,
             tx_data: Some(TransactionData {
                 to: self.to.to_owned(),
                 value: self.value.to_owned(),
@@ -38,10 +40,11 @@ impl MultisigTransaction {
             detailed_execution_info: Some(DetailedExecutionInfo::Multisig(
                 self.build_execution_details(safe_info, gas_token, rejections),
             )),
-            safe_app_info: self
-                .origin
-                .as_ref()
-                .and_then(|origin| safe_app_info_from(origin, info_provider)),
+            safe_app_info: match &self.origin {
+                Some(origin) => safe_app_info_from(origin, info_provider).await,
+                None => None,
+            },
+            actions: to_transaction_actions(&self.safe, &self.data_decoded, info_provider).await,
         })
     }
 
@@ -93,14 +96,14 @@ impl MultisigTransaction {
 }
 
 impl ModuleTransaction {
-    pub fn to_transaction_details(
+    pub async fn to_transaction_details(
         &self,
         info_provider: &mut dyn InfoProvider,
     ) -> ApiResult<TransactionDetails> {
         Ok(TransactionDetails {
             executed_at: Some(self.execution_date.timestamp_millis()),
             tx_status: self.map_status(),
-            tx_info: self.to_transaction_info(info_provider),
+            tx_info: self.to_transaction_info(info_provider).await,
             tx_data: Some(TransactionData {
                 to: self.to.to_owned(),
                 value: self.value.to_owned(),
@@ -113,6 +116,58 @@ impl ModuleTransaction {
                 address: self.module.to_owned(),
             })),
             safe_app_info: None,
+            actions: to_transaction_actions(&self.safe, &self.data_decoded, info_provider).await,
         })
     }
 }
+
+impl InternalTransaction {
+    // Classified as if the safe executed it, as multiSend is delegate called by the safe
+    async fn to_transaction_action(
+        &self,
+        safe: &str,
+        info_provider: &mut dyn InfoProvider,
+    ) -> TransactionAction {
+        let safe_call = SafeCall {
+            safe,
+            to: &self.to,
+            value: &self.value,
+            data: &self.data,
+            data_decoded: &self.data_decoded,
+            operation: Some(self.operation),
+            is_cancellation: false,
+        };
+        TransactionAction {
+            to: self.to.to_owned(),
+            value: self.value.to_owned(),
+            hex_data: self.data.to_owned(),
+            data_decoded: self.data_decoded.to_owned(),
+            operation: self.operation,
+            action_info: safe_call.transaction_info(info_provider).await,
+            actions: to_transaction_actions(safe, &self.data_decoded, info_provider).await,
+        }
+    }
+}
+
+// Boxed as the actions of nested multiSend calls are converted recursively
+pub(super) fn to_transaction_actions<'a>(
+    safe: &'a str,
+    data_decoded: &'a Option<DataDecoded>,
+    info_provider: &'a mut dyn InfoProvider,
+) -> LocalBoxFuture<'a, Option<Vec<TransactionAction>>> {
+    async move {
+        let value_decoded = data_decoded
+            .as_ref()?
+            .get_parameter_value_decoded(MULTI_SEND_TRANSACTIONS_PARAM)?;
+        match value_decoded {
+            ValueDecodedType::InternalTransaction(internal_transactions) => {
+                let mut actions = Vec::with_capacity(internal_transactions.len());
+                for transaction in internal_transactions.iter() {
+                    actions.push(transaction.to_transaction_action(safe, info_provider).await);
+                }
+                Some(actions)
+            }
+        }
+    }
+    .boxed_local()
+}
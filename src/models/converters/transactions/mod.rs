@@ -1,24 +1,128 @@
 extern crate chrono;
 
 pub mod details;
+pub mod requests;
 pub mod safe_app_info;
+pub mod simulation;
 pub mod summary;
 
 #[cfg(test)]
 mod tests;
 
 use super::get_transfer_direction;
-use crate::models::backend::transactions::{ModuleTransaction, MultisigTransaction};
+use crate::models::backend::transactions::{ModuleTransaction, MultisigTransaction, Transaction};
+use crate::models::backend::transfers::Transfer as TransferDto;
 use crate::models::commons::{DataDecoded, Operation};
 use crate::models::converters::get_address_info;
 use crate::models::service::transactions::{
-    Custom, Erc20Transfer, Erc721Transfer, EtherTransfer, SettingsChange, TransactionInfo,
-    TransactionStatus, Transfer, TransferDirection, TransferInfo,
+    Approval, ApprovalWarning, Custom, Erc1155Transfer, Erc20Transfer, Erc721Transfer,
+    EtherTransfer, SettingsChange, TransactionInfo, TransactionStatus, Transfer, TransferDirection,
+    TransferInfo,
 };
 use crate::providers::info::{InfoProvider, SafeInfo, TokenInfo, TokenType};
+use crate::utils::data_decoder::{decode_data, needs_contract_abi};
 use crate::utils::TRANSFER_METHOD;
 
+impl Transaction {
+    // Addresses the converters will look up the address info for (excluding the safe itself)
+    pub fn referenced_addresses(&self, safe: &str) -> Vec<String> {
+        let addresses = match self {
+            Transaction::Multisig(transaction) => transaction.referenced_addresses(),
+            Transaction::Ethereum(transaction) => {
+                transaction.transfers.as_ref().map_or(vec![], |transfers| {
+                    transfers
+                        .iter()
+                        .flat_map(|transfer| match transfer {
+                            TransferDto::Erc721(transfer) => {
+                                vec![transfer.from.to_owned(), transfer.to.to_owned()]
+                            }
+                            TransferDto::Erc20(transfer) => {
+                                vec![transfer.from.to_owned(), transfer.to.to_owned()]
+                            }
+                            TransferDto::Erc1155(transfer) => {
+                                vec![transfer.from.to_owned(), transfer.to.to_owned()]
+                            }
+                            TransferDto::Ether(transfer) => {
+                                vec![transfer.from.to_owned(), transfer.to.to_owned()]
+                            }
+                            TransferDto::Unknown => vec![],
+                        })
+                        .collect()
+                })
+            }
+            Transaction::Module(transaction) => {
+                vec![transaction.to.to_owned(), transaction.module.to_owned()]
+            }
+            Transaction::Unknown => vec![],
+        };
+        addresses
+            .into_iter()
+            .filter(|address| address != safe)
+            .collect()
+    }
+
+    // Decodes the data locally when the transaction service could not decode it
+    pub async fn decode_missing_data(&mut self, info_provider: &mut dyn InfoProvider) {
+        match self {
+            Transaction::Multisig(transaction) => {
+                transaction.decode_missing_data(info_provider).await
+            }
+            Transaction::Module(transaction) => {
+                transaction.decode_missing_data(info_provider).await
+            }
+            _ => {}
+        }
+    }
+
+    // Contract whose ABI `decode_missing_data` will look up
+    pub fn undecoded_target(&self) -> Option<String> {
+        match self {
+            Transaction::Multisig(transaction) => transaction.undecoded_target(),
+            Transaction::Module(transaction) => undecoded_target(
+                &transaction.to,
+                &transaction.data,
+                &transaction.data_decoded,
+            ),
+            _ => None,
+        }
+    }
+}
+
+fn undecoded_target(
+    to: &str,
+    data: &Option<String>,
+    data_decoded: &Option<DataDecoded>,
+) -> Option<String> {
+    match (data, data_decoded) {
+        (Some(data), None) if needs_contract_abi(data) => Some(to.to_owned()),
+        _ => None,
+    }
+}
+
 impl MultisigTransaction {
+    pub async fn decode_missing_data(&mut self, info_provider: &mut dyn InfoProvider) {
+        if self.data_decoded.is_none() {
+            if let Some(data) = &self.data {
+                self.data_decoded = decode_data(info_provider, &self.to, data).await;
+            }
+        }
+    }
+
+    pub fn undecoded_target(&self) -> Option<String> {
+        undecoded_target(&self.to, &self.data, &self.data_decoded)
+    }
+
+    pub fn referenced_addresses(&self) -> Vec<String> {
+        let mut addresses = vec![self.to.to_owned()];
+        if let Some(data_decoded) = &self.data_decoded {
+            addresses.extend(data_decoded.get_address_parameters());
+        }
+        addresses
+            .into_iter()
+            .filter(|address| address != &self.safe)
+            .collect()
+    }
+
     fn confirmation_count(&self) -> u64 {
         match &self.confirmations {
             Some(confirmations) => confirmations.len() as u64,
@@ -48,6 +152,15 @@ impl MultisigTransaction {
         )
     }
 
+    // Owners that still have to confirm, empty once the transaction does not need confirmations anymore
+    pub fn awaiting_signers(&self, safe_info: &SafeInfo) -> Vec<String> {
+        if self.map_status(safe_info) == TransactionStatus::AwaitingConfirmations {
+            self.missing_signers(&safe_info.owners)
+        } else {
+            vec![]
+        }
+    }
+
     fn map_status(&self, safe_info: &SafeInfo) -> TransactionStatus {
         if self.is_executed {
             if self.is_successful.unwrap_or(false) {
@@ -64,23 +177,91 @@ impl MultisigTransaction {
         }
     }
 
-    fn transaction_info(&self, info_provider: &mut dyn InfoProvider) -> TransactionInfo {
+    async fn transaction_info(&self, info_provider: &mut dyn InfoProvider) -> TransactionInfo {
+        self.to_safe_call().transaction_info(info_provider).await
+    }
+
+    fn to_safe_call(&self) -> SafeCall {
+        SafeCall {
+            safe: &self.safe,
+            to: &self.to,
+            value: &self.value,
+            data: &self.data,
+            data_decoded: &self.data_decoded,
+            operation: self.operation,
+            is_cancellation: self.is_cancellation(),
+        }
+    }
+
+    fn is_cancellation(&self) -> bool {
+        self.to == self.safe
+            && data_size(&self.data) == 0
+            && self.value.as_ref().map_or(true, |value| value == "0")
+            && self
+                .operation
+                .map_or(true, |operation| operation == Operation::CALL)
+            && self
+                .base_gas
+                .as_ref()
+                .map_or(true, |base_gas| base_gas.eq(&0))
+            && self
+                .gas_price
+                .as_ref()
+                .map_or(true, |gas_price| gas_price == "0")
+            && self.gas_token.as_ref().map_or(true, |gas_token| {
+                gas_token == "0x0000000000000000000000000000000000000000"
+            })
+            && self
+                .refund_receiver
+                .as_ref()
+                .map_or(true, |refund_receiver| {
+                    refund_receiver == "0x0000000000000000000000000000000000000000"
+                })
+            && self
+                .safe_tx_gas
+                .as_ref()
+                .map_or(true, |safe_tx_gas| safe_tx_gas.eq(&0))
+    }
+}
+
+// A call executed by the safe: the transaction itself or one of the actions it batches
+pub(super) struct SafeCall<'c> {
+    pub(super) safe: &'c str,
+    pub(super) to: &'c str,
+    pub(super) value: &'c Option<String>,
+    pub(super) data: &'c Option<String>,
+    pub(super) data_decoded: &'c Option<DataDecoded>,
+    pub(super) operation: Option<Operation>,
+    pub(super) is_cancellation: bool,
+}
+
+impl SafeCall<'_> {
+    async fn transaction_info(&self, info_provider: &mut dyn InfoProvider) -> TransactionInfo {
         let value = self.value_as_uint();
         let data_size = data_size(&self.data);
 
         if (value > 0 && data_size > 0) || !self.operation.contains(&Operation::CALL) {
-            TransactionInfo::Custom(self.to_custom(info_provider))
+            TransactionInfo::Custom(self.to_custom(info_provider).await)
         } else if value > 0 && data_size == 0 {
-            TransactionInfo::Transfer(self.to_ether_transfer(info_provider))
-        } else if value == 0
-            && data_size > 0
-            && self.safe == self.to
-            && self
-                .data_decoded
-                .as_ref()
-                .map_or_else(|| false, |it| it.is_settings_change())
+            TransactionInfo::Transfer(self.to_ether_transfer(info_provider).await)
+        } else if let Some(data_decoded) = self.data_decoded.as_ref().filter(|it| {
+            value == 0 && data_size > 0 && self.safe == self.to && it.is_settings_change()
+        }) {
+            TransactionInfo::SettingsChange(
+                self.to_settings_change(data_decoded, info_provider).await,
+            )
+        } else if self
+            .data_decoded
+            .as_ref()
+            .map_or(false, |it| it.is_erc1155_transfer_method())
+            && check_sender_or_receiver(&self.data_decoded, self.safe)
         {
-            TransactionInfo::SettingsChange(self.to_settings_change(info_provider))
+            // The signature is enough to tell ERC1155 transfers apart, the token may be unknown
+            let token = info_provider.token_info(&self.to).await.ok();
+            TransactionInfo::Transfer(
+                self.to_erc1155_transfer(token.as_ref(), info_provider)
+                    .await,
+            )
         } else if self
             .data_decoded
             .as_ref()
@@ -88,37 +269,78 @@ impl MultisigTransaction {
                 data_decoded.is_erc20_transfer_method() || data_decoded.is_erc721_transfer_method()
             })
             .unwrap_or(false)
-            && check_sender_or_receiver(&self.data_decoded, &self.safe)
+            && check_sender_or_receiver(&self.data_decoded, self.safe)
         {
-            match info_provider.token_info(&self.to) {
+            match info_provider.token_info(&self.to).await {
                 Ok(token) => match token.token_type {
-                    TokenType::Erc20 => {
-                        TransactionInfo::Transfer(self.to_erc20_transfer(&token, info_provider))
-                    }
-                    TokenType::Erc721 => {
-                        TransactionInfo::Transfer(self.to_erc721_transfer(&token, info_provider))
-                    }
-                    _ => TransactionInfo::Custom(self.to_custom(info_provider)),
+                    TokenType::Erc20 => TransactionInfo::Transfer(
+                        self.to_erc20_transfer(&token, info_provider).await,
+                    ),
+                    TokenType::Erc721 => TransactionInfo::Transfer(
+                        self.to_erc721_transfer(&token, info_provider).await,
+                    ),
+                    _ => TransactionInfo::Custom(self.to_custom(info_provider).await),
                 },
-                _ => TransactionInfo::Custom(self.to_custom(info_provider)),
+                _ => TransactionInfo::Custom(self.to_custom(info_provider).await),
+            }
+        } else if value == 0
+            && self
+                .data_decoded
+                .as_ref()
+                .map_or(false, |it| it.is_approval_method())
+        {
+            let token = info_provider.token_info(&self.to).await.ok();
+            match self.to_approval(token, info_provider).await {
+                Some(approval) => TransactionInfo::Approval(approval),
+                None => TransactionInfo::Custom(self.to_custom(info_provider).await),
             }
         } else {
-            TransactionInfo::Custom(self.to_custom(info_provider))
+            TransactionInfo::Custom(self.to_custom(info_provider).await)
         }
     }
 
-    fn to_erc20_transfer(
+    // ERC721 `approve` (or `approve` of an unknown token) is kept as a custom transaction
+    async fn to_approval(
+        &self,
+        token: Option<TokenInfo>,
+        info_provider: &mut dyn InfoProvider,
+    ) -> Option<Approval> {
+        let data_decoded = self.data_decoded.as_ref()?;
+        let change = data_decoded.to_allowance_change()?;
+        if !change.is_allowance_of(token.as_ref()) {
+            return None;
+        }
+        let warning = if change.revoked {
+            ApprovalWarning::None
+        } else if change.unlimited {
+            ApprovalWarning::High
+        } else {
+            ApprovalWarning::Medium
+        };
+        Some(Approval {
+            method: data_decoded.method.to_owned(),
+            token_address: self.to.to_owned(),
+            token_info: token,
+            spender_info: get_address_info(self.safe, &change.spender, info_provider).await,
+            spender: change.spender,
+            amount: change.amount,
+            unlimited: change.unlimited,
+            warning,
+        })
+    }
+
+    async fn to_erc20_transfer(
         &self,
         token: &TokenInfo,
         info_provider: &mut dyn InfoProvider,
     ) -> Transfer {
-        let sender = get_from_param(&self.data_decoded, &self.safe);
+        let sender = get_from_param(&self.data_decoded, self.safe);
         let recipient = get_to_param(&self.data_decoded, "0x0");
-        let direction = get_transfer_direction(&self.safe, &sender, &recipient);
+        let direction = get_transfer_direction(self.safe, &sender, &recipient);
         Transfer {
-            sender_info: get_address_info(&self.safe, &sender, info_provider),
+            sender_info: get_address_info(self.safe, &sender, info_provider).await,
             sender,
-            recipient_info: get_address_info(&self.safe, &recipient, info_provider),
+            recipient_info: get_address_info(self.safe, &recipient, info_provider).await,
             recipient,
             direction,
             transfer_info: TransferInfo::Erc20(Erc20Transfer {
@@ -136,18 +358,18 @@ impl MultisigTransaction {
         }
     }
 
-    fn to_erc721_transfer(
+    async fn to_erc721_transfer(
         &self,
         token: &TokenInfo,
         info_provider: &mut dyn InfoProvider,
     ) -> Transfer {
-        let sender = get_from_param(&self.data_decoded, &self.safe);
+        let sender = get_from_param(&self.data_decoded, self.safe);
         let recipient = get_to_param(&self.data_decoded, "0x0");
-        let direction = get_transfer_direction(&self.safe, &sender, &recipient);
+        let direction = get_transfer_direction(self.safe, &sender, &recipient);
         Transfer {
-            sender_info: get_address_info(&self.safe, &sender, info_provider),
+            sender_info: get_address_info(self.safe, &sender, info_provider).await,
             sender,
-            recipient_info: get_address_info(&self.safe, &recipient, info_provider),
+            recipient_info: get_address_info(self.safe, &recipient, info_provider).await,
             recipient,
             direction,
             transfer_info: TransferInfo::Erc721(Erc721Transfer {
@@ -167,36 +389,64 @@ impl MultisigTransaction {
         }
     }
 
-    fn to_ether_transfer(&self, info_provider: &mut dyn InfoProvider) -> Transfer {
+    async fn to_erc1155_transfer(
+        &self,
+        token: Option<&TokenInfo>,
+        info_provider: &mut dyn InfoProvider,
+    ) -> Transfer {
+        let sender = get_from_param(&self.data_decoded, self.safe);
+        let recipient = get_to_param(&self.data_decoded, "0x0");
+        let direction = get_transfer_direction(self.safe, &sender, &recipient);
+        let data_decoded = self.data_decoded.as_ref();
+        Transfer {
+            sender_info: get_address_info(self.safe, &sender, info_provider).await,
+            sender,
+            recipient_info: get_address_info(self.safe, &recipient, info_provider).await,
+            recipient,
+            direction,
+            transfer_info: TransferInfo::Erc1155(Erc1155Transfer {
+                token_address: self.to.to_owned(),
+                // (from, to, id(s), amount(s), data) for single and batch transfers
+                token_ids: data_decoded.map_or(vec![], |it| it.get_parameter_values_at(2)),
+                amounts: data_decoded.map_or(vec![], |it| it.get_parameter_values_at(3)),
+                token_name: token.map(|it| it.name.to_owned()),
+                token_symbol: token.map(|it| it.symbol.to_owned()),
+                logo_uri: token.and_then(|it| it.logo_uri.to_owned()),
+            }),
+        }
+    }
+
+    async fn to_ether_transfer(&self, info_provider: &mut dyn InfoProvider) -> Transfer {
         Transfer {
             sender_info: None,
             sender: self.safe.to_owned(),
-            recipient_info: info_provider.full_address_info_search(&self.to).ok(),
+            recipient_info: info_provider.full_address_info_search(&self.to).await.ok(),
             recipient: self.to.to_owned(),
             direction: TransferDirection::Outgoing,
             transfer_info: TransferInfo::Ether(EtherTransfer {
-                value: self.value.as_ref().unwrap().to_string(),
+                value: self.value.as_ref().unwrap_or(&String::from("0")).clone(),
             }),
         }
     }
 
-    fn to_settings_change(&self, info_provider: &mut dyn InfoProvider) -> SettingsChange {
+    async fn to_settings_change(
+        &self,
+        data_decoded: &DataDecoded,
+        info_provider: &mut dyn InfoProvider,
+    ) -> SettingsChange {
         SettingsChange {
-            data_decoded: self.data_decoded.as_ref().unwrap().to_owned(),
-            settings_info: self
-                .data_decoded
-                .as_ref()
-                .and_then(|it| it.to_settings_info(info_provider)),
+            data_decoded: data_decoded.to_owned(),
+            settings_info: data_decoded.to_settings_info(info_provider).await,
         }
     }
 
-    fn to_custom(&self, info_provider: &mut dyn InfoProvider) -> Custom {
+    async fn to_custom(&self, info_provider: &mut dyn InfoProvider) -> Custom {
         Custom {
             to: self.to.to_owned(),
-            to_info: info_provider.full_address_info_search(&self.to).ok(),
-            is_cancellation: self.is_cancellation(),
+            to_info: info_provider.full_address_info_search(&self.to).await.ok(),
+            is_cancellation: self.is_cancellation,
             data_size: data_size(&self.data).to_string(),
-            value: self.value.as_ref().unwrap().into(),
+            value: self.value.as_ref().unwrap_or(&String::from("0")).clone(),
             method_name: self.data_decoded.as_ref().map(|it| it.method.to_owned()),
             action_count: self
                 .data_decoded
@@ -212,42 +462,20 @@ impl MultisigTransaction {
             .flatten()
             .unwrap_or(0)
     }
-
-    fn is_cancellation(&self) -> bool {
-        self.to == self.safe
-            && data_size(&self.data) == 0
-            && self.value.as_ref().map_or(true, |value| value == "0")
-            && self
-                .operation
-                .map_or(true, |operation| operation == Operation::CALL)
-            && self
-                .base_gas
-                .as_ref()
-                .map_or(true, |base_gas| base_gas.eq(&0))
-            && self
-                .gas_price
-                .as_ref()
-                .map_or(true, |gas_price| gas_price == "0")
-            && self.gas_token.as_ref().map_or(true, |gas_token| {
-                gas_token == "0x0000000000000000000000000000000000000000"
-            })
-            && self
-                .refund_receiver
-                .as_ref()
-                .map_or(true, |refund_receiver| {
-                    refund_receiver == "0x0000000000000000000000000000000000000000"
-                })
-            && self
-                .safe_tx_gas
-                .as_ref()
-                .map_or(true, |safe_tx_gas| safe_tx_gas.eq(&0))
-    }
 }
 
 impl ModuleTransaction {
-    fn to_transaction_info(&self, info_provider: &mut dyn InfoProvider) -> TransactionInfo {
+    pub async fn decode_missing_data(&mut self, info_provider: &mut dyn InfoProvider) {
+        if self.data_decoded.is_none() {
+            if let Some(data) = &self.data {
+                self.data_decoded = decode_data(info_provider, &self.to, data).await;
+            }
+        }
+    }
+
+    async fn to_transaction_info(&self, info_provider: &mut dyn InfoProvider) -> TransactionInfo {
         TransactionInfo::Custom(Custom {
-            to_info: info_provider.full_address_info_search(&self.to).ok(),
+            to_info: info_provider.full_address_info_search(&self.to).await.ok(),
             to: self.to.to_owned(),
             data_size: data_size(&self.data).to_string(),
             value: self.value.as_ref().unwrap_or(&String::from("0")).clone(),
@@ -303,10 +531,10 @@ fn get_to_param(data_decoded: &Option<DataDecoded>, fallback: &str) -> String {
 }
 
 fn check_sender_or_receiver(data_decoded: &Option<DataDecoded>, expected: &str) -> bool {
-    if data_decoded.is_none() {
-        return false;
+    let data = match data_decoded {
+        Some(data) => data,
+        None => return false,
     };
-    let data = data_decoded.as_ref().unwrap();
     data.method == TRANSFER_METHOD
         || &get_from_param(data_decoded, "") == expected
         || &get_to_param(data_decoded, "") == expected
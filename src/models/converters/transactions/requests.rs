@@ -0,0 +1,73 @@
+use crate::models::service::transactions::requests::{
+    MultisigTransactionRequest, TransactionPrepareRequest,
+};
+use crate::utils::errors::ApiResult;
+use crate::utils::transactions::SafeTransaction;
+use ethabi::Uint;
+use ethereum_types::Address;
+
+impl MultisigTransactionRequest {
+    pub fn to_safe_transaction(&self) -> ApiResult<SafeTransaction> {
+        Ok(SafeTransaction {
+            to: parse_address(&self.to)?,
+            value: parse_uint(&self.value)?,
+            data: parse_data(&self.data)?,
+            operation: self.operation,
+            safe_tx_gas: parse_uint(&self.safe_tx_gas)?,
+            base_gas: parse_uint(&self.base_gas)?,
+            gas_price: parse_uint(&self.gas_price)?,
+            gas_token: parse_address(&self.gas_token)?,
+            refund_receiver: parse_optional_address(&self.refund_receiver)?,
+            nonce: parse_uint(&self.nonce)?,
+        })
+    }
+}
+
+impl TransactionPrepareRequest {
+    // Gas parameters that are not set default to 0, like for a transaction without refund
+    pub fn to_safe_transaction(&self, nonce: Uint) -> ApiResult<SafeTransaction> {
+        Ok(SafeTransaction {
+            to: parse_address(&self.to)?,
+            value: parse_uint(&self.value)?,
+            data: parse_data(&self.data)?,
+            operation: self.operation,
+            safe_tx_gas: parse_optional_uint(&self.safe_tx_gas)?,
+            base_gas: parse_optional_uint(&self.base_gas)?,
+            gas_price: parse_optional_uint(&self.gas_price)?,
+            gas_token: parse_optional_address(&self.gas_token)?,
+            refund_receiver: parse_optional_address(&self.refund_receiver)?,
+            nonce,
+        })
+    }
+}
+
+fn parse_address(address: &str) -> ApiResult<Address> {
+    serde_json::from_value(serde_json::Value::String(address.to_string()))
+        .map_err(|_| client_error!(422, "Invalid address"))
+}
+
+fn parse_optional_address(address: &Option<String>) -> ApiResult<Address> {
+    match address {
+        Some(address) => parse_address(address),
+        None => Ok(Address::zero()),
+    }
+}
+
+fn parse_uint(value: &str) -> ApiResult<Uint> {
+    Uint::from_dec_str(value).map_err(|_| client_error!(422, "Invalid number"))
+}
+
+fn parse_optional_uint(value: &Option<String>) -> ApiResult<Uint> {
+    match value {
+        Some(value) => parse_uint(value),
+        None => Ok(Uint::zero()),
+    }
+}
+
+fn parse_data(data: &Option<String>) -> ApiResult<Vec<u8>> {
+    match data {
+        Some(data) => hex::decode(data.trim_start_matches("0x"))
+            .map_err(|_| client_error!(422, "Invalid data")),
+        None => Ok(vec![]),
+    }
+}
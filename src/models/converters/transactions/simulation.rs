@@ -0,0 +1,162 @@
+use crate::models::service::transactions::requests::BalanceChange;
+use crate::models::service::transactions::{
+    Erc20Transfer, Erc721Transfer, EtherTransfer, TransferDirection, TransferInfo,
+};
+use crate::providers::info::{InfoProvider, TokenInfo, TokenType};
+use crate::utils::data_decoder::checksum_address;
+use ethabi::{Address, Uint};
+use ethcontract_common::hash::keccak256;
+use evm::backend::Log;
+
+// Same signature for ERC20 and ERC721, the token id of ERC721 is indexed
+const TRANSFER_EVENT_SIGNATURE: &str = "Transfer(address,address,uint256)";
+
+// Token moved by a `Transfer` event
+struct Movement {
+    token_type: TokenType,
+    token_address: Address,
+    token_id: Option<Uint>,
+    from: Address,
+    to: Address,
+    value: Uint,
+}
+
+struct Total {
+    token_type: TokenType,
+    token_address: Address,
+    token_id: Option<Uint>,
+    incoming: Uint,
+    outgoing: Uint,
+}
+
+// Net changes of the balances of `safe`: the native token from its balance before and after the
+// execution, tokens from the ERC20/ERC721 `Transfer` events in order of appearance. The logs of
+// the executor only contain the events of calls that didn't revert.
+pub async fn to_balance_changes(
+    safe: &Address,
+    balance_before: Uint,
+    balance_after: Uint,
+    logs: &[Log],
+    info_provider: &mut dyn InfoProvider,
+) -> Vec<BalanceChange> {
+    let mut balance_changes = vec![];
+    if balance_before != balance_after {
+        let (direction, value) = net_change(balance_after, balance_before);
+        balance_changes.push(BalanceChange {
+            direction,
+            transfer_info: TransferInfo::Ether(EtherTransfer {
+                value: value.to_string(),
+            }),
+        });
+    }
+
+    let mut totals: Vec<Total> = vec![];
+    for movement in logs.iter().filter_map(to_movement) {
+        let incoming = movement.to == *safe;
+        let outgoing = movement.from == *safe;
+        if incoming == outgoing {
+            continue;
+        }
+        let index = match totals.iter().position(|total| {
+            total.token_type == movement.token_type
+                && total.token_address == movement.token_address
+                && total.token_id == movement.token_id
+        }) {
+            Some(index) => index,
+            None => {
+                totals.push(Total {
+                    token_type: movement.token_type,
+                    token_address: movement.token_address,
+                    token_id: movement.token_id,
+                    incoming: Uint::zero(),
+                    outgoing: Uint::zero(),
+                });
+                totals.len() - 1
+            }
+        };
+        let total = &mut totals[index];
+        if incoming {
+            total.incoming = total.incoming.saturating_add(movement.value);
+        } else {
+            total.outgoing = total.outgoing.saturating_add(movement.value);
+        }
+    }
+
+    for total in totals
+        .into_iter()
+        .filter(|total| total.incoming != total.outgoing)
+    {
+        let token_address = checksum_address(total.token_address.as_bytes());
+        let token_info = info_provider.token_info(&token_address).await.ok();
+        let (direction, value) = net_change(total.incoming, total.outgoing);
+        balance_changes.push(BalanceChange {
+            direction,
+            transfer_info: to_transfer_info(
+                total.token_type,
+                token_address,
+                token_info.as_ref(),
+                total.token_id,
+                value,
+            ),
+        });
+    }
+    balance_changes
+}
+
+fn net_change(incoming: Uint, outgoing: Uint) -> (TransferDirection, Uint) {
+    if incoming > outgoing {
+        (TransferDirection::Incoming, incoming - outgoing)
+    } else {
+        (TransferDirection::Outgoing, outgoing - incoming)
+    }
+}
+
+fn to_transfer_info(
+    token_type: TokenType,
+    token_address: String,
+    token_info: Option<&TokenInfo>,
+    token_id: Option<Uint>,
+    value: Uint,
+) -> TransferInfo {
+    match (token_type, token_id) {
+        (TokenType::Erc721, Some(token_id)) => TransferInfo::Erc721(Erc721Transfer {
+            token_address,
+            token_id: token_id.to_string(),
+            token_name: token_info.map(|it| it.name.to_owned()),
+            token_symbol: token_info.map(|it| it.symbol.to_owned()),
+            logo_uri: token_info.and_then(|it| it.logo_uri.to_owned()),
+        }),
+        _ => TransferInfo::Erc20(Erc20Transfer {
+            token_address,
+            token_name: token_info.map(|it| it.name.to_owned()),
+            token_symbol: token_info.map(|it| it.symbol.to_owned()),
+            logo_uri: token_info.and_then(|it| it.logo_uri.to_owned()),
+            decimals: token_info.map(|it| it.decimals),
+            value: value.to_string(),
+        }),
+    }
+}
+
+// ERC20 transfers have the value as data, ERC721 transfers index the token id as 4th topic
+fn to_movement(log: &Log) -> Option<Movement> {
+    if log.topics.first()?.as_bytes() != &keccak256(TRANSFER_EVENT_SIGNATURE)[..] {
+        return None;
+    }
+    let (token_type, token_id, value) = match (log.topics.len(), log.data.len()) {
+        (3, 32) => (TokenType::Erc20, None, Uint::from_big_endian(&log.data)),
+        (4, 0) => (
+            TokenType::Erc721,
+            Some(Uint::from_big_endian(log.topics[3].as_bytes())),
+            Uint::one(),
+        ),
+        _ => return None,
+    };
+    Some(Movement {
+        token_type,
+        token_address: log.address,
+        token_id,
+        from: Address::from_slice(&log.topics[1].as_bytes()[12..]),
+        to: Address::from_slice(&log.topics[2].as_bytes()[12..]),
+        value,
+    })
+}
@@ -10,25 +10,28 @@ use crate::models::service::transactions::{
     Creation, TransactionInfo, TransactionStatus, ID_PREFIX_CREATION_TX, ID_PREFIX_ETHEREUM_TX,
     ID_PREFIX_MODULE_TX, ID_PREFIX_MULTISIG_TX,
 };
+use crate::providers::address_info::AddressInfo;
 use crate::providers::info::InfoProvider;
 use crate::utils::errors::ApiResult;
 use crate::utils::hex_hash;
 
 impl Transaction {
-    pub fn to_transaction_summary(
+    pub async fn to_transaction_summary(
         &self,
         info_provider: &mut dyn InfoProvider,
         safe: &str,
     ) -> ApiResult<Vec<TransactionSummary>> {
         match self {
             Transaction::Multisig(transaction) => {
-                Ok(transaction.to_transaction_summary(info_provider)?)
+                Ok(transaction.to_transaction_summary(info_provider).await?)
             }
             Transaction::Ethereum(transaction) => {
-                Ok(transaction.to_transaction_summary(info_provider, safe))
+                transaction
+                    .to_transaction_summary(info_provider, safe)
+                    .await
             }
             Transaction::Module(transaction) => {
-                Ok(transaction.to_transaction_summary(info_provider))
+                Ok(transaction.to_transaction_summary(info_provider).await)
             }
             Transaction::Unknown => bail!("Unknown transaction type from backend"),
         }
@@ -36,11 +39,11 @@ impl Transaction {
 }
 
 impl MultisigTransaction {
-    pub fn to_transaction_summary(
+    pub async fn to_transaction_summary(
         &self,
         info_provider: &mut dyn InfoProvider,
     ) -> ApiResult<Vec<TransactionSummary>> {
-        let safe_info = info_provider.safe_info(&self.safe.to_string())?;
+        let safe_info = info_provider.safe_info(&self.safe.to_string()).await?;
         let tx_status = self.map_status(&safe_info);
         let missing_signers = if tx_status == TransactionStatus::AwaitingConfirmations {
             Some(self.missing_signers(&safe_info.owners))
@@ -60,45 +63,43 @@ impl MultisigTransaction {
                 confirmations_required: self.confirmation_required(safe_info.threshold),
                 missing_signers,
             }),
-            tx_info: self.transaction_info(info_provider),
-            safe_app_info: self
-                .origin
-                .as_ref()
-                .and_then(|origin| safe_app_info_from(origin, info_provider)),
+            tx_info: self.transaction_info(info_provider).await,
+            safe_app_info: match &self.origin {
+                Some(origin) => safe_app_info_from(origin, info_provider).await,
+                None => None,
+            },
         }])
     }
 }
 
 impl EthereumTransaction {
-    pub(super) fn to_transaction_summary(
+    pub(super) async fn to_transaction_summary(
         &self,
         info_provider: &mut dyn InfoProvider,
         safe: &str,
-    ) -> Vec<TransactionSummary> {
-        match &self.transfers {
-            Some(transfers) => transfers
-                .into_iter()
-                .map(|transfer| TransactionSummary {
-                    id: create_id!(
-                        ID_PREFIX_ETHEREUM_TX,
-                        safe,
-                        self.tx_hash,
-                        hex_hash(transfer)
-                    ),
-                    timestamp: self.execution_date.timestamp_millis(),
-                    tx_status: TransactionStatus::Success,
-                    execution_info: None,
-                    safe_app_info: None,
-                    tx_info: transfer.to_transfer(info_provider, safe),
-                })
-                .collect(),
-            _ => vec![],
+    ) -> ApiResult<Vec<TransactionSummary>> {
+        let mut summaries = vec![];
+        for transfer in self.transfers.iter().flatten() {
+            summaries.push(TransactionSummary {
+                id: create_id!(
+                    ID_PREFIX_ETHEREUM_TX,
+                    safe,
+                    self.tx_hash,
+                    hex_hash(transfer)
+                ),
+                timestamp: self.execution_date.timestamp_millis(),
+                tx_status: TransactionStatus::Success,
+                execution_info: None,
+                safe_app_info: None,
+                tx_info: transfer.to_transfer(info_provider, safe).await?,
+            });
         }
+        Ok(summaries)
     }
 }
 
 impl ModuleTransaction {
-    pub(super) fn to_transaction_summary(
+    pub(super) async fn to_transaction_summary(
         &self,
         info_provider: &mut dyn InfoProvider,
     ) -> Vec<TransactionSummary> {
@@ -113,40 +114,45 @@ impl ModuleTransaction {
             tx_status: self.map_status(),
             execution_info: None,
             safe_app_info: None,
-            tx_info: self.to_transaction_info(info_provider),
+            tx_info: self.to_transaction_info(info_provider).await,
         }]
     }
 }
 
 impl CreationTransaction {
-    pub fn to_transaction_summary(
+    pub async fn to_transaction_summary(
         &self,
         safe_address: &String,
         info_provider: &mut dyn InfoProvider,
     ) -> TransactionSummary {
+        let creator_info = info_provider.contract_info(&self.creator).await.ok();
+        let implementation_info = contract_info(&self.master_copy, info_provider).await;
+        let factory_info = contract_info(&self.factory_address, info_provider).await;
         TransactionSummary {
             id: create_id!(ID_PREFIX_CREATION_TX, safe_address),
             timestamp: self.created.timestamp_millis(),
             tx_status: TransactionStatus::Success,
             tx_info: TransactionInfo::Creation(Creation {
                 creator: self.creator.clone(),
-                creator_info: info_provider.contract_info(&self.creator).ok(),
+                creator_info,
                 transaction_hash: self.transaction_hash.clone(),
                 implementation: self.master_copy.clone(),
-                implementation_info: self
-                    .master_copy
-                    .as_ref()
-                    .map(|address| info_provider.contract_info(address).ok())
-                    .flatten(),
+                implementation_info,
                 factory: self.factory_address.clone(),
-                factory_info: self
-                    .factory_address
-                    .as_ref()
-                    .map(|address| info_provider.contract_info(address).ok())
-                    .flatten(),
+                factory_info,
             }),
             execution_info: None,
             safe_app_info: None,
         }
     }
 }
+
+async fn contract_info(
+    address: &Option<String>,
+    info_provider: &mut dyn InfoProvider,
+) -> Option<AddressInfo> {
+    match address {
+        Some(address) => info_provider.contract_info(address).await.ok(),
+        None => None,
+    }
+}
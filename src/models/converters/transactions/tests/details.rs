@@ -7,13 +7,13 @@ use crate::models::service::transactions::details::{
     TransactionData, TransactionDetails,
 };
 use crate::models::service::transactions::{
-    Custom, Erc721Transfer, TransactionInfo, TransactionStatus, Transfer, TransferDirection,
-    TransferInfo,
+    Approval, ApprovalWarning, Custom, Erc721Transfer, TransactionInfo, TransactionStatus,
+    Transfer, TransferDirection, TransferInfo,
 };
 use crate::providers::info::*;
 
-#[test]
-fn multisig_custom_transaction_to_transaction_details() {
+#[tokio::test]
+async fn multisig_custom_transaction_to_transaction_details() {
     let multisig_tx =
         serde_json::from_str::<MultisigTransaction>(crate::json::MULTISIG_TX_CUSTOM).unwrap();
     let safe_info = serde_json::from_str::<SafeInfo>(crate::json::SAFE_WITH_MODULES).unwrap();
@@ -27,7 +27,7 @@ fn multisig_custom_transaction_to_transaction_details() {
         .return_once(move |_| Ok(safe_info));
     mock_info_provider
         .expect_token_info()
-        .times(1)
+        .times(2)
         .returning(move |_| bail!("Token Address 0x0"));
     mock_info_provider
         .expect_full_address_info_search()
@@ -38,14 +38,15 @@ fn multisig_custom_transaction_to_transaction_details() {
         executed_at: multisig_tx.execution_date.map(|it| it.timestamp_millis()),
         tx_status: TransactionStatus::Success,
         tx_hash: Some("0x0ebb2c317f55c96469e0ed2014f5833dc02a70b42f0ac52f4630938900caa698".to_string()),
-        tx_info: TransactionInfo::Custom(Custom {
-            to: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
-            data_size: "68".to_string(),
-            value: "0".to_string(),
-            method_name: Some("approve".to_string()),
-            action_count: None,
-            to_info: None,
-            is_cancellation: false,
+        tx_info: TransactionInfo::Approval(Approval {
+            method: "approve".to_string(),
+            token_address: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
+            token_info: None,
+            spender: "0xae9844F89D98c150F5e61bfC676D68b492155990".to_string(),
+            spender_info: None,
+            amount: Some("500000000000000".to_string()),
+            unlimited: false,
+            warning: ApprovalWarning::Medium,
         }),
         tx_data: Some(TransactionData {
             hex_data: Some(String::from("0x095ea7b3000000000000000000000000ae9844f89d98c150f5e61bfc676d68b4921559900000000000000000000000000000000000000000000000000001c6bf52634000")),
@@ -104,16 +105,18 @@ fn multisig_custom_transaction_to_transaction_details() {
                 gas_token_info: None,
             })),
         safe_app_info: None,
+        actions: None,
     };
 
     let actual =
-        MultisigTransaction::to_transaction_details(&multisig_tx, None, &mut mock_info_provider);
+        MultisigTransaction::to_transaction_details(&multisig_tx, None, &mut mock_info_provider)
+            .await;
 
     assert_eq!(expected, actual.unwrap());
 }
 
-#[test]
-fn module_transaction_to_transaction_details_success() {
+#[tokio::test]
+async fn module_transaction_to_transaction_details_success() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -150,16 +153,18 @@ fn module_transaction_to_transaction_details_success() {
                 address: "0xfa559f0932b7B60d90B4af0b8813d4088465096b".to_string()
             })),
         safe_app_info: None,
+        actions: None,
     };
 
     let actual =
-        ModuleTransaction::to_transaction_details(&module_transaction, &mut mock_info_provider);
+        ModuleTransaction::to_transaction_details(&module_transaction, &mut mock_info_provider)
+            .await;
 
     assert_eq!(expected, actual.unwrap());
 }
 
-#[test]
-fn module_transaction_to_transaction_details_failed() {
+#[tokio::test]
+async fn module_transaction_to_transaction_details_failed() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -196,16 +201,18 @@ fn module_transaction_to_transaction_details_failed() {
                 address: "0xfa559f0932b7B60d90B4af0b8813d4088465096b".to_string()
             })),
         safe_app_info: None,
+        actions: None,
     };
 
     let actual =
-        ModuleTransaction::to_transaction_details(&module_transaction, &mut mock_info_provider);
+        ModuleTransaction::to_transaction_details(&module_transaction, &mut mock_info_provider)
+            .await;
 
     assert_eq!(expected, actual.unwrap());
 }
 
-#[test]
-fn ethereum_tx_transfer_to_transaction_details() {
+#[tokio::test]
+async fn ethereum_tx_transfer_to_transaction_details() {
     let transfer =
         serde_json::from_str::<TransferDto>(crate::json::ERC_20_TRANSFER_WITH_ERC721_TOKEN_INFO)
             .unwrap();
@@ -233,6 +240,7 @@ fn ethereum_tx_transfer_to_transaction_details() {
         tx_data: None,
         detailed_execution_info: None,
         safe_app_info: None,
+        actions: None,
     };
 
     let mut mock_info_provider = MockInfoProvider::new();
@@ -247,13 +255,14 @@ fn ethereum_tx_transfer_to_transaction_details() {
         &transfer,
         &mut mock_info_provider,
         "0xBc79855178842FDBA0c353494895DEEf509E26bB",
-    );
+    )
+    .await;
 
     assert_eq!(expected, actual.unwrap());
 }
 
-#[test]
-fn multisig_transaction_with_origin() {
+#[tokio::test]
+async fn multisig_transaction_with_origin() {
     let multisig_tx =
         serde_json::from_str::<MultisigTransaction>(crate::json::MULTISIG_TX_WITH_ORIGIN).unwrap();
     let mut safe_info = serde_json::from_str::<SafeInfo>(crate::json::SAFE_WITH_MODULES).unwrap();
@@ -281,14 +290,15 @@ fn multisig_transaction_with_origin() {
         });
     mock_info_provider
         .expect_full_address_info_search()
-        .times(1)
-        .return_once(move |_| bail!("no address info"));
+        .times(2)
+        .returning(move |_| bail!("no address info"));
 
     let mut expected = crate::json::TX_DETAILS_WITH_ORIGIN.replace('\n', "");
     expected.retain(|c| !c.is_whitespace());
 
     let actual =
         MultisigTransaction::to_transaction_details(&multisig_tx, None, &mut mock_info_provider)
+            .await
             .unwrap();
 
     let actual_json = serde_json::to_string(&actual).unwrap();
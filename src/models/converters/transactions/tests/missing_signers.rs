@@ -56,3 +56,29 @@ fn missing_signers_on_awaiting_confirmation() {
     ];
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn awaiting_signers_on_awaiting_confirmations() {
+    let tx = serde_json::from_str::<MultisigTransaction>(
+        crate::json::MULTISIG_TX_AWAITING_CONFIRMATIONS,
+    )
+    .unwrap();
+    let safe_info = serde_json::from_str::<SafeInfo>(crate::json::SAFE_WITH_THRESHOLD_TWO).unwrap();
+
+    let actual = tx.awaiting_signers(&safe_info);
+
+    assert!(!actual.is_empty());
+    assert_eq!(tx.missing_signers(&safe_info.owners), actual);
+}
+
+#[test]
+fn awaiting_signers_on_awaiting_execution() {
+    let tx =
+        serde_json::from_str::<MultisigTransaction>(crate::json::MULTISIG_TX_AWAITING_EXECUTION)
+            .unwrap();
+    let safe_info = serde_json::from_str::<SafeInfo>(crate::json::SAFE_WITH_MODULES).unwrap();
+
+    let actual = tx.awaiting_signers(&safe_info);
+
+    assert!(actual.is_empty());
+}
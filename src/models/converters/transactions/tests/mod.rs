@@ -4,7 +4,10 @@ mod details;
 mod is_cancellation;
 pub(super) mod map_status;
 pub(super) mod missing_signers;
+mod referenced_addresses;
 mod safe_app_info;
+mod simulation;
 mod summary;
+mod transaction_actions;
 pub(super) mod transaction_types;
 pub(super) mod transfer_type_checks;
@@ -0,0 +1,38 @@
+use crate::models::backend::transactions::{MultisigTransaction, Transaction};
+
+#[test]
+fn referenced_addresses_multisig_erc20_transfer() {
+    let tx = serde_json::from_str::<MultisigTransaction>(crate::json::MULTISIG_TX_ERC20_TRANSFER)
+        .unwrap();
+    let expected = vec![
+        "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
+        "0x65F8236309e5A99Ff0d129d04E486EBCE20DC7B0".to_string(),
+    ];
+
+    let actual = tx.referenced_addresses();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn referenced_addresses_module_transaction() {
+    let tx = serde_json::from_str::<Transaction>(crate::json::MODULE_TX).unwrap();
+    let expected = vec![
+        "0xaAEb2035FF394fdB2C879190f95e7676f1A9444B".to_string(),
+        "0xfa559f0932b7B60d90B4af0b8813d4088465096b".to_string(),
+    ];
+
+    let actual = tx.referenced_addresses("0x9422ff6AFB126C31F62057e2853d65cBB73f4608");
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn referenced_addresses_excludes_safe() {
+    let tx = serde_json::from_str::<Transaction>(crate::json::MODULE_TX).unwrap();
+    let expected = vec!["0xfa559f0932b7B60d90B4af0b8813d4088465096b".to_string()];
+
+    let actual = tx.referenced_addresses("0xaAEb2035FF394fdB2C879190f95e7676f1A9444B");
+
+    assert_eq!(expected, actual);
+}
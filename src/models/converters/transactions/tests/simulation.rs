@@ -0,0 +1,166 @@
+use crate::models::converters::transactions::simulation::to_balance_changes;
+use crate::models::service::transactions::requests::BalanceChange;
+use crate::models::service::transactions::{
+    Erc20Transfer, Erc721Transfer, EtherTransfer, TransferDirection, TransferInfo,
+};
+use crate::providers::info::*;
+use ethabi::{Address, Hash, Uint};
+use ethcontract_common::hash::keccak256;
+use evm::backend::Log;
+use mockall::predicate::eq;
+
+const SAFE: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const OTHER: &str = "0xF2565317F3Ae8Ae9EA98E9Fe1e7FADC77F823cbD";
+const ERC20_TOKEN: &str = "0xd6f5Bef6bb4acD235CF85c0ce196316d10785d67";
+const ERC721_TOKEN: &str = "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02";
+
+fn address(address: &str) -> Address {
+    address.trim_start_matches("0x").parse().unwrap()
+}
+
+fn topic(value: &[u8]) -> Hash {
+    let mut topic = [0u8; 32];
+    topic[32 - value.len()..].copy_from_slice(value);
+    Hash::from(topic)
+}
+
+fn erc20_transfer(from: &str, to: &str, value: u64) -> Log {
+    let mut data = [0u8; 32];
+    Uint::from(value).to_big_endian(&mut data);
+    Log {
+        address: address(ERC20_TOKEN),
+        topics: vec![
+            Hash::from(keccak256("Transfer(address,address,uint256)")),
+            topic(address(from).as_bytes()),
+            topic(address(to).as_bytes()),
+        ],
+        data: data.to_vec(),
+    }
+}
+
+fn erc721_transfer(from: &str, to: &str, token_id: u8) -> Log {
+    Log {
+        address: address(ERC721_TOKEN),
+        topics: vec![
+            Hash::from(keccak256("Transfer(address,address,uint256)")),
+            topic(address(from).as_bytes()),
+            topic(address(to).as_bytes()),
+            topic(&[token_id]),
+        ],
+        data: vec![],
+    }
+}
+
+#[tokio::test]
+async fn to_balance_changes_swap() {
+    let logs = vec![
+        erc20_transfer(OTHER, SAFE, 6000),
+        erc721_transfer(SAFE, OTHER, 3),
+        erc20_transfer(SAFE, OTHER, 1000),
+        // Not a transfer of the safe
+        erc20_transfer(OTHER, ERC721_TOKEN, 1000),
+    ];
+    let token_info = TokenInfo {
+        token_type: TokenType::Erc20,
+        address: String::from(ERC20_TOKEN),
+        decimals: 18,
+        symbol: String::from("DAI"),
+        name: String::from("Dai"),
+        logo_uri: None,
+    };
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_token_info()
+        .with(eq(ERC20_TOKEN))
+        .times(1)
+        .return_once(move |_| Ok(token_info));
+    mock_info_provider
+        .expect_token_info()
+        .with(eq(ERC721_TOKEN))
+        .times(1)
+        .return_once(move |_| bail!("No token info"));
+
+    let actual = to_balance_changes(
+        &address(SAFE),
+        Uint::from_dec_str("1000000000000000000").unwrap(),
+        Uint::from_dec_str("10000000000000000").unwrap(),
+        &logs,
+        &mut mock_info_provider,
+    )
+    .await;
+
+    assert_eq!(
+        actual,
+        vec![
+            BalanceChange {
+                direction: TransferDirection::Outgoing,
+                transfer_info: TransferInfo::Ether(EtherTransfer {
+                    value: String::from("990000000000000000"),
+                }),
+            },
+            BalanceChange {
+                direction: TransferDirection::Incoming,
+                transfer_info: TransferInfo::Erc20(Erc20Transfer {
+                    token_address: String::from(ERC20_TOKEN),
+                    token_name: Some(String::from("Dai")),
+                    token_symbol: Some(String::from("DAI")),
+                    logo_uri: None,
+                    decimals: Some(18),
+                    value: String::from("5000"),
+                }),
+            },
+            BalanceChange {
+                direction: TransferDirection::Outgoing,
+                transfer_info: TransferInfo::Erc721(Erc721Transfer {
+                    token_address: String::from(ERC721_TOKEN),
+                    token_id: String::from("3"),
+                    token_name: None,
+                    token_symbol: None,
+                    logo_uri: None,
+                }),
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn to_balance_changes_without_net_change() {
+    let logs = vec![
+        erc20_transfer(OTHER, SAFE, 1000),
+        erc20_transfer(SAFE, OTHER, 1000),
+    ];
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider.expect_token_info().times(0);
+
+    let actual = to_balance_changes(
+        &address(SAFE),
+        Uint::from(1000),
+        Uint::from(1000),
+        &logs,
+        &mut mock_info_provider,
+    )
+    .await;
+
+    assert!(actual.is_empty());
+}
+
+#[tokio::test]
+async fn to_balance_changes_other_address() {
+    let logs = vec![
+        erc20_transfer(OTHER, SAFE, 6000),
+        erc721_transfer(SAFE, OTHER, 3),
+    ];
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider.expect_token_info().times(0);
+
+    let actual = to_balance_changes(
+        &address("0x8D29bE29923b68abfDD21e541b9374737B49cdAD"),
+        Uint::zero(),
+        Uint::zero(),
+        &logs,
+        &mut mock_info_provider,
+    )
+    .await;
+
+    assert!(actual.is_empty());
+}
@@ -10,9 +10,10 @@ use crate::models::commons::{DataDecoded, Operation, Parameter};
 use crate::models::converters::transactions::data_size;
 use crate::models::service::transactions::summary::{ExecutionInfo, TransactionSummary};
 use crate::models::service::transactions::{
-    Creation, Custom, Erc20Transfer, Erc721Transfer, EtherTransfer, SettingsChange, SettingsInfo,
-    TransactionInfo, TransactionStatus, Transfer, TransferDirection, TransferInfo,
-    ID_PREFIX_CREATION_TX, ID_PREFIX_ETHEREUM_TX, ID_PREFIX_MODULE_TX, ID_PREFIX_MULTISIG_TX,
+    Approval, ApprovalWarning, Creation, Custom, Erc20Transfer, Erc721Transfer, EtherTransfer,
+    SettingsChange, SettingsInfo, TransactionInfo, TransactionStatus, Transfer, TransferDirection,
+    TransferInfo, ID_PREFIX_CREATION_TX, ID_PREFIX_ETHEREUM_TX, ID_PREFIX_MODULE_TX,
+    ID_PREFIX_MULTISIG_TX,
 };
 use crate::providers::address_info::AddressInfo;
 use crate::providers::info::*;
@@ -30,18 +31,20 @@ fn data_size_calculation() {
     );
 }
 
-#[test]
-fn unknown_tx_to_summary_transaction() {
+#[tokio::test]
+async fn unknown_tx_to_summary_transaction() {
     let unknown_tx = TransactionDto::Unknown;
     let mut mock_info_provider = MockInfoProvider::new();
 
-    let error = unknown_tx.to_transaction_summary(&mut mock_info_provider, &String::from(""));
+    let error = unknown_tx
+        .to_transaction_summary(&mut mock_info_provider, &String::from(""))
+        .await;
 
     assert!(error.is_err());
 }
 
-#[test]
-fn module_tx_to_summary_transaction_success() {
+#[tokio::test]
+async fn module_tx_to_summary_transaction_success() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -68,7 +71,8 @@ fn module_tx_to_summary_transaction_success() {
         operation: Operation::CALL,
     };
 
-    let actual = ModuleTransaction::to_transaction_summary(&module_tx, &mut mock_info_provider);
+    let actual =
+        ModuleTransaction::to_transaction_summary(&module_tx, &mut mock_info_provider).await;
     let expected = vec![TransactionSummary {
         id: create_id!(
             ID_PREFIX_MODULE_TX,
@@ -93,8 +97,8 @@ fn module_tx_to_summary_transaction_success() {
     assert_eq!(actual, expected);
 }
 
-#[test]
-fn module_tx_to_summary_transaction_failed() {
+#[tokio::test]
+async fn module_tx_to_summary_transaction_failed() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -121,7 +125,8 @@ fn module_tx_to_summary_transaction_failed() {
         operation: Operation::CALL,
     };
 
-    let actual = ModuleTransaction::to_transaction_summary(&module_tx, &mut mock_info_provider);
+    let actual =
+        ModuleTransaction::to_transaction_summary(&module_tx, &mut mock_info_provider).await;
     let expected = vec![TransactionSummary {
         id: create_id!(
             ID_PREFIX_MODULE_TX,
@@ -146,8 +151,8 @@ fn module_tx_to_summary_transaction_failed() {
     assert_eq!(actual, expected);
 }
 
-#[test]
-fn ethereum_tx_to_summary_transaction_no_transfers() {
+#[tokio::test]
+async fn ethereum_tx_to_summary_transaction_no_transfers() {
     let safe_address = String::from("0x2323");
     let mut mock_info_provider = MockInfoProvider::new();
 
@@ -164,12 +169,14 @@ fn ethereum_tx_to_summary_transaction_no_transfers() {
         &ethereum_tx,
         &mut mock_info_provider,
         &safe_address,
-    );
+    )
+    .await
+    .unwrap();
     assert_eq!(actual, Vec::new());
 }
 
-#[test]
-fn ethereum_tx_to_summary_transaction_with_transfers() {
+#[tokio::test]
+async fn ethereum_tx_to_summary_transaction_with_transfers() {
     let safe_address = String::from("0x2323");
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider
@@ -210,7 +217,9 @@ fn ethereum_tx_to_summary_transaction_with_transfers() {
         &ethereum_tx,
         &mut mock_info_provider,
         &safe_address,
-    );
+    )
+    .await
+    .unwrap();
     let expected = vec![
         TransactionSummary {
             id: create_id!(
@@ -260,8 +269,8 @@ fn ethereum_tx_to_summary_transaction_with_transfers() {
     assert_eq!(actual, expected);
 }
 
-#[test]
-fn creation_transaction_to_summary_no_address_info_available() {
+#[tokio::test]
+async fn creation_transaction_to_summary_no_address_info_available() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider
         .expect_contract_info()
@@ -300,14 +309,16 @@ fn creation_transaction_to_summary_no_address_info_available() {
         safe_app_info: None,
     };
 
-    let actual = creation_tx.to_transaction_summary(&safe_address, &mut mock_info_provider);
+    let actual = creation_tx
+        .to_transaction_summary(&safe_address, &mut mock_info_provider)
+        .await;
 
     assert_eq!(expected, actual);
 }
 
 //TODO test with addresses returned
-#[test]
-fn creation_transaction_to_summary_address_info_available() {
+#[tokio::test]
+async fn creation_transaction_to_summary_address_info_available() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider
         .expect_contract_info()
@@ -360,13 +371,15 @@ fn creation_transaction_to_summary_address_info_available() {
         safe_app_info: None,
     };
 
-    let actual = creation_tx.to_transaction_summary(&safe_address, &mut mock_info_provider);
+    let actual = creation_tx
+        .to_transaction_summary(&safe_address, &mut mock_info_provider)
+        .await;
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn multisig_transaction_to_erc20_transfer_summary() {
+#[tokio::test]
+async fn multisig_transaction_to_erc20_transfer_summary() {
     let multisig_tx =
         serde_json::from_str::<MultisigTransaction>(crate::json::MULTISIG_TX_ERC20_TRANSFER)
             .unwrap();
@@ -415,13 +428,14 @@ fn multisig_transaction_to_erc20_transfer_summary() {
         safe_app_info: None,
     };
 
-    let actual = MultisigTransaction::to_transaction_summary(&multisig_tx, &mut mock_info_provider);
+    let actual =
+        MultisigTransaction::to_transaction_summary(&multisig_tx, &mut mock_info_provider).await;
 
     assert_eq!(&expected, actual.unwrap().get(0).unwrap());
 }
 
-#[test]
-fn multisig_transaction_to_erc721_transfer_summary() {
+#[tokio::test]
+async fn multisig_transaction_to_erc721_transfer_summary() {
     let multisig_tx =
         serde_json::from_str::<MultisigTransaction>(crate::json::MULTISIG_TX_ERC721_TRANSFER)
             .unwrap();
@@ -469,13 +483,14 @@ fn multisig_transaction_to_erc721_transfer_summary() {
         safe_app_info: None,
     };
 
-    let actual = MultisigTransaction::to_transaction_summary(&multisig_tx, &mut mock_info_provider);
+    let actual =
+        MultisigTransaction::to_transaction_summary(&multisig_tx, &mut mock_info_provider).await;
 
     assert_eq!(&expected, actual.unwrap().get(0).unwrap());
 }
 
-#[test]
-fn multisig_transaction_to_ether_transfer_summary() {
+#[tokio::test]
+async fn multisig_transaction_to_ether_transfer_summary() {
     let multisig_tx =
         serde_json::from_str::<MultisigTransaction>(crate::json::MULTISIG_TX_ETHER_TRANSFER)
             .unwrap();
@@ -519,13 +534,14 @@ fn multisig_transaction_to_ether_transfer_summary() {
         safe_app_info: None,
     };
 
-    let actual = MultisigTransaction::to_transaction_summary(&multisig_tx, &mut mock_info_provider);
+    let actual =
+        MultisigTransaction::to_transaction_summary(&multisig_tx, &mut mock_info_provider).await;
 
     assert_eq!(&expected, actual.unwrap().get(0).unwrap());
 }
 
-#[test]
-fn multisig_transaction_to_settings_change_summary() {
+#[tokio::test]
+async fn multisig_transaction_to_settings_change_summary() {
     let multisig_tx =
         serde_json::from_str::<MultisigTransaction>(crate::json::MULTISIG_TX_SETTINGS_CHANGE)
             .unwrap();
@@ -582,13 +598,14 @@ fn multisig_transaction_to_settings_change_summary() {
         safe_app_info: None,
     };
 
-    let actual = MultisigTransaction::to_transaction_summary(&multisig_tx, &mut mock_info_provider);
+    let actual =
+        MultisigTransaction::to_transaction_summary(&multisig_tx, &mut mock_info_provider).await;
 
     assert_eq!(&expected, actual.unwrap().get(0).unwrap());
 }
 
-#[test]
-fn multisig_transaction_to_custom_summary() {
+#[tokio::test]
+async fn multisig_transaction_to_approval_summary() {
     let multisig_tx =
         serde_json::from_str::<MultisigTransaction>(crate::json::MULTISIG_TX_CUSTOM).unwrap();
     let safe_info = serde_json::from_str::<SafeInfo>(crate::json::SAFE_WITH_MODULES).unwrap();
@@ -598,7 +615,10 @@ fn multisig_transaction_to_custom_summary() {
         .expect_safe_info()
         .times(1)
         .return_once(move |_| Ok(safe_info));
-    mock_info_provider.expect_token_info().times(0);
+    mock_info_provider
+        .expect_token_info()
+        .times(1)
+        .return_once(move |_| bail!("No token info"));
     mock_info_provider
         .expect_full_address_info_search()
         .times(1)
@@ -612,14 +632,15 @@ fn multisig_transaction_to_custom_summary() {
         ),
         timestamp: multisig_tx.execution_date.unwrap().timestamp_millis(),
         tx_status: TransactionStatus::Success,
-        tx_info: TransactionInfo::Custom(Custom {
-            to: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
-            data_size: "68".to_string(),
-            value: "0".to_string(),
-            method_name: Some("approve".to_string()),
-            action_count: None,
-            to_info: None,
-            is_cancellation: false,
+        tx_info: TransactionInfo::Approval(Approval {
+            method: "approve".to_string(),
+            token_address: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
+            token_info: None,
+            spender: "0xae9844F89D98c150F5e61bfC676D68b492155990".to_string(),
+            spender_info: None,
+            amount: Some("500000000000000".to_string()),
+            unlimited: false,
+            warning: ApprovalWarning::Medium,
         }),
         execution_info: Some(ExecutionInfo {
             nonce: 84,
@@ -630,13 +651,14 @@ fn multisig_transaction_to_custom_summary() {
         safe_app_info: None,
     };
 
-    let actual = MultisigTransaction::to_transaction_summary(&multisig_tx, &mut mock_info_provider);
+    let actual =
+        MultisigTransaction::to_transaction_summary(&multisig_tx, &mut mock_info_provider).await;
 
     assert_eq!(&expected, actual.unwrap().get(0).unwrap());
 }
 
-#[test]
-fn multisig_transaction_with_missing_signers() {
+#[tokio::test]
+async fn multisig_transaction_with_missing_signers() {
     let multisig_tx = serde_json::from_str::<MultisigTransaction>(
         crate::json::MULTISIG_TX_AWAITING_CONFIRMATIONS,
     )
@@ -688,13 +710,14 @@ fn multisig_transaction_with_missing_signers() {
         safe_app_info: None,
     };
 
-    let actual = MultisigTransaction::to_transaction_summary(&multisig_tx, &mut mock_info_provider);
+    let actual =
+        MultisigTransaction::to_transaction_summary(&multisig_tx, &mut mock_info_provider).await;
 
     assert_eq!(&expected, actual.unwrap().get(0).unwrap());
 }
 
-#[test]
-fn ethereum_transaction_with_inconsistent_token_types() {
+#[tokio::test]
+async fn ethereum_transaction_with_inconsistent_token_types() {
     let ethereum_tx = serde_json::from_str::<EthereumTransaction>(
         crate::json::ETHEREUM_TX_INCONSISTENT_TOKEN_TYPES,
     )
@@ -712,7 +735,9 @@ fn ethereum_transaction_with_inconsistent_token_types() {
         &ethereum_tx,
         &mut mock_info_provider,
         "0xBc79855178842FDBA0c353494895DEEf509E26bB",
-    );
+    )
+    .await
+    .unwrap();
     let expected = TransactionSummary {
         id: create_id!(
             ID_PREFIX_ETHEREUM_TX,
@@ -744,8 +769,8 @@ fn ethereum_transaction_with_inconsistent_token_types() {
     assert_eq!(&expected, actual.first().unwrap());
 }
 
-#[test]
-fn multisig_transaction_with_origin() {
+#[tokio::test]
+async fn multisig_transaction_with_origin() {
     let multisig_tx =
         serde_json::from_str::<MultisigTransaction>(crate::json::MULTISIG_TX_WITH_ORIGIN).unwrap();
     let mut safe_info = serde_json::from_str::<SafeInfo>(crate::json::SAFE_WITH_MODULES).unwrap();
@@ -803,7 +828,8 @@ fn multisig_transaction_with_origin() {
         }),
     };
 
-    let actual = MultisigTransaction::to_transaction_summary(&multisig_tx, &mut mock_info_provider);
+    let actual =
+        MultisigTransaction::to_transaction_summary(&multisig_tx, &mut mock_info_provider).await;
 
     assert_eq!(&expected, actual.unwrap().get(0).unwrap());
 }
@@ -0,0 +1,149 @@
+use crate::models::commons::{DataDecoded, Operation, ParamValue, Parameter, ValueDecodedType};
+use crate::models::converters::transactions::details::to_transaction_actions;
+use crate::models::service::transactions::details::TransactionAction;
+use crate::models::service::transactions::{
+    Custom, Erc20Transfer, EtherTransfer, TransactionInfo, Transfer, TransferDirection,
+    TransferInfo,
+};
+use crate::providers::address_info::AddressInfo;
+use crate::providers::info::*;
+
+const SAFE: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const MULTI_SEND: &str = "0x8D29bE29923b68abfDD21e541b9374737B49cdAD";
+const RECIPIENT: &str = "0x938bae50a210b80EA233112800Cd5Bc2e7644300";
+
+#[tokio::test]
+async fn to_transaction_actions_no_multi_send() {
+    let data_decoded = DataDecoded {
+        method: String::from("changeThreshold"),
+        parameters: Some(vec![Parameter {
+            name: String::from("_threshold"),
+            param_type: String::from("uint256"),
+            value: ParamValue::SingleValue(String::from("2")),
+            value_decoded: None,
+        }]),
+    };
+    let mut mock_info_provider = MockInfoProvider::new();
+
+    assert_eq!(
+        to_transaction_actions(SAFE, &Some(data_decoded), &mut mock_info_provider).await,
+        None
+    );
+    assert_eq!(
+        to_transaction_actions(SAFE, &None, &mut mock_info_provider).await,
+        None
+    );
+}
+
+#[tokio::test]
+async fn to_transaction_actions_erc20_transfers() {
+    let data_decoded =
+        serde_json::from_str::<DataDecoded>(crate::json::DATA_DECODED_MULTI_SEND).unwrap();
+    let token_info = TokenInfo {
+        token_type: TokenType::Erc20,
+        address: String::from("0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02"),
+        decimals: 18,
+        symbol: String::from("RIN"),
+        name: String::from("Rinkeby Token"),
+        logo_uri: None,
+    };
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_token_info()
+        .times(3)
+        .returning(move |_| Ok(token_info.clone()));
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(3)
+        .returning(move |_| {
+            Ok(AddressInfo {
+                name: String::from("Recipient"),
+                logo_uri: None,
+            })
+        });
+
+    let actual = to_transaction_actions(SAFE, &Some(data_decoded), &mut mock_info_provider)
+        .await
+        .unwrap();
+
+    assert_eq!(actual.len(), 3);
+    assert_eq!(
+        actual[0].action_info,
+        TransactionInfo::Transfer(Transfer {
+            sender: String::from(SAFE),
+            sender_info: None,
+            recipient: String::from(RECIPIENT),
+            recipient_info: Some(AddressInfo {
+                name: String::from("Recipient"),
+                logo_uri: None,
+            }),
+            direction: TransferDirection::Outgoing,
+            transfer_info: TransferInfo::Erc20(Erc20Transfer {
+                token_address: String::from("0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02"),
+                token_name: Some(String::from("Rinkeby Token")),
+                token_symbol: Some(String::from("RIN")),
+                logo_uri: None,
+                decimals: Some(18),
+                value: String::from("1000000000000000"),
+            }),
+        })
+    );
+    assert_eq!(actual[0].operation, Operation::CALL);
+    assert_eq!(actual[0].actions, None);
+}
+
+#[tokio::test]
+async fn to_transaction_actions_nested_multi_send() {
+    let data_decoded =
+        serde_json::from_str::<DataDecoded>(crate::json::DATA_DECODED_NESTED_MULTI_SEND).unwrap();
+    let nested_data_decoded = match data_decoded.get_parameter_value_decoded("transactions") {
+        Some(ValueDecodedType::InternalTransaction(transactions)) => {
+            transactions[0].data_decoded.clone()
+        }
+        _ => None,
+    };
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(2)
+        .returning(move |_| bail!("No address info"));
+
+    let expected = vec![TransactionAction {
+        to: String::from(MULTI_SEND),
+        value: Some(String::from("0")),
+        hex_data: Some(String::from("0x8d80ff0a")),
+        data_decoded: nested_data_decoded,
+        operation: Operation::DELEGATE,
+        action_info: TransactionInfo::Custom(Custom {
+            to: String::from(MULTI_SEND),
+            data_size: String::from("4"),
+            value: String::from("0"),
+            method_name: Some(String::from("multiSend")),
+            action_count: Some(1),
+            to_info: None,
+            is_cancellation: false,
+        }),
+        actions: Some(vec![TransactionAction {
+            to: String::from(RECIPIENT),
+            value: Some(String::from("1000")),
+            hex_data: None,
+            data_decoded: None,
+            operation: Operation::CALL,
+            action_info: TransactionInfo::Transfer(Transfer {
+                sender: String::from(SAFE),
+                sender_info: None,
+                recipient: String::from(RECIPIENT),
+                recipient_info: None,
+                direction: TransferDirection::Outgoing,
+                transfer_info: TransferInfo::Ether(EtherTransfer {
+                    value: String::from("1000"),
+                }),
+            }),
+            actions: None,
+        }]),
+    }];
+
+    let actual = to_transaction_actions(SAFE, &Some(data_decoded), &mut mock_info_provider).await;
+
+    assert_eq!(actual, Some(expected));
+}
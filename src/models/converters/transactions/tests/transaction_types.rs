@@ -2,14 +2,15 @@ use crate::models::backend::transactions::MultisigTransaction;
 use crate::models::commons::ParamValue::SingleValue;
 use crate::models::commons::{DataDecoded, Parameter};
 use crate::models::service::transactions::{
-    Custom, Erc20Transfer, Erc721Transfer, EtherTransfer, SettingsChange, SettingsInfo,
-    TransactionInfo, Transfer, TransferDirection, TransferInfo,
+    Approval, ApprovalWarning, Custom, Erc1155Transfer, Erc20Transfer, Erc721Transfer,
+    EtherTransfer, SettingsChange, SettingsInfo, TransactionInfo, Transfer, TransferDirection,
+    TransferInfo,
 };
 use crate::providers::address_info::AddressInfo;
 use crate::providers::info::*;
 
-#[test]
-fn transaction_operation_not_call() {
+#[tokio::test]
+async fn transaction_operation_not_call() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -32,13 +33,13 @@ fn transaction_operation_not_call() {
         is_cancellation: false,
     });
 
-    let actual = tx.transaction_info(&mut mock_info_provider);
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn transaction_data_size_and_value_greater_than_0() {
+#[tokio::test]
+async fn transaction_data_size_and_value_greater_than_0() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -61,13 +62,13 @@ fn transaction_data_size_and_value_greater_than_0() {
         is_cancellation: false,
     });
 
-    let actual = tx.transaction_info(&mut mock_info_provider);
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn transaction_data_size_and_value_greater_than_0_with_address_info() {
+#[tokio::test]
+async fn transaction_data_size_and_value_greater_than_0_with_address_info() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -98,13 +99,13 @@ fn transaction_data_size_and_value_greater_than_0_with_address_info() {
         is_cancellation: false,
     });
 
-    let actual = tx.transaction_info(&mut mock_info_provider);
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn transaction_data_size_0_value_greater_than_0() {
+#[tokio::test]
+async fn transaction_data_size_0_value_greater_than_0() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -126,13 +127,13 @@ fn transaction_data_size_0_value_greater_than_0() {
         }),
     });
 
-    let actual = tx.transaction_info(&mut mock_info_provider);
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn transaction_data_size_greater_than_value_0_to_is_safe_is_settings_method() {
+#[tokio::test]
+async fn transaction_data_size_greater_than_value_0_to_is_safe_is_settings_method() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -167,13 +168,14 @@ fn transaction_data_size_greater_than_value_0_to_is_safe_is_settings_method() {
         },
     });
 
-    let actual = tx.transaction_info(&mut mock_info_provider);
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn transaction_data_size_greater_than_value_0_to_is_safe_is_settings_method_with_address_info() {
+#[tokio::test]
+async fn transaction_data_size_greater_than_value_0_to_is_safe_is_settings_method_with_address_info(
+) {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -208,13 +210,13 @@ fn transaction_data_size_greater_than_value_0_to_is_safe_is_settings_method_with
         },
     });
 
-    let actual = tx.transaction_info(&mut mock_info_provider);
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn transaction_data_size_greater_than_value_0_to_is_safe_is_not_settings_method() {
+#[tokio::test]
+async fn transaction_data_size_greater_than_value_0_to_is_safe_is_not_settings_method() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -237,13 +239,13 @@ fn transaction_data_size_greater_than_value_0_to_is_safe_is_not_settings_method(
         is_cancellation: false,
     });
 
-    let actual = tx.transaction_info(&mut mock_info_provider);
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn transaction_data_decoded_is_erc20_receiver_ok_transfer_method() {
+#[tokio::test]
+async fn transaction_data_decoded_is_erc20_receiver_ok_transfer_method() {
     let token_info = serde_json::from_str::<TokenInfo>(crate::json::TOKEN_USDT).unwrap();
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
@@ -275,13 +277,13 @@ fn transaction_data_decoded_is_erc20_receiver_ok_transfer_method() {
             }),
     });
 
-    let actual = tx.transaction_info(&mut mock_info_provider);
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn transaction_data_decoded_is_erc721_receiver_ok_transfer_method() {
+#[tokio::test]
+async fn transaction_data_decoded_is_erc721_receiver_ok_transfer_method() {
     let token_info = serde_json::from_str::<TokenInfo>(crate::json::TOKEN_CRYPTO_KITTIES).unwrap();
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
@@ -311,13 +313,51 @@ fn transaction_data_decoded_is_erc721_receiver_ok_transfer_method() {
         }),
     });
 
-    let actual = tx.transaction_info(&mut mock_info_provider);
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn transaction_data_decoded_is_erc20_receiver_not_ok_transfer_method() {
+#[tokio::test]
+async fn transaction_data_decoded_is_erc1155_batch_transfer_method() {
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider.expect_safe_info().times(0);
+    mock_info_provider
+        .expect_token_info()
+        .times(1)
+        .return_once(move |_| bail!("No token info"));
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(1)
+        .return_once(move |_| bail!("No address info"));
+
+    let tx = serde_json::from_str::<MultisigTransaction>(
+        crate::json::MULTISIG_TX_ERC1155_BATCH_TRANSFER,
+    )
+    .unwrap();
+    let expected = TransactionInfo::Transfer(Transfer {
+        sender: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        sender_info: None,
+        recipient: "0x938bae50a210b80EA233112800Cd5Bc2e7644300".to_string(),
+        recipient_info: None,
+        direction: TransferDirection::Outgoing,
+        transfer_info: TransferInfo::Erc1155(Erc1155Transfer {
+            token_address: "0x7E9dB9C2e8C1E2BE5Da4a9a4dbE25AdD3D4EfF1A".to_string(),
+            token_ids: vec!["1".to_string(), "7".to_string()],
+            amounts: vec!["10".to_string(), "2".to_string()],
+            token_name: None,
+            token_symbol: None,
+            logo_uri: None,
+        }),
+    });
+
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
+
+    assert_eq!(expected, actual);
+}
+
+#[tokio::test]
+async fn transaction_data_decoded_is_erc20_receiver_not_ok_transfer_method() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -340,13 +380,13 @@ fn transaction_data_decoded_is_erc20_receiver_not_ok_transfer_method() {
         is_cancellation: false,
     });
 
-    let actual = tx.transaction_info(&mut mock_info_provider);
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn transaction_data_decoded_is_erc721_receiver_not_ok_transfer_method() {
+#[tokio::test]
+async fn transaction_data_decoded_is_erc721_receiver_not_ok_transfer_method() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -369,13 +409,13 @@ fn transaction_data_decoded_is_erc721_receiver_not_ok_transfer_method() {
         is_cancellation: false,
     });
 
-    let actual = tx.transaction_info(&mut mock_info_provider);
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn transaction_data_decoded_is_transfer_method_receiver_ok_token_type_unknown() {
+#[tokio::test]
+async fn transaction_data_decoded_is_transfer_method_receiver_ok_token_type_unknown() {
     let token_info = TokenInfo {
         token_type: TokenType::Unknown,
         address: "".to_string(),
@@ -407,13 +447,13 @@ fn transaction_data_decoded_is_transfer_method_receiver_ok_token_type_unknown()
         is_cancellation: false,
     });
 
-    let actual = tx.transaction_info(&mut mock_info_provider);
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn transaction_data_decoded_is_erc20_receiver_ok_token_fetch_error() {
+#[tokio::test]
+async fn transaction_data_decoded_is_erc20_receiver_ok_token_fetch_error() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider
@@ -437,13 +477,13 @@ fn transaction_data_decoded_is_erc20_receiver_ok_token_fetch_error() {
         is_cancellation: false,
     });
 
-    let actual = tx.transaction_info(&mut mock_info_provider);
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
 
-#[test]
-fn cancellation_transaction() {
+#[tokio::test]
+async fn cancellation_transaction() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
     mock_info_provider.expect_token_info().times(0);
@@ -464,7 +504,201 @@ fn cancellation_transaction() {
         is_cancellation: true,
     });
 
-    let actual = tx.transaction_info(&mut mock_info_provider);
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
+
+    assert_eq!(expected, actual);
+}
+
+fn approval_transaction(data_decoded: &str) -> MultisigTransaction {
+    let mut tx =
+        serde_json::from_str::<MultisigTransaction>(crate::json::MULTISIG_TX_CUSTOM).unwrap();
+    tx.data_decoded = Some(serde_json::from_str(data_decoded).unwrap());
+    tx
+}
+
+#[tokio::test]
+async fn transaction_data_decoded_is_unlimited_erc20_approval() {
+    let token_info = serde_json::from_str::<TokenInfo>(crate::json::TOKEN_USDT).unwrap();
+    let expected_token_info = token_info.clone();
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_token_info()
+        .times(1)
+        .return_once(move |_| Ok(token_info));
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(1)
+        .return_once(move |_| {
+            Ok(AddressInfo {
+                name: "Uniswap Router".to_string(),
+                logo_uri: None,
+            })
+        });
+
+    let tx = approval_transaction(crate::json::DATA_DECODED_APPROVE_UNLIMITED);
+    let expected = TransactionInfo::Approval(Approval {
+        method: "approve".to_string(),
+        token_address: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
+        token_info: Some(expected_token_info),
+        spender: "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D".to_string(),
+        spender_info: Some(AddressInfo {
+            name: "Uniswap Router".to_string(),
+            logo_uri: None,
+        }),
+        amount: Some(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+                .to_string(),
+        ),
+        unlimited: true,
+        warning: ApprovalWarning::High,
+    });
+
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
+
+    assert_eq!(expected, actual);
+}
+
+#[tokio::test]
+async fn transaction_data_decoded_is_revoked_erc20_approval() {
+    let token_info = serde_json::from_str::<TokenInfo>(crate::json::TOKEN_USDT).unwrap();
+    let expected_token_info = token_info.clone();
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_token_info()
+        .times(1)
+        .return_once(move |_| Ok(token_info));
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(1)
+        .return_once(move |_| bail!("No address info"));
+
+    let tx = approval_transaction(crate::json::DATA_DECODED_APPROVE_REVOKED);
+    let expected = TransactionInfo::Approval(Approval {
+        method: "approve".to_string(),
+        token_address: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
+        token_info: Some(expected_token_info),
+        spender: "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D".to_string(),
+        spender_info: None,
+        amount: Some("0".to_string()),
+        unlimited: false,
+        warning: ApprovalWarning::None,
+    });
+
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
+
+    assert_eq!(expected, actual);
+}
+
+#[tokio::test]
+async fn transaction_data_decoded_is_approval_for_all() {
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_token_info()
+        .times(1)
+        .return_once(move |_| bail!("No token info"));
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(1)
+        .return_once(move |_| bail!("No address info"));
+
+    let tx = approval_transaction(crate::json::DATA_DECODED_SET_APPROVAL_FOR_ALL);
+    let expected = TransactionInfo::Approval(Approval {
+        method: "setApprovalForAll".to_string(),
+        token_address: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
+        token_info: None,
+        spender: "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D".to_string(),
+        spender_info: None,
+        amount: None,
+        unlimited: true,
+        warning: ApprovalWarning::High,
+    });
+
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
+
+    assert_eq!(expected, actual);
+}
+
+#[tokio::test]
+async fn transaction_data_decoded_is_erc721_approve() {
+    let token_info = serde_json::from_str::<TokenInfo>(crate::json::TOKEN_CRYPTO_KITTIES).unwrap();
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_token_info()
+        .times(1)
+        .return_once(move |_| Ok(token_info));
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(1)
+        .return_once(move |_| bail!("No address info"));
+
+    let tx = approval_transaction(crate::json::DATA_DECODED_APPROVE);
+    let expected = TransactionInfo::Custom(Custom {
+        to: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
+        data_size: "68".to_string(),
+        value: "0".to_string(),
+        method_name: Some("approve".to_string()),
+        action_count: None,
+        to_info: None,
+        is_cancellation: false,
+    });
+
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
+
+    assert_eq!(expected, actual);
+}
+
+#[tokio::test]
+async fn transaction_without_value_is_custom_with_zero_value() {
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider.expect_token_info().times(0);
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(1)
+        .return_once(move |_| bail!("No address info"));
+
+    let mut tx =
+        serde_json::from_str::<MultisigTransaction>(crate::json::MULTISIG_TX_CUSTOM).unwrap();
+    tx.value = None;
+    tx.data_decoded = None;
+    let expected = TransactionInfo::Custom(Custom {
+        to: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
+        data_size: "68".to_string(),
+        value: "0".to_string(),
+        method_name: None,
+        action_count: None,
+        to_info: None,
+        is_cancellation: false,
+    });
+
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
+
+    assert_eq!(expected, actual);
+}
+
+#[tokio::test]
+async fn transaction_data_decoded_is_approve_of_unknown_token() {
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_token_info()
+        .times(1)
+        .return_once(move |_| bail!("No token info"));
+    mock_info_provider
+        .expect_full_address_info_search()
+        .times(1)
+        .return_once(move |_| bail!("No address info"));
+
+    let tx = approval_transaction(crate::json::DATA_DECODED_APPROVE);
+    let expected = TransactionInfo::Custom(Custom {
+        to: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
+        data_size: "68".to_string(),
+        value: "0".to_string(),
+        method_name: Some("approve".to_string()),
+        action_count: None,
+        to_info: None,
+        is_cancellation: false,
+    });
+
+    let actual = tx.transaction_info(&mut mock_info_provider).await;
 
     assert_eq!(expected, actual);
 }
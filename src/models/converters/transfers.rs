@@ -1,34 +1,47 @@
 use super::get_transfer_direction;
 use crate::models::backend::transfers::{
-    Erc20Transfer as Erc20TransferDto, Erc721Transfer as Erc721TransferDto,
-    EtherTransfer as EtherTransferDto, Transfer as TransferDto,
+    Erc1155Transfer as Erc1155TransferDto, Erc20Transfer as Erc20TransferDto,
+    Erc721Transfer as Erc721TransferDto, EtherTransfer as EtherTransferDto,
+    Transfer as TransferDto,
 };
 use crate::models::converters::get_address_info;
 use crate::models::service::transactions::details::TransactionDetails;
 use crate::models::service::transactions::Transfer as ServiceTransfer;
 use crate::models::service::transactions::{
-    Erc20Transfer, Erc721Transfer, EtherTransfer, TransactionInfo, TransactionStatus, TransferInfo,
+    Erc1155Transfer, Erc20Transfer, Erc721Transfer, EtherTransfer, TransactionInfo,
+    TransactionStatus, TransferInfo,
 };
 use crate::providers::info::{InfoProvider, TokenInfo, TokenType};
-use crate::utils::errors::ApiResult;
+use crate::utils::errors::{ApiError, ApiResult};
 
 impl TransferDto {
-    pub fn to_transfer(&self, info_provider: &mut dyn InfoProvider, safe: &str) -> TransactionInfo {
-        match self {
-            TransferDto::Erc721(transfer) => {
-                TransactionInfo::Transfer(transfer.to_transfer_transaction(info_provider, safe))
-            }
-            TransferDto::Erc20(transfer) => {
-                TransactionInfo::Transfer(transfer.to_transfer_transaction(info_provider, safe))
-            }
-            TransferDto::Ether(transfer) => {
-                TransactionInfo::Transfer(transfer.to_transfer_transaction(info_provider, safe))
-            }
+    pub async fn to_transfer(
+        &self,
+        info_provider: &mut dyn InfoProvider,
+        safe: &str,
+    ) -> ApiResult<TransactionInfo> {
+        Ok(match self {
+            TransferDto::Erc721(transfer) => TransactionInfo::Transfer(
+                transfer
+                    .to_transfer_transaction(info_provider, safe)
+                    .await?,
+            ),
+            TransferDto::Erc20(transfer) => TransactionInfo::Transfer(
+                transfer
+                    .to_transfer_transaction(info_provider, safe)
+                    .await?,
+            ),
+            TransferDto::Erc1155(transfer) => TransactionInfo::Transfer(
+                transfer.to_transfer_transaction(info_provider, safe).await,
+            ),
+            TransferDto::Ether(transfer) => TransactionInfo::Transfer(
+                transfer.to_transfer_transaction(info_provider, safe).await,
+            ),
             _ => TransactionInfo::Unknown,
-        }
+        })
     }
 
-    pub fn to_transaction_details(
+    pub async fn to_transaction_details(
         &self,
         info_provider: &mut dyn InfoProvider,
         safe: &str,
@@ -36,11 +49,12 @@ impl TransferDto {
         Ok(TransactionDetails {
             executed_at: self.get_execution_time(),
             tx_status: TransactionStatus::Success,
-            tx_info: self.to_transfer(info_provider, safe),
+            tx_info: self.to_transfer(info_provider, safe).await?,
             tx_data: None,
             tx_hash: self.get_transaction_hash(),
             detailed_execution_info: None,
             safe_app_info: None,
+            actions: None,
         })
     }
 
@@ -48,6 +62,7 @@ impl TransferDto {
         match self {
             TransferDto::Erc721(transfer) => Some(transfer.execution_date.timestamp_millis()),
             TransferDto::Erc20(transfer) => Some(transfer.execution_date.timestamp_millis()),
+            TransferDto::Erc1155(transfer) => Some(transfer.execution_date.timestamp_millis()),
             TransferDto::Ether(transfer) => Some(transfer.execution_date.timestamp_millis()),
             _ => None,
         }
@@ -57,6 +72,7 @@ impl TransferDto {
         match self {
             TransferDto::Erc721(transfer) => Some(transfer.transaction_hash.to_owned()),
             TransferDto::Erc20(transfer) => Some(transfer.transaction_hash.to_owned()),
+            TransferDto::Erc1155(transfer) => Some(transfer.transaction_hash.to_owned()),
             TransferDto::Ether(transfer) => Some(transfer.transaction_hash.to_owned()),
             _ => None,
         }
@@ -64,24 +80,28 @@ impl TransferDto {
 }
 
 impl Erc20TransferDto {
-    pub(super) fn to_transfer_transaction(
+    pub(super) async fn to_transfer_transaction(
         &self,
         info_provider: &mut dyn InfoProvider,
         safe: &str,
-    ) -> ServiceTransfer {
-        ServiceTransfer {
-            sender_info: get_address_info(safe, &self.from, info_provider),
+    ) -> ApiResult<ServiceTransfer> {
+        Ok(ServiceTransfer {
+            sender_info: get_address_info(safe, &self.from, info_provider).await,
             sender: self.from.to_owned(),
-            recipient_info: get_address_info(safe, &self.to, info_provider),
+            recipient_info: get_address_info(safe, &self.to, info_provider).await,
             recipient: self.to.to_owned(),
             direction: get_transfer_direction(safe, &self.from, &self.to),
-            transfer_info: self.to_transfer_info(info_provider),
-        }
+            transfer_info: self.to_transfer_info(info_provider).await?,
+        })
     }
 
-    pub(super) fn to_transfer_info(&self, info_provider: &mut dyn InfoProvider) -> TransferInfo {
+    pub(super) async fn to_transfer_info(
+        &self,
+        info_provider: &mut dyn InfoProvider,
+    ) -> ApiResult<TransferInfo> {
         let token_info =
-            token_info_with_fallback(info_provider, &self.token_address, self.token_info.clone());
+            token_info_with_fallback(info_provider, &self.token_address, self.token_info.clone())
+                .await;
         build_transfer_info(
             token_info.as_ref(),
             TokenType::Erc20,
@@ -92,24 +112,28 @@ impl Erc20TransferDto {
 }
 
 impl Erc721TransferDto {
-    pub(super) fn to_transfer_transaction(
+    pub(super) async fn to_transfer_transaction(
         &self,
         info_provider: &mut dyn InfoProvider,
         safe: &str,
-    ) -> ServiceTransfer {
-        ServiceTransfer {
-            sender_info: get_address_info(safe, &self.from, info_provider),
+    ) -> ApiResult<ServiceTransfer> {
+        Ok(ServiceTransfer {
+            sender_info: get_address_info(safe, &self.from, info_provider).await,
             sender: self.from.to_owned(),
-            recipient_info: get_address_info(safe, &self.to, info_provider),
+            recipient_info: get_address_info(safe, &self.to, info_provider).await,
             recipient: self.to.to_owned(),
             direction: get_transfer_direction(safe, &self.from, &self.to),
-            transfer_info: self.to_transfer_info(info_provider),
-        }
+            transfer_info: self.to_transfer_info(info_provider).await?,
+        })
     }
 
-    pub(super) fn to_transfer_info(&self, info_provider: &mut dyn InfoProvider) -> TransferInfo {
+    pub(super) async fn to_transfer_info(
+        &self,
+        info_provider: &mut dyn InfoProvider,
+    ) -> ApiResult<TransferInfo> {
         let token_info =
-            token_info_with_fallback(info_provider, &self.token_address, self.token_info.clone());
+            token_info_with_fallback(info_provider, &self.token_address, self.token_info.clone())
+                .await;
         build_transfer_info(
             token_info.as_ref(),
             TokenType::Erc721,
@@ -119,16 +143,50 @@ impl Erc721TransferDto {
     }
 }
 
+impl Erc1155TransferDto {
+    pub(super) async fn to_transfer_transaction(
+        &self,
+        info_provider: &mut dyn InfoProvider,
+        safe: &str,
+    ) -> ServiceTransfer {
+        ServiceTransfer {
+            sender_info: get_address_info(safe, &self.from, info_provider).await,
+            sender: self.from.to_owned(),
+            recipient_info: get_address_info(safe, &self.to, info_provider).await,
+            recipient: self.to.to_owned(),
+            direction: get_transfer_direction(safe, &self.from, &self.to),
+            transfer_info: self.to_transfer_info(info_provider).await,
+        }
+    }
+
+    pub(super) async fn to_transfer_info(
+        &self,
+        info_provider: &mut dyn InfoProvider,
+    ) -> TransferInfo {
+        let token_info =
+            token_info_with_fallback(info_provider, &self.token_address, self.token_info.clone())
+                .await;
+        TransferInfo::Erc1155(Erc1155Transfer {
+            token_address: self.token_address.to_owned(),
+            token_ids: vec![self.token_id.to_owned()],
+            amounts: vec![self.value.to_owned()],
+            token_name: token_info.as_ref().map(|it| it.name.to_owned()),
+            token_symbol: token_info.as_ref().map(|it| it.symbol.to_owned()),
+            logo_uri: token_info.and_then(|it| it.logo_uri),
+        })
+    }
+}
+
 impl EtherTransferDto {
-    pub(super) fn to_transfer_transaction(
+    pub(super) async fn to_transfer_transaction(
         &self,
         info_provider: &mut dyn InfoProvider,
         safe: &str,
     ) -> ServiceTransfer {
         ServiceTransfer {
-            sender_info: get_address_info(safe, &self.from, info_provider),
+            sender_info: get_address_info(safe, &self.from, info_provider).await,
             sender: self.from.to_owned(),
-            recipient_info: get_address_info(safe, &self.to, info_provider),
+            recipient_info: get_address_info(safe, &self.to, info_provider).await,
             recipient: self.to.to_owned(),
             direction: get_transfer_direction(safe, &self.from, &self.to),
             transfer_info: self.to_transfer_info(),
@@ -147,34 +205,40 @@ fn build_transfer_info(
     default_token_type: TokenType,
     token_address: &str,
     element: &str,
-) -> TransferInfo {
+) -> ApiResult<TransferInfo> {
     match token_info
         .map(|it| it.token_type.to_owned())
         .unwrap_or(default_token_type)
     {
-        TokenType::Erc20 => TransferInfo::Erc20(Erc20Transfer {
+        TokenType::Erc20 => Ok(TransferInfo::Erc20(Erc20Transfer {
             token_address: token_address.to_owned(),
             token_name: token_info.map(|it| it.name.to_owned()),
             token_symbol: token_info.map(|it| it.symbol.to_owned()),
             logo_uri: token_info.map(|it| it.logo_uri.to_owned()).flatten(),
             decimals: token_info.map(|it| it.decimals.to_owned()),
             value: element.to_owned(),
-        }),
-        TokenType::Erc721 => TransferInfo::Erc721(Erc721Transfer {
+        })),
+        TokenType::Erc721 => Ok(TransferInfo::Erc721(Erc721Transfer {
             token_address: token_address.to_owned(),
             token_id: element.to_owned(),
             token_name: token_info.map(|it| it.name.to_owned()),
             token_symbol: token_info.map(|it| it.symbol.to_owned()),
             logo_uri: token_info.map(|it| it.logo_uri.to_owned()).flatten(),
-        }),
-        _ => panic!("Transfer token type not supported"),
+        })),
+        token_type => Err(ApiError::new_from_message(format!(
+            "Transfer token type {:?} not supported for token {}",
+            token_type, token_address
+        ))),
     }
 }
 
-fn token_info_with_fallback(
+async fn token_info_with_fallback(
     info_provider: &mut dyn InfoProvider,
     token_address: &str,
     token_info: Option<TokenInfo>,
 ) -> Option<TokenInfo> {
-    token_info.or_else(|| info_provider.token_info(token_address).ok())
+    match token_info {
+        Some(token_info) => Some(token_info),
+        None => info_provider.token_info(token_address).await.ok(),
+    }
 }
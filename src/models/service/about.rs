@@ -3,7 +3,10 @@ use serde::Serialize;
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct About {
-    pub transaction_service_base_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_service_base_url: Option<String>,
     pub name: String,
     pub version: String,
     pub build_number: Option<String>,
@@ -0,0 +1,29 @@
+use crate::providers::address_info::AddressInfo;
+use crate::providers::info::TokenInfo;
+use serde::Serialize;
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Allowances {
+    pub results: Vec<Allowance>,
+    // Set when the history of the safe has more executed transactions than were scanned, older
+    // approvals are not listed
+    pub truncated: bool,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Allowance {
+    pub token_address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_info: Option<TokenInfo>,
+    pub spender: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spender_info: Option<AddressInfo>,
+    // Not set for `setApprovalForAll`, which grants access to all the tokens. Remaining amount when
+    // the chain has an RPC node configured, approved amount otherwise.
+    pub amount: Option<String>,
+    pub unlimited: bool,
+    pub tx_hash: Option<String>,
+    pub approved_at: Option<i64>,
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainInfo {
+    pub chain_id: String,
+    pub chain_name: String,
+    pub transaction_service: String,
+    pub native_currency: NativeCurrency,
+    pub block_explorer_uri_template: BlockExplorerUriTemplate,
+    pub gas_price: Vec<GasPrice>,
+    #[serde(default = "default_exchange_base_currency")]
+    pub exchange_base_currency: String,
+    // JSON-RPC node for direct reads, not exposed as it can contain an api key
+    #[serde(default, skip_serializing)]
+    pub rpc_uri: Option<String>,
+    // Whether Safes are upgraded to the EIP-155 deployments of the master copies, for chains that
+    // reject transactions without replay protection (the canonical ones were deployed without)
+    #[serde(default, skip_serializing)]
+    pub eip155_deployments: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeCurrency {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u64,
+    pub logo_uri: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockExplorerUriTemplate {
+    pub address: String,
+    pub tx_hash: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GasPrice {
+    #[serde(rename_all = "camelCase")]
+    Oracle {
+        uri: String,
+        gas_parameter: String,
+        gwei_factor: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Fixed { wei_value: String },
+}
+
+fn default_exchange_base_currency() -> String {
+    String::from("USD")
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeActivity {
+    pub address: String,
+    // Only activity of webhooks that include the chain is streamed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<String>,
+    #[serde(flatten)]
+    pub activity: ActivityType,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ActivityType {
+    #[serde(rename_all = "camelCase")]
+    NewProposal { safe_tx_hash: String },
+    #[serde(rename_all = "camelCase")]
+    NewConfirmation { safe_tx_hash: String, owner: String },
+    #[serde(rename_all = "camelCase")]
+    Execution {
+        safe_tx_hash: String,
+        tx_hash: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    IncomingTransfer {
+        tx_hash: String,
+        token_address: Option<String>,
+    },
+}
+
+impl ActivityType {
+    // Name of the server sent event, matches the serialized `type`
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            ActivityType::NewProposal { .. } => "NEW_PROPOSAL",
+            ActivityType::NewConfirmation { .. } => "NEW_CONFIRMATION",
+            ActivityType::Execution { .. } => "EXECUTION",
+            ActivityType::IncomingTransfer { .. } => "INCOMING_TRANSFER",
+        }
+    }
+}
@@ -1,4 +1,10 @@
 pub mod about;
+pub mod allowances;
 pub mod balances;
+pub mod chains;
+pub mod events;
+pub mod notifications;
+pub mod owners;
 pub mod safes;
 pub mod transactions;
+pub mod webhooks;
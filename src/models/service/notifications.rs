@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceRegistration {
+    pub device_token: String,
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PushNotification {
+    #[serde(rename = "type")]
+    pub notification_type: NotificationType,
+    pub chain_id: String,
+    pub address: String,
+    pub safe_tx_hash: String,
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationType {
+    ConfirmationRequest,
+}
@@ -0,0 +1,20 @@
+use serde::Serialize;
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerSafes {
+    pub safes: Vec<OwnerSafe>,
+    // Chains for which the safes of the owner could not be (completely) loaded
+    pub failed_chains: Vec<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerSafe {
+    pub chain_id: String,
+    pub address: String,
+    pub threshold: u64,
+    pub nonce: u64,
+    // Queued transactions that still need a confirmation of the owner
+    pub awaiting_confirmation: u64,
+}
@@ -1,3 +1,4 @@
+use crate::models::commons::Operation;
 use serde::Serialize;
 
 #[derive(Serialize, Debug, PartialEq)]
@@ -8,11 +9,34 @@ pub struct SafeInfoEx {
     pub threshold: u64,
     pub owners: Vec<AddressEx>,
     pub implementation: AddressEx,
+    pub implementation_version_state: ImplementationVersionState,
     pub modules: Option<Vec<AddressEx>>,
     pub fallback_handler: Option<AddressEx>,
     pub version: Option<String>,
 }
 
+// Version of the master copy compared to the latest known one, see `utils::master_copies`
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ImplementationVersionState {
+    UpToDate,
+    Outdated,
+    Unknown,
+}
+
+// Transaction upgrading the Safe, to be proposed like any other transaction
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeUpgrade {
+    pub to: String,
+    pub value: String,
+    pub data: String,
+    pub operation: Operation,
+    pub implementation: String,
+    pub fallback_handler: Option<String>,
+    pub version: String,
+}
+
 #[derive(Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct AddressEx {
@@ -14,6 +14,8 @@ pub struct TransactionDetails {
     pub tx_hash: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub safe_app_info: Option<SafeAppInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Vec<TransactionAction>>,
 }
 
 #[derive(Serialize, Debug, PartialEq)]
@@ -67,3 +69,17 @@ pub struct TransactionData {
     pub value: Option<String>,
     pub operation: Operation,
 }
+
+// Call batched by a multiSend, with the nested actions if it is a multiSend itself
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionAction {
+    pub to: String,
+    pub value: Option<String>,
+    pub hex_data: Option<String>,
+    pub data_decoded: Option<DataDecoded>,
+    pub operation: Operation,
+    pub action_info: TransactionInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Vec<TransactionAction>>,
+}
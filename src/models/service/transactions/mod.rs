@@ -1,5 +1,6 @@
 use crate::models::commons::DataDecoded;
 use crate::providers::address_info::AddressInfo;
+use crate::providers::info::TokenInfo;
 use serde::Serialize;
 
 pub mod details;
@@ -47,6 +48,7 @@ pub enum TransactionStatus {
 pub enum TransactionInfo {
     Transfer(Transfer),
     SettingsChange(SettingsChange),
+    Approval(Approval),
     Custom(Custom),
     Creation(Creation),
     Unknown,
@@ -78,6 +80,7 @@ pub enum TransferDirection {
 pub enum TransferInfo {
     Erc20(Erc20Transfer),
     Erc721(Erc721Transfer),
+    Erc1155(Erc1155Transfer),
     Ether(EtherTransfer),
 }
 
@@ -102,6 +105,18 @@ pub struct Erc721Transfer {
     pub logo_uri: Option<String>,
 }
 
+// Single transfers have one token id, batch transfers one amount for every token id
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Erc1155Transfer {
+    pub token_address: String,
+    pub token_ids: Vec<String>,
+    pub amounts: Vec<String>,
+    pub token_name: Option<String>,
+    pub token_symbol: Option<String>,
+    pub logo_uri: Option<String>,
+}
+
 #[derive(Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct EtherTransfer {
@@ -170,6 +185,32 @@ pub enum SettingsInfo {
     },
 }
 
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Approval {
+    pub method: String,
+    pub token_address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_info: Option<TokenInfo>,
+    pub spender: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spender_info: Option<AddressInfo>,
+    // Not set for `setApprovalForAll`, which approves all the tokens of the safe
+    pub amount: Option<String>,
+    pub unlimited: bool,
+    pub warning: ApprovalWarning,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ApprovalWarning {
+    // The allowance is revoked
+    None,
+    Medium,
+    // The spender can move all the tokens of the safe
+    High,
+}
+
 #[derive(Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Custom {
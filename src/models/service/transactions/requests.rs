@@ -1,4 +1,5 @@
 use crate::models::commons::Operation;
+use crate::models::service::transactions::{TransferDirection, TransferInfo};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -26,3 +27,62 @@ pub struct MultisigTransactionRequest {
     pub signature: String,
     pub origin: Option<String>,
 }
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectionRequest {
+    pub sender: String,
+    pub signature: String,
+    pub origin: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionPrepareRequest {
+    pub to: String,
+    pub value: String,
+    pub data: Option<String>,
+    // Next nonce of the safe (after the queued transactions) if not set
+    pub nonce: Option<String>,
+    pub operation: Operation,
+    pub safe_tx_gas: Option<String>,
+    pub base_gas: Option<String>,
+    pub gas_price: Option<String>,
+    pub gas_token: Option<String>,
+    pub refund_receiver: Option<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PreparedTransaction {
+    pub safe_tx_hash: String,
+    pub nonce: String,
+    pub typed_data: serde_json::Value,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSimulation {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    // Gas used by the call of the safe, without the overhead of `execTransaction`
+    pub gas_used: Option<String>,
+    pub balance_changes: Vec<BalanceChange>,
+}
+
+// Net change of a balance of the safe during the simulated execution, the value of the transfer
+// info is the amount the balance changed by
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    pub direction: TransferDirection,
+    pub transfer_info: TransferInfo,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionEstimation {
+    pub safe_tx_gas: String,
+    pub base_gas: String,
+}
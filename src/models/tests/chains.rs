@@ -0,0 +1,70 @@
+use crate::models::service::chains::{
+    BlockExplorerUriTemplate, ChainInfo, GasPrice, NativeCurrency,
+};
+
+#[test]
+fn deserialise_chain_info() {
+    let json = r#"
+    {
+        "chainId": "4",
+        "chainName": "Rinkeby",
+        "transactionService": "https://safe-transaction.rinkeby.gnosis.io",
+        "nativeCurrency": {
+            "name": "Ether",
+            "symbol": "ETH",
+            "decimals": 18,
+            "logoUri": null
+        },
+        "blockExplorerUriTemplate": {
+            "address": "https://rinkeby.etherscan.io/address/{{address}}",
+            "txHash": "https://rinkeby.etherscan.io/tx/{{txHash}}"
+        },
+        "gasPrice": [
+            {
+                "type": "ORACLE",
+                "uri": "https://ethgasstation.info/json/ethgasAPI.json",
+                "gasParameter": "average",
+                "gweiFactor": "100000000"
+            },
+            {
+                "type": "FIXED",
+                "weiValue": "1000000000"
+            }
+        ]
+    }
+    "#;
+
+    let actual = serde_json::from_str::<ChainInfo>(json);
+
+    let expected = ChainInfo {
+        chain_id: "4".to_string(),
+        chain_name: "Rinkeby".to_string(),
+        transaction_service: "https://safe-transaction.rinkeby.gnosis.io".to_string(),
+        native_currency: NativeCurrency {
+            name: "Ether".to_string(),
+            symbol: "ETH".to_string(),
+            decimals: 18,
+            logo_uri: None,
+        },
+        block_explorer_uri_template: BlockExplorerUriTemplate {
+            address: "https://rinkeby.etherscan.io/address/{{address}}".to_string(),
+            tx_hash: "https://rinkeby.etherscan.io/tx/{{txHash}}".to_string(),
+        },
+        gas_price: vec![
+            GasPrice::Oracle {
+                uri: "https://ethgasstation.info/json/ethgasAPI.json".to_string(),
+                gas_parameter: "average".to_string(),
+                gwei_factor: "100000000".to_string(),
+            },
+            GasPrice::Fixed {
+                wei_value: "1000000000".to_string(),
+            },
+        ],
+        exchange_base_currency: "USD".to_string(),
+        rpc_uri: None,
+        eip155_deployments: false,
+    };
+
+    assert!(actual.is_ok());
+    assert_eq!(expected, actual.unwrap());
+}
@@ -1 +1,2 @@
+mod chains;
 mod commons;
@@ -1,7 +1,7 @@
 use crate::utils::json::default_if_null;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, PartialEq, Debug)]
+#[derive(Serialize, Clone, PartialEq, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AddressInfo {
     pub name: String,
@@ -17,5 +17,10 @@ pub struct ContractInfo {
     #[serde(deserialize_with = "default_if_null")]
     pub display_name: String,
     pub logo_uri: Option<String>,
-    // pub contract_abi: Option<ContractAbi>, //Ignored for now
+    pub contract_abi: Option<ContractAbi>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ContractAbi {
+    pub abi: serde_json::Value,
 }
@@ -0,0 +1,177 @@
+use crate::providers::rpc::{BlockHeader, RpcClient};
+use crate::utils::errors::{ApiError, ApiResult};
+use ethabi::{Address, Hash, Uint};
+use ethcontract_common::hash::keccak256;
+use evm::backend::{Backend, Basic};
+use futures::executor::block_on;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+
+// Number of previous blocks the `BLOCKHASH` opcode can access
+const BLOCK_HASH_RANGE: u64 = 256;
+
+// State of the chain for the embedded EVM, read from the JSON-RPC node at the block the backend was
+// created with and kept for the lifetime of the backend. `Backend` is synchronous, the EVM waits
+// for each read of the node. It can't fail either, so the first error of the node is kept and the
+// default value is used instead, see `into_result`.
+pub struct RpcBackend<'r, 'c> {
+    rpc_client: &'r RpcClient<'c>,
+    chain_id: Uint,
+    origin: Address,
+    block: BlockHeader,
+    code_overrides: HashMap<Address, Vec<u8>>,
+    basics: RefCell<HashMap<Address, Basic>>,
+    codes: RefCell<HashMap<Address, Vec<u8>>>,
+    storage: RefCell<HashMap<(Address, Hash), Hash>>,
+    error: RefCell<Option<ApiError>>,
+}
+
+impl<'r, 'c> RpcBackend<'r, 'c> {
+    pub async fn new(
+        rpc_client: &'r RpcClient<'c>,
+        chain_id: &str,
+        origin: Address,
+    ) -> ApiResult<Self> {
+        Ok(RpcBackend {
+            rpc_client,
+            chain_id: Uint::from_dec_str(chain_id)
+                .map_err(|_| client_error!(422, "Chain id has to be a number"))?,
+            origin,
+            block: rpc_client.block_header("latest").await?,
+            code_overrides: HashMap::new(),
+            basics: RefCell::new(HashMap::new()),
+            codes: RefCell::new(HashMap::new()),
+            storage: RefCell::new(HashMap::new()),
+            error: RefCell::new(None),
+        })
+    }
+
+    // Runs `code` instead of the deployed code of `address`, its balance and storage are kept
+    pub fn with_code(mut self, address: Address, code: Vec<u8>) -> Self {
+        self.code_overrides.insert(address, code);
+        self
+    }
+
+    // Fails with the first error of the node, results based on the state are not reliable then
+    pub fn into_result(self) -> ApiResult<()> {
+        match self.error.into_inner() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    fn block_tag(&self) -> String {
+        format!("0x{:x}", self.block.number)
+    }
+
+    fn or_default<T: Default>(&self, request: impl Future<Output = ApiResult<T>>) -> T {
+        block_on(request).unwrap_or_else(|error| {
+            let mut first_error = self.error.borrow_mut();
+            if first_error.is_none() {
+                *first_error = Some(error);
+            }
+            T::default()
+        })
+    }
+}
+
+impl<'r, 'c> Backend for RpcBackend<'r, 'c> {
+    // Fees are not paid in the simulation
+    fn gas_price(&self) -> Uint {
+        Uint::zero()
+    }
+
+    fn origin(&self) -> Address {
+        self.origin
+    }
+
+    fn block_hash(&self, number: Uint) -> Hash {
+        if number >= self.block.number || self.block.number - number > Uint::from(BLOCK_HASH_RANGE)
+        {
+            return Hash::zero();
+        }
+        let block = format!("0x{:x}", number);
+        self.or_default(async { Ok(self.rpc_client.block_header(&block).await?.hash) })
+    }
+
+    // The execution is simulated on top of the block, as part of the next one
+    fn block_number(&self) -> Uint {
+        self.block.number.saturating_add(Uint::one())
+    }
+
+    fn block_coinbase(&self) -> Address {
+        self.block.coinbase
+    }
+
+    fn block_timestamp(&self) -> Uint {
+        self.block.timestamp
+    }
+
+    fn block_difficulty(&self) -> Uint {
+        self.block.difficulty
+    }
+
+    fn block_gas_limit(&self) -> Uint {
+        self.block.gas_limit
+    }
+
+    fn chain_id(&self) -> Uint {
+        self.chain_id
+    }
+
+    // Empty accounts are treated as not existing (EIP-161)
+    fn exists(&self, address: Address) -> bool {
+        let basic = self.basic(address);
+        !basic.balance.is_zero() || !basic.nonce.is_zero() || self.code_size(address) > 0
+    }
+
+    fn basic(&self, address: Address) -> Basic {
+        if let Some(basic) = self.basics.borrow().get(&address) {
+            return basic.clone();
+        }
+        let block_tag = self.block_tag();
+        let basic = Basic {
+            balance: self.or_default(self.rpc_client.get_balance(&address, &block_tag)),
+            nonce: self.or_default(self.rpc_client.get_transaction_count(&address, &block_tag)),
+        };
+        self.basics.borrow_mut().insert(address, basic.clone());
+        basic
+    }
+
+    fn code_hash(&self, address: Address) -> Hash {
+        if !self.exists(address) {
+            return Hash::zero();
+        }
+        Hash::from(keccak256(&self.code(address)))
+    }
+
+    fn code_size(&self, address: Address) -> usize {
+        self.code(address).len()
+    }
+
+    fn code(&self, address: Address) -> Vec<u8> {
+        if let Some(code) = self.code_overrides.get(&address) {
+            return code.to_owned();
+        }
+        if let Some(code) = self.codes.borrow().get(&address) {
+            return code.to_owned();
+        }
+        let code = self.or_default(self.rpc_client.get_code(&address, &self.block_tag()));
+        self.codes.borrow_mut().insert(address, code.to_owned());
+        code
+    }
+
+    fn storage(&self, address: Address, index: Hash) -> Hash {
+        if let Some(value) = self.storage.borrow().get(&(address, index)) {
+            return *value;
+        }
+        let value = self.or_default(self.rpc_client.get_storage_at(
+            &address,
+            &index,
+            &self.block_tag(),
+        ));
+        self.storage.borrow_mut().insert((address, index), value);
+        value
+    }
+}
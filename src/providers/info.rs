@@ -1,19 +1,26 @@
 use crate::cache::cache_operations::RequestCached;
 use crate::cache::Cache;
+use crate::config::chains::chain_config;
 use crate::config::{
     address_info_cache_duration, base_exchange_api_url, base_transaction_service_url,
     exchange_api_cache_duration, long_error_duration, safe_app_info_request_timeout,
-    safe_app_manifest_cache_duration, safe_info_cache_duration, safe_info_request_timeout,
-    short_error_duration, token_info_cache_duration, token_info_request_timeout,
+    safe_app_manifest_cache_duration, safe_info_cache_duration, safe_info_from_rpc,
+    safe_info_request_timeout, safe_info_rpc_check, short_error_duration,
+    token_info_cache_duration, token_info_request_timeout,
 };
 use crate::models::commons::Page;
+use crate::models::service::chains::ChainInfo;
 use crate::providers::address_info::{AddressInfo, ContractInfo};
+use crate::providers::rpc::RpcClient;
 use crate::utils::context::Context;
+use crate::utils::data_decoder::parse_abi;
 use crate::utils::errors::ApiResult;
 use crate::utils::json::default_if_null;
 use crate::utils::urls::build_manifest_url;
+use async_trait::async_trait;
+use ethabi::Contract;
+use itertools::Itertools;
 use mockall::automock;
-use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::collections::HashMap;
@@ -26,6 +33,7 @@ pub const TOKENS_KEY: &'static str = "dip_ti";
 pub enum TokenType {
     Erc721,
     Erc20,
+    Erc1155,
     Ether,
     #[serde(other)]
     Unknown,
@@ -79,52 +87,59 @@ pub struct TokenInfo {
     pub logo_uri: Option<String>,
 }
 
+// Futures are not `Send`, they run on the thread of the request (see `Context::block_on`)
 #[automock]
+#[async_trait(?Send)]
 pub trait InfoProvider {
-    fn safe_info(&mut self, safe: &str) -> ApiResult<SafeInfo>;
-    fn token_info(&mut self, token: &str) -> ApiResult<TokenInfo>;
-    fn safe_app_info(&mut self, url: &str) -> ApiResult<SafeAppInfo>;
-    fn contract_info(&mut self, address: &str) -> ApiResult<AddressInfo>;
-
-    fn full_address_info_search(&mut self, address: &str) -> ApiResult<AddressInfo> {
-        self.token_info(&address)
-            .map(|it| AddressInfo {
-                name: it.name,
-                logo_uri: it.logo_uri.to_owned(),
-            })
-            .or_else(|_| self.contract_info(&address))
+    async fn safe_info(&mut self, safe: &str) -> ApiResult<SafeInfo>;
+    async fn token_info(&mut self, token: &str) -> ApiResult<TokenInfo>;
+    async fn safe_app_info(&mut self, url: &str) -> ApiResult<SafeAppInfo>;
+    async fn contract_info(&mut self, address: &str) -> ApiResult<AddressInfo>;
+    async fn contract_abi(&mut self, address: &str) -> ApiResult<Option<Contract>>;
+
+    async fn full_address_info_search(&mut self, address: &str) -> ApiResult<AddressInfo> {
+        match self.token_info(&address).await {
+            Ok(token_info) => Ok(AddressInfo {
+                name: token_info.name,
+                logo_uri: token_info.logo_uri.to_owned(),
+            }),
+            Err(_) => self.contract_info(&address).await,
+        }
     }
 }
 
 pub struct DefaultInfoProvider<'p> {
-    client: &'p reqwest::blocking::Client,
+    chain_id: String,
+    client: &'p reqwest::Client,
     cache: &'p dyn Cache,
     safe_cache: HashMap<String, Option<SafeInfo>>,
     token_cache: HashMap<String, Option<TokenInfo>>,
+    contract_cache: HashMap<String, Option<AddressInfo>>,
+    abi_cache: HashMap<String, Option<Contract>>,
 }
 
+#[async_trait(?Send)]
 impl InfoProvider for DefaultInfoProvider<'_> {
-    fn safe_info(&mut self, safe: &str) -> ApiResult<SafeInfo> {
-        self.cached(
-            |this| &mut this.safe_cache,
-            DefaultInfoProvider::load_safe_info,
-            safe,
-        )
+    async fn safe_info(&mut self, safe: &str) -> ApiResult<SafeInfo> {
+        if let Some(cached) = self.safe_cache.get(safe) {
+            return from_local_cache(cached);
+        }
+        let safe_info = self.load_safe_info(safe).await?;
+        to_local_cache(&mut self.safe_cache, safe, safe_info)
     }
 
-    fn token_info(&mut self, token: &str) -> ApiResult<TokenInfo> {
-        if token != "0x0000000000000000000000000000000000000000" {
-            self.cached(
-                |this| &mut this.token_cache,
-                DefaultInfoProvider::load_token_info,
-                token,
-            )
-        } else {
+    async fn token_info(&mut self, token: &str) -> ApiResult<TokenInfo> {
+        if token == "0x0000000000000000000000000000000000000000" {
             bail!("Token Address is 0x0")
         }
+        if let Some(cached) = self.token_cache.get(token) {
+            return from_local_cache(cached);
+        }
+        let token_info = self.load_token_info(token).await?;
+        to_local_cache(&mut self.token_cache, token, token_info)
     }
 
-    fn safe_app_info(&mut self, url: &str) -> ApiResult<SafeAppInfo> {
+    async fn safe_app_info(&mut self, url: &str) -> ApiResult<SafeAppInfo> {
         let manifest_url = build_manifest_url(url)?;
 
         let manifest_json = RequestCached::new(manifest_url)
@@ -132,7 +147,8 @@ impl InfoProvider for DefaultInfoProvider<'_> {
             .error_cache_duration(long_error_duration())
             .cache_all_errors()
             .request_timeout(safe_app_info_request_timeout())
-            .execute(self.client, self.cache)?;
+            .execute(self.client, self.cache)
+            .await?;
         let manifest = serde_json::from_str::<Manifest>(&manifest_json)?;
         Ok(SafeAppInfo {
             name: manifest.name.to_owned(),
@@ -141,143 +157,320 @@ impl InfoProvider for DefaultInfoProvider<'_> {
         })
     }
 
-    fn contract_info(&mut self, address: &str) -> ApiResult<AddressInfo> {
-        let url = format!(
-            "{}/v1/contracts/{}/",
-            base_transaction_service_url(),
-            address
-        );
-        let contract_info_json = RequestCached::new(url)
-            .cache_duration(address_info_cache_duration())
-            .error_cache_duration(long_error_duration())
-            .execute(self.client, self.cache)?;
-        let contract_info = serde_json::from_str::<ContractInfo>(&contract_info_json)?;
-        if contract_info.display_name.trim().is_empty() {
-            bail!("No display name")
-        } else {
-            Ok(AddressInfo {
-                name: contract_info.display_name.to_owned(),
-                logo_uri: contract_info.logo_uri.to_owned(),
-            })
+    async fn contract_info(&mut self, address: &str) -> ApiResult<AddressInfo> {
+        if let Some(cached) = self.contract_cache.get(address) {
+            return from_local_cache(cached);
+        }
+        let contract_info = self.load_contract_info(address).await?;
+        to_local_cache(&mut self.contract_cache, address, contract_info)
+    }
+
+    async fn contract_abi(&mut self, address: &str) -> ApiResult<Option<Contract>> {
+        if let Some(contract_abi) = self.abi_cache.get(address) {
+            return Ok(contract_abi.clone());
         }
+        let contract_info_json = self
+            .contract_info_request(address)?
+            .execute(self.client, self.cache)
+            .await?;
+        let contract_abi = to_contract_abi(&contract_info_json)?;
+        self.abi_cache
+            .insert(address.to_string(), contract_abi.clone());
+        Ok(contract_abi)
     }
 }
 
 impl DefaultInfoProvider<'_> {
-    pub fn new<'p>(context: &'p Context) -> DefaultInfoProvider<'p> {
+    pub fn new<'p>(chain_id: &str, context: &'p Context) -> DefaultInfoProvider<'p> {
         DefaultInfoProvider {
+            chain_id: chain_id.to_string(),
             client: context.client(),
             cache: context.cache(),
             safe_cache: HashMap::new(),
             token_cache: HashMap::new(),
+            contract_cache: HashMap::new(),
+            abi_cache: HashMap::new(),
         }
     }
 
-    fn cached<T>(
-        &mut self,
-        local_cache: impl Fn(&mut Self) -> &mut HashMap<String, Option<T>>,
-        generator: impl Fn(&mut Self, &String) -> ApiResult<Option<T>>,
-        key: impl Into<String>,
-    ) -> ApiResult<T>
-    where
-        T: Clone + DeserializeOwned,
-    {
-        let key = key.into();
-        match local_cache(self).get(&key) {
-            Some(value) => value
-                .clone()
-                .ok_or(api_error!("Cached value not available")),
-            None => {
-                let value: Option<T> = generator(self, &key)?;
-                local_cache(self).insert(key, value.clone());
-                value.ok_or(api_error!("Could not generate value"))
+    // Resolves the contract info of all the addresses in one go, so that the converters
+    // can be served from the local cache instead of requesting them one by one
+    pub async fn prefetch_address_infos(&mut self, addresses: &[String]) -> ApiResult<()> {
+        let mut unresolved = vec![];
+        for address in addresses.iter().unique() {
+            if !self.contract_cache.contains_key(address) && self.token_info(address).await.is_err()
+            {
+                unresolved.push(address.to_owned());
             }
         }
+        let requests = unresolved
+            .iter()
+            .map(|address| self.contract_info_request(address))
+            .collect::<ApiResult<Vec<RequestCached>>>()?;
+        let responses = RequestCached::execute_all(&requests, self.client, self.cache).await;
+        for (address, response) in unresolved.into_iter().zip(responses) {
+            let address_info = response
+                .and_then(|json| to_address_info(&json))
+                .unwrap_or(None);
+            self.contract_cache.insert(address, address_info);
+        }
+        Ok(())
     }
 
-    fn load_safe_info(&mut self, safe: &String) -> ApiResult<Option<SafeInfo>> {
-        let url = format!("{}/v1/safes/{}/", base_transaction_service_url(), safe);
-        let data = RequestCached::new(url)
-            .cache_duration(safe_info_cache_duration())
-            .error_cache_duration(short_error_duration())
-            .request_timeout(safe_info_request_timeout())
-            .execute(self.client, self.cache)?;
-        Ok(serde_json::from_str(&data).unwrap_or(None))
+    // Same as `prefetch_address_infos` for the ABIs used to decode the data of the transactions.
+    // The contract info comes with the ABI, so it is kept as well.
+    pub async fn prefetch_contract_abis(&mut self, addresses: &[String]) -> ApiResult<()> {
+        let unresolved = addresses
+            .iter()
+            .unique()
+            .filter(|address| !self.abi_cache.contains_key(*address))
+            .cloned()
+            .collect::<Vec<String>>();
+        let requests = unresolved
+            .iter()
+            .map(|address| self.contract_info_request(address))
+            .collect::<ApiResult<Vec<RequestCached>>>()?;
+        let responses = RequestCached::execute_all(&requests, self.client, self.cache).await;
+        for (address, response) in unresolved.into_iter().zip(responses) {
+            let (address_info, contract_abi) = match response {
+                Ok(json) => (
+                    to_address_info(&json).unwrap_or(None),
+                    to_contract_abi(&json).unwrap_or(None),
+                ),
+                Err(_) => (None, None),
+            };
+            self.contract_cache
+                .entry(address.to_owned())
+                .or_insert(address_info);
+            self.abi_cache.insert(address, contract_abi);
+        }
+        Ok(())
+    }
+
+    pub fn chain_config(&self) -> ApiResult<&'static ChainInfo> {
+        chain_config(&self.chain_id)
+    }
+
+    async fn load_safe_info(&self, safe: &str) -> ApiResult<Option<SafeInfo>> {
+        let data = safe_info_request(&self.chain_id, safe)?
+            .execute(self.client, self.cache)
+            .await?;
+        let mut safe_info: Option<SafeInfo> = serde_json::from_str(&data).unwrap_or(None);
+        if let Some(safe_info) = safe_info.as_mut() {
+            self.check_safe_info_with_rpc(safe_info).await;
+        }
+        Ok(safe_info)
+    }
+
+    // The transaction service can lag behind the chain, the node is the source of truth when set.
+    // Failures of the node are only logged, the safe info of the transaction service is served then.
+    async fn check_safe_info_with_rpc(&self, safe_info: &mut SafeInfo) {
+        let from_rpc = safe_info_from_rpc();
+        if !from_rpc && !safe_info_rpc_check() {
+            return;
+        }
+        if self
+            .chain_config()
+            .map_or(true, |chain| chain.rpc_uri.is_none())
+        {
+            return;
+        }
+        let safe_state = match RpcClient::new(&self.chain_id, self.client) {
+            Ok(rpc_client) => rpc_client.safe_state(self.cache, &safe_info.address).await,
+            Err(error) => Err(error),
+        };
+        match safe_state {
+            Ok(safe_state) => {
+                let mismatches = safe_state.mismatches(safe_info);
+                if !mismatches.is_empty() {
+                    log::warn!(
+                        "Safe info of {} on chain {} differs from the RPC node: {}",
+                        safe_info.address,
+                        self.chain_id,
+                        mismatches.join(", ")
+                    );
+                }
+                if from_rpc {
+                    safe_state.apply_to(safe_info);
+                }
+            }
+            Err(error) => log::error!(
+                "Could not read {} on chain {} from the RPC node: {:?}",
+                safe_info.address,
+                self.chain_id,
+                error
+            ),
+        }
+    }
+
+    fn contract_info_request(&self, address: &str) -> ApiResult<RequestCached> {
+        let url = format!(
+            "{}/v1/contracts/{}/",
+            base_transaction_service_url(&self.chain_id)?,
+            address
+        );
+        let mut request = RequestCached::new(url);
+        request
+            .chain_id(&self.chain_id)
+            .cache_duration(address_info_cache_duration())
+            .error_cache_duration(long_error_duration());
+        Ok(request)
     }
 
-    fn populate_token_cache(&mut self) -> ApiResult<()> {
-        let url = format!("{}/v1/tokens/?limit=10000", base_transaction_service_url());
+    async fn load_contract_info(&self, address: &str) -> ApiResult<Option<AddressInfo>> {
+        let contract_info_json = self
+            .contract_info_request(address)?
+            .execute(self.client, self.cache)
+            .await?;
+        to_address_info(&contract_info_json)
+    }
+
+    async fn populate_token_cache(&self) -> ApiResult<()> {
+        let url = format!(
+            "{}/v1/tokens/?limit=10000",
+            base_transaction_service_url(&self.chain_id)?
+        );
         let response = self
             .client
             .get(&url)
             .timeout(Duration::from_millis(token_info_request_timeout()))
-            .send()?;
-        let data: Page<TokenInfo> = response.json()?;
+            .send()
+            .await?;
+        let data: Page<TokenInfo> = response.json().await?;
+        let tokens_key = self.tokens_key();
         for token in data.results.iter() {
             self.cache
-                .insert_in_hash(TOKENS_KEY, &token.address, &serde_json::to_string(&token)?);
+                .insert_in_hash(&tokens_key, &token.address, &serde_json::to_string(&token)?)
+                .await;
         }
         Ok(())
     }
 
-    fn check_token_cache(&mut self) -> ApiResult<()> {
-        if self.cache.has_key(TOKENS_KEY) {
+    fn tokens_key(&self) -> String {
+        format!("{}_{}", TOKENS_KEY, self.chain_id)
+    }
+
+    async fn check_token_cache(&self) -> ApiResult<()> {
+        let tokens_key = self.tokens_key();
+        if self.cache.has_key(&tokens_key).await {
             return Ok(());
         }
-        self.cache.insert_in_hash(TOKENS_KEY, "state", "populating");
-        let result = self.populate_token_cache();
+        self.cache
+            .insert_in_hash(&tokens_key, "state", "populating")
+            .await;
+        let result = self.populate_token_cache().await;
         if result.is_ok() {
             self.cache
-                .expire_entity(TOKENS_KEY, token_info_cache_duration());
-            self.cache.insert_in_hash(TOKENS_KEY, "state", "populated");
+                .expire_entity(&tokens_key, token_info_cache_duration())
+                .await;
+            self.cache
+                .insert_in_hash(&tokens_key, "state", "populated")
+                .await;
         } else {
-            self.cache.expire_entity(TOKENS_KEY, short_error_duration());
-            self.cache.insert_in_hash(TOKENS_KEY, "state", "errored");
+            self.cache
+                .expire_entity(&tokens_key, short_error_duration())
+                .await;
+            self.cache
+                .insert_in_hash(&tokens_key, "state", "errored")
+                .await;
         }
         result
     }
 
-    fn load_token_info(&mut self, token: &String) -> ApiResult<Option<TokenInfo>> {
-        self.check_token_cache()?;
-        match self.cache.get_from_hash(TOKENS_KEY, token) {
+    async fn load_token_info(&self, token: &str) -> ApiResult<Option<TokenInfo>> {
+        self.check_token_cache().await?;
+        match self.cache.get_from_hash(&self.tokens_key(), token).await {
             Some(cached) => Ok(Some(serde_json::from_str::<TokenInfo>(&cached)?)),
             None => Ok(None),
         }
     }
 
-    pub fn exchange_usd_to(&self, currency_code: &str) -> ApiResult<f64> {
-        if &currency_code.to_lowercase() == "usd" {
+    // Rate to convert from the fiat currency the chain reports its balances in to `currency_code`
+    pub async fn exchange_rate(&self, currency_code: &str) -> ApiResult<f64> {
+        let base_currency = self.chain_config()?.exchange_base_currency.to_uppercase();
+        let currency_code = currency_code.to_uppercase();
+        if currency_code == base_currency {
             return Ok(1.0);
         }
 
-        let currency_code = currency_code.to_uppercase();
-        let exchange = self.fetch_exchange()?;
+        let exchange = self.fetch_exchange().await?;
         match exchange.rates {
             Some(rates) => {
-                let base_to_usd = rates.get("USD").unwrap_or(&0.0);
+                let rate_to_base = rates.get(&base_currency).unwrap_or(&0.0);
                 rates
                     .get(&currency_code)
                     .cloned()
-                    .map(|base_to_requested_code| base_to_requested_code / base_to_usd)
+                    .map(|rate_to_requested_code| rate_to_requested_code / rate_to_base)
                     .ok_or(client_error!(422, "Currency not found"))
             }
             None => Err(client_error!(422, "Currency not found")),
         }
     }
 
-    pub fn available_currency_codes(&self) -> ApiResult<Vec<String>> {
-        let exchange = self.fetch_exchange()?;
+    pub async fn available_currency_codes(&self) -> ApiResult<Vec<String>> {
+        let exchange = self.fetch_exchange().await?;
         Ok(exchange
             .rates
             .map_or(vec![], |s| s.keys().cloned().collect::<Vec<_>>()))
     }
 
-    fn fetch_exchange(&self) -> ApiResult<Exchange> {
-        let url = base_exchange_api_url();
+    async fn fetch_exchange(&self) -> ApiResult<Exchange> {
+        let url = base_exchange_api_url()?;
         let body = RequestCached::new(url)
             .cache_duration(exchange_api_cache_duration())
             .error_cache_duration(short_error_duration())
-            .execute(self.client, self.cache)?;
+            .execute(self.client, self.cache)
+            .await?;
         Ok(serde_json::from_str::<Exchange>(&body)?)
     }
 }
+
+// Values that could not be loaded are kept as `None`, so that they are not requested again
+fn from_local_cache<T: Clone>(cached: &Option<T>) -> ApiResult<T> {
+    cached
+        .clone()
+        .ok_or(api_error!("Cached value not available"))
+}
+
+fn to_local_cache<T: Clone>(
+    local_cache: &mut HashMap<String, Option<T>>,
+    key: &str,
+    value: Option<T>,
+) -> ApiResult<T> {
+    local_cache.insert(key.to_string(), value.clone());
+    value.ok_or(api_error!("Could not generate value"))
+}
+
+fn to_address_info(contract_info_json: &str) -> ApiResult<Option<AddressInfo>> {
+    let contract_info = serde_json::from_str::<ContractInfo>(contract_info_json)?;
+    if contract_info.display_name.trim().is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(AddressInfo {
+            name: contract_info.display_name.to_owned(),
+            logo_uri: contract_info.logo_uri.to_owned(),
+        }))
+    }
+}
+
+fn to_contract_abi(contract_info_json: &str) -> ApiResult<Option<Contract>> {
+    let contract_info = serde_json::from_str::<ContractInfo>(contract_info_json)?;
+    Ok(contract_info
+        .contract_abi
+        .and_then(|contract_abi| parse_abi(&contract_abi.abi)))
+}
+
+pub fn safe_info_request(chain_id: &str, safe: &str) -> ApiResult<RequestCached> {
+    let url = format!(
+        "{}/v1/safes/{}/",
+        base_transaction_service_url(chain_id)?,
+        safe
+    );
+    let mut request = RequestCached::new(url);
+    request
+        .chain_id(chain_id)
+        .cache_duration(safe_info_cache_duration())
+        .error_cache_duration(short_error_duration())
+        .request_timeout(safe_info_request_timeout());
+    Ok(request)
+}
@@ -1,2 +1,8 @@
 pub mod address_info;
+pub mod evm;
 pub mod info;
+pub mod push;
+pub mod rpc;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,85 @@
+use crate::config::{push_notification_request_timeout, push_notifications_fcm_key};
+use crate::models::service::notifications::PushNotification;
+use crate::utils::errors::{ApiError, ApiResult};
+use async_trait::async_trait;
+use mockall::automock;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use serde_json::json;
+use std::time::Duration;
+
+const FCM_SEND_URL: &'static str = "https://fcm.googleapis.com/fcm/send";
+// Maximum amount of `registration_ids` per request of the FCM legacy HTTP API
+const FCM_MAX_DEVICES: usize = 1000;
+
+#[automock]
+#[async_trait(?Send)]
+pub trait PushProvider: Send + Sync {
+    async fn send(
+        &self,
+        client: &reqwest::Client,
+        device_tokens: &[String],
+        notification: &PushNotification,
+    ) -> ApiResult<()>;
+}
+
+pub fn push_provider() -> Box<dyn PushProvider> {
+    match push_notifications_fcm_key() {
+        Some(server_key) => Box::new(FcmPushProvider { server_key }),
+        None => Box::new(LogPushProvider),
+    }
+}
+
+pub struct FcmPushProvider {
+    server_key: String,
+}
+
+#[async_trait(?Send)]
+impl PushProvider for FcmPushProvider {
+    async fn send(
+        &self,
+        client: &reqwest::Client,
+        device_tokens: &[String],
+        notification: &PushNotification,
+    ) -> ApiResult<()> {
+        for chunk in device_tokens.chunks(FCM_MAX_DEVICES) {
+            let response = client
+                .post(FCM_SEND_URL)
+                .timeout(Duration::from_millis(push_notification_request_timeout()))
+                .header(AUTHORIZATION, format!("key={}", self.server_key))
+                .header(CONTENT_TYPE, "application/json")
+                .body(serde_json::to_string(&json!({
+                    "registration_ids": chunk,
+                    "data": notification,
+                }))?)
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                return Err(ApiError::new_from_message(format!(
+                    "Push notification failed with status {}",
+                    response.status()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Used when no push service is configured
+pub struct LogPushProvider;
+
+#[async_trait(?Send)]
+impl PushProvider for LogPushProvider {
+    async fn send(
+        &self,
+        _client: &reqwest::Client,
+        device_tokens: &[String],
+        notification: &PushNotification,
+    ) -> ApiResult<()> {
+        log::debug!(
+            "Push notification {:?} for {} devices",
+            notification,
+            device_tokens.len()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,506 @@
+use crate::cache::Cache;
+use crate::config::chains::chain_config;
+use crate::config::{rpc_cache_duration, rpc_request_timeout};
+use crate::providers::info::SafeInfo;
+use crate::utils::data_decoder::checksum_address;
+use crate::utils::errors::{ApiError, ApiResult};
+use crate::utils::transactions::parse_version;
+use ethabi::{Address, Hash, ParamType, Token, Uint};
+use ethcontract_common::hash::keccak256;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+// Starts like the cached requests, so that the webhooks of the safe invalidate it as well
+const SAFE_STATE_KEY: &str = "c_reqs_rpc";
+const MODULES_PAGE_SIZE: u64 = 50;
+
+const NONCE_SIGNATURE: &str = "nonce()";
+const GET_THRESHOLD_SIGNATURE: &str = "getThreshold()";
+const GET_OWNERS_SIGNATURE: &str = "getOwners()";
+const GET_MODULES_PAGINATED_SIGNATURE: &str = "getModulesPaginated(address,uint256)";
+const VERSION_SIGNATURE: &str = "VERSION()";
+const ALLOWANCE_SIGNATURE: &str = "allowance(address,address)";
+const IS_APPROVED_FOR_ALL_SIGNATURE: &str = "isApprovedForAll(address,address)";
+
+// Error returned by the node, e.g. when an `eth_call` reverts
+#[derive(Deserialize, Debug, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    pub data: Option<Value>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RpcResponse {
+    result: Option<Value>,
+    error: Option<RpcError>,
+}
+
+// Header fields of a block, as needed to execute calls on top of it
+#[derive(Debug, PartialEq)]
+pub struct BlockHeader {
+    pub number: Uint,
+    pub hash: Hash,
+    pub timestamp: Uint,
+    pub gas_limit: Uint,
+    pub difficulty: Uint,
+    pub coinbase: Address,
+}
+
+// Values are hex encoded as expected by the JSON-RPC API
+#[derive(Serialize, Debug)]
+pub struct CallRequest {
+    pub from: String,
+    pub to: String,
+    pub value: String,
+    pub data: String,
+}
+
+// Client for the JSON-RPC node configured for the chain (`rpcUri` in the chains config)
+pub struct RpcClient<'c> {
+    client: &'c reqwest::Client,
+    chain_id: String,
+    url: String,
+}
+
+// State of a safe as read from its contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SafeState {
+    pub nonce: u64,
+    pub threshold: u64,
+    pub owners: Vec<String>,
+    // `getModulesPaginated` is only available from Safe v1.1.0 on
+    pub modules: Option<Vec<String>>,
+    pub version: Option<String>,
+}
+
+impl<'c> RpcClient<'c> {
+    pub fn new(chain_id: &str, client: &'c reqwest::Client) -> ApiResult<Self> {
+        let url = chain_config(chain_id)?
+            .rpc_uri
+            .to_owned()
+            .ok_or_else(|| client_error!(422, "No RPC node configured for the chain"))?;
+        Ok(RpcClient {
+            client,
+            chain_id: chain_id.to_string(),
+            url,
+        })
+    }
+
+    // Cached for `RPC_CACHE_DURATION`, or until a webhook for the safe arrives
+    pub async fn safe_state(&self, cache: &dyn Cache, safe_address: &str) -> ApiResult<SafeState> {
+        let key = format!("{}_{}_{}", SAFE_STATE_KEY, self.chain_id, safe_address);
+        if let Some(state) = cache
+            .fetch(&key)
+            .await
+            .and_then(|cached| serde_json::from_str(&cached).ok())
+        {
+            return Ok(state);
+        }
+        let state = self.load_safe_state(safe_address).await?;
+        cache
+            .create(&key, &serde_json::to_string(&state)?, rpc_cache_duration())
+            .await;
+        Ok(state)
+    }
+
+    // Node errors (like reverts) are returned as `Err` in the result, transport errors as `ApiError`
+    pub async fn call(&self, call: &CallRequest) -> ApiResult<Result<Vec<u8>, RpcError>> {
+        Ok(
+            match self.request("eth_call", json!([call, "latest"])).await? {
+                Ok(result) => Ok(hex_value(&result)?),
+                Err(error) => Err(error),
+            },
+        )
+    }
+
+    pub async fn estimate_gas(&self, call: &CallRequest) -> ApiResult<Result<Uint, RpcError>> {
+        Ok(
+            match self.request("eth_estimateGas", json!([call])).await? {
+                Ok(result) => hex_uint(&result),
+                Err(error) => Err(error),
+            },
+        )
+    }
+
+    // `block` is a block number (hex encoded) or tag (e.g. `latest`)
+    pub async fn block_header(&self, block: &str) -> ApiResult<BlockHeader> {
+        let result = self
+            .query("eth_getBlockByNumber", json!([block, false]))
+            .await?;
+        let field = |name: &str| {
+            result
+                .get(name)
+                .ok_or_else(|| ApiError::new_from_message("Unexpected RPC result"))
+        };
+        let hash = hex_value(field("hash")?)?;
+        let coinbase = hex_value(field("miner")?)?;
+        if hash.len() != 32 || coinbase.len() != 20 {
+            return Err(ApiError::new_from_message("Unexpected RPC result"));
+        }
+        Ok(BlockHeader {
+            number: hex_uint(field("number")?)?,
+            hash: Hash::from_slice(&hash),
+            timestamp: hex_uint(field("timestamp")?)?,
+            gas_limit: hex_uint(field("gasLimit")?)?,
+            difficulty: hex_uint(field("difficulty")?)?,
+            coinbase: Address::from_slice(&coinbase),
+        })
+    }
+
+    pub async fn get_balance(&self, address: &Address, block: &str) -> ApiResult<Uint> {
+        hex_uint(
+            &self
+                .query("eth_getBalance", json!([address, block]))
+                .await?,
+        )
+    }
+
+    pub async fn get_transaction_count(&self, address: &Address, block: &str) -> ApiResult<Uint> {
+        hex_uint(
+            &self
+                .query("eth_getTransactionCount", json!([address, block]))
+                .await?,
+        )
+    }
+
+    pub async fn get_code(&self, address: &Address, block: &str) -> ApiResult<Vec<u8>> {
+        hex_value(&self.query("eth_getCode", json!([address, block])).await?)
+    }
+
+    pub async fn get_storage_at(
+        &self,
+        address: &Address,
+        slot: &Hash,
+        block: &str,
+    ) -> ApiResult<Hash> {
+        let value = hex_uint(
+            &self
+                .query("eth_getStorageAt", json!([address, slot, block]))
+                .await?,
+        )?;
+        let mut word = [0u8; 32];
+        value.to_big_endian(&mut word);
+        Ok(Hash::from(word))
+    }
+
+    // Amount `spender` can still transfer from `owner`, `None` if the token doesn't implement ERC20
+    pub async fn allowance(
+        &self,
+        token: &str,
+        owner: &str,
+        spender: &str,
+    ) -> ApiResult<Option<Uint>> {
+        let (owner, spender) = match (parse_address(owner), parse_address(spender)) {
+            (Some(owner), Some(spender)) => (owner, spender),
+            _ => return Ok(None),
+        };
+        Ok(self
+            .read(
+                token,
+                ALLOWANCE_SIGNATURE,
+                &[Token::Address(owner), Token::Address(spender)],
+            )
+            .await?
+            .and_then(|result| decode_uint(&result)))
+    }
+
+    // `None` if the token doesn't implement `isApprovedForAll` (ERC721 and ERC1155)
+    pub async fn is_approved_for_all(
+        &self,
+        token: &str,
+        owner: &str,
+        operator: &str,
+    ) -> ApiResult<Option<bool>> {
+        let (owner, operator) = match (parse_address(owner), parse_address(operator)) {
+            (Some(owner), Some(operator)) => (owner, operator),
+            _ => return Ok(None),
+        };
+        Ok(self
+            .read(
+                token,
+                IS_APPROVED_FOR_ALL_SIGNATURE,
+                &[Token::Address(owner), Token::Address(operator)],
+            )
+            .await?
+            .and_then(|result| decode_bool(&result)))
+    }
+
+    async fn load_safe_state(&self, safe_address: &str) -> ApiResult<SafeState> {
+        let nonce = self
+            .read(safe_address, NONCE_SIGNATURE, &[])
+            .await?
+            .and_then(|result| decode_u64(&result));
+        let threshold = self
+            .read(safe_address, GET_THRESHOLD_SIGNATURE, &[])
+            .await?
+            .and_then(|result| decode_u64(&result));
+        let owners = self
+            .read(safe_address, GET_OWNERS_SIGNATURE, &[])
+            .await?
+            .and_then(|result| decode_addresses(&result));
+        let (nonce, threshold, owners) = match (nonce, threshold, owners) {
+            (Some(nonce), Some(threshold), Some(owners)) => (nonce, threshold, owners),
+            _ => return Err(client_error!(422, "Address is not a Safe")),
+        };
+        let version = self
+            .read(safe_address, VERSION_SIGNATURE, &[])
+            .await?
+            .and_then(|result| decode_string(&result));
+        Ok(SafeState {
+            nonce,
+            threshold,
+            owners,
+            modules: self.load_modules(safe_address).await?,
+            version,
+        })
+    }
+
+    // Modules are a linked list in the contract, starting and ending with the sentinel
+    async fn load_modules(&self, safe_address: &str) -> ApiResult<Option<Vec<String>>> {
+        let sentinel = Address::from_low_u64_be(1);
+        let mut modules = vec![];
+        let mut start = sentinel;
+        loop {
+            let page = self
+                .read(
+                    safe_address,
+                    GET_MODULES_PAGINATED_SIGNATURE,
+                    &[
+                        Token::Address(start),
+                        Token::Uint(Uint::from(MODULES_PAGE_SIZE)),
+                    ],
+                )
+                .await?
+                .and_then(|result| decode_modules_page(&result));
+            let (page, next) = match page {
+                Some(page) => page,
+                None => return Ok(None),
+            };
+            let last_page = page.is_empty() || next == sentinel || next.is_zero();
+            modules.extend(page);
+            if last_page {
+                return Ok(Some(modules));
+            }
+            start = next;
+        }
+    }
+
+    // `None` when the call reverted, e.g. because the contract doesn't implement the method
+    async fn read(
+        &self,
+        to: &str,
+        signature: &str,
+        params: &[Token],
+    ) -> ApiResult<Option<Vec<u8>>> {
+        let call = CallRequest {
+            from: to_hex_string!(Address::zero().as_bytes()),
+            to: to.to_owned(),
+            value: String::from("0x0"),
+            data: to_hex_string!(call_data(signature, params)),
+        };
+        Ok(self.call(&call).await?.ok())
+    }
+
+    // For reads of the state, where errors of the node are unexpected
+    async fn query(&self, method: &str, params: Value) -> ApiResult<Value> {
+        self.request(method, params).await?.map_err(|error| {
+            ApiError::new_from_message(format!("{} failed: {}", method, error.message))
+        })
+    }
+
+    async fn request(&self, method: &str, params: Value) -> ApiResult<Result<Value, RpcError>> {
+        let response = self
+            .client
+            .post(&self.url)
+            .timeout(Duration::from_millis(rpc_request_timeout()))
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(ApiError::from_http_response(
+                response,
+                format!("Unexpected {} error", method),
+            )
+            .await);
+        }
+        let response: RpcResponse = response.json().await?;
+        match (response.result, response.error) {
+            (_, Some(error)) => Ok(Err(error)),
+            (Some(result), None) => Ok(Ok(result)),
+            (None, None) => Err(ApiError::new_from_message(format!(
+                "Empty {} response",
+                method
+            ))),
+        }
+    }
+}
+
+impl RpcError {
+    // Revert data is only attached by some nodes (e.g. geth), as hex string
+    pub fn revert_data(&self) -> Option<Vec<u8>> {
+        let data = self.data.as_ref()?.as_str()?;
+        hex::decode(data.trim_start_matches("0x")).ok()
+    }
+}
+
+impl SafeState {
+    // Fields for which the transaction service reports a different value than the contract
+    pub fn mismatches(&self, safe_info: &SafeInfo) -> Vec<&'static str> {
+        let mut mismatches = vec![];
+        if self.nonce != safe_info.nonce {
+            mismatches.push("nonce");
+        }
+        if self.threshold != safe_info.threshold {
+            mismatches.push("threshold");
+        }
+        if !same_addresses(&self.owners, &safe_info.owners) {
+            mismatches.push("owners");
+        }
+        if let (Some(modules), Some(safe_info_modules)) = (&self.modules, &safe_info.modules) {
+            if !same_addresses(modules, safe_info_modules) {
+                mismatches.push("modules");
+            }
+        }
+        if !self.same_version(safe_info) {
+            mismatches.push("version");
+        }
+        mismatches
+    }
+
+    // The contract has the final say, values it can't provide are kept
+    pub fn apply_to(&self, safe_info: &mut SafeInfo) {
+        safe_info.nonce = self.nonce;
+        safe_info.threshold = self.threshold;
+        safe_info.owners = self.owners.to_owned();
+        if self.modules.is_some() {
+            safe_info.modules = self.modules.to_owned();
+        }
+        // The version of the transaction service also tells L2 safes apart (e.g. `1.3.0+L2`)
+        if !self.same_version(safe_info) {
+            safe_info.version = self.version.to_owned();
+        }
+    }
+
+    fn same_version(&self, safe_info: &SafeInfo) -> bool {
+        match (&self.version, &safe_info.version) {
+            (Some(version), Some(safe_info_version)) => {
+                parse_version(version) == parse_version(safe_info_version)
+            }
+            (None, _) => true,
+            (Some(_), None) => false,
+        }
+    }
+}
+
+pub(crate) fn call_data(signature: &str, params: &[Token]) -> Vec<u8> {
+    let mut data = keccak256(signature)[..4].to_vec();
+    data.extend(ethabi::encode(params));
+    data
+}
+
+pub(crate) fn decode_u64(result: &[u8]) -> Option<u64> {
+    match ethabi::decode(&[ParamType::Uint(256)], result)
+        .ok()?
+        .pop()?
+    {
+        Token::Uint(value) if value <= Uint::from(u64::MAX) => Some(value.low_u64()),
+        _ => None,
+    }
+}
+
+pub(crate) fn decode_uint(result: &[u8]) -> Option<Uint> {
+    match ethabi::decode(&[ParamType::Uint(256)], result)
+        .ok()?
+        .pop()?
+    {
+        Token::Uint(value) => Some(value),
+        _ => None,
+    }
+}
+
+pub(crate) fn decode_bool(result: &[u8]) -> Option<bool> {
+    match ethabi::decode(&[ParamType::Bool], result).ok()?.pop()? {
+        Token::Bool(value) => Some(value),
+        _ => None,
+    }
+}
+
+pub(crate) fn decode_string(result: &[u8]) -> Option<String> {
+    match ethabi::decode(&[ParamType::String], result).ok()?.pop()? {
+        Token::String(value) => Some(value),
+        _ => None,
+    }
+}
+
+pub(crate) fn decode_addresses(result: &[u8]) -> Option<Vec<String>> {
+    let address_array = ParamType::Array(Box::new(ParamType::Address));
+    to_addresses(ethabi::decode(&[address_array], result).ok()?.pop()?)
+}
+
+// `getModulesPaginated` returns the page and the module to start the next page from
+pub(crate) fn decode_modules_page(result: &[u8]) -> Option<(Vec<String>, Address)> {
+    let address_array = ParamType::Array(Box::new(ParamType::Address));
+    let mut tokens = ethabi::decode(&[address_array, ParamType::Address], result)
+        .ok()?
+        .into_iter();
+    let modules = to_addresses(tokens.next()?)?;
+    match tokens.next()? {
+        Token::Address(next) => Some((modules, next)),
+        _ => None,
+    }
+}
+
+fn to_addresses(token: Token) -> Option<Vec<String>> {
+    match token {
+        Token::Array(tokens) => tokens
+            .into_iter()
+            .map(|token| match token {
+                Token::Address(address) => Some(checksum_address(address.as_bytes())),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+fn parse_address(address: &str) -> Option<Address> {
+    address.trim_start_matches("0x").parse().ok()
+}
+
+fn same_addresses(addresses: &[String], other: &[String]) -> bool {
+    addresses.len() == other.len()
+        && addresses
+            .iter()
+            .zip(other.iter())
+            .all(|(address, other)| address.eq_ignore_ascii_case(other))
+}
+
+// Quantities above 256 bits can't be represented, `Uint::from_big_endian` would panic for them
+pub(crate) fn hex_uint(value: &Value) -> ApiResult<Uint> {
+    let value = hex_value(value)?;
+    if value.len() > 32 {
+        return Err(ApiError::new_from_message("Unexpected RPC result"));
+    }
+    Ok(Uint::from_big_endian(&value))
+}
+
+fn hex_value(value: &Value) -> ApiResult<Vec<u8>> {
+    let value = value
+        .as_str()
+        .ok_or_else(|| ApiError::new_from_message("Unexpected RPC result"))?;
+    let value = value.trim_start_matches("0x");
+    // Quantities are not zero padded (e.g. `0x5208`)
+    let value = if value.len() % 2 == 0 {
+        value.to_string()
+    } else {
+        format!("0{}", value)
+    };
+    hex::decode(value).map_err(|_| ApiError::new_from_message("Unexpected RPC result"))
+}
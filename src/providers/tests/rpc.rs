@@ -0,0 +1,110 @@
+use crate::providers::info::SafeInfo;
+use crate::providers::rpc::{
+    call_data, decode_addresses, decode_bool, decode_modules_page, decode_string, decode_u64,
+    hex_uint, SafeState,
+};
+use ethabi::{Address, Token, Uint};
+use serde_json::json;
+
+const OWNER: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const MODULE: &str = "0x938bae50a210b80EA233112800Cd5Bc2e7644300";
+
+fn safe_state(safe_info: &SafeInfo) -> SafeState {
+    SafeState {
+        nonce: safe_info.nonce,
+        threshold: safe_info.threshold,
+        owners: safe_info.owners.to_owned(),
+        modules: safe_info.modules.to_owned(),
+        version: safe_info.version.to_owned(),
+    }
+}
+
+#[test]
+fn call_data_get_modules_paginated() {
+    let actual = call_data(
+        "getModulesPaginated(address,uint256)",
+        &[
+            Token::Address(Address::from_low_u64_be(1)),
+            Token::Uint(Uint::from(50)),
+        ],
+    );
+
+    assert_eq!(
+        to_hex_string!(actual),
+        "0xcc2f845200000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000032"
+    );
+    assert_eq!(to_hex_string!(call_data("nonce()", &[])), "0xaffed0e0");
+}
+
+#[test]
+fn decode_safe_reads() {
+    let nonce =
+        hex::decode("00000000000000000000000000000000000000000000000000000000000000b4").unwrap();
+    let owners = hex::decode("000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000020000000000000000000000001230b3d59858296a31053c1b8562ecf89a2f888b000000000000000000000000938bae50a210b80ea233112800cd5bc2e7644300").unwrap();
+    let version = hex::decode("00000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000005312e332e30000000000000000000000000000000000000000000000000000000").unwrap();
+
+    assert_eq!(decode_u64(&nonce), Some(180));
+    assert_eq!(
+        decode_addresses(&owners),
+        Some(vec![String::from(OWNER), String::from(MODULE)])
+    );
+    assert_eq!(decode_string(&version), Some(String::from("1.3.0")));
+    assert_eq!(
+        decode_bool(
+            &hex::decode("0000000000000000000000000000000000000000000000000000000000000001")
+                .unwrap()
+        ),
+        Some(true)
+    );
+    // Calls to accounts without code succeed without any result
+    assert_eq!(decode_u64(&[]), None);
+    assert_eq!(decode_bool(&[]), None);
+    assert_eq!(decode_addresses(&[]), None);
+}
+
+#[test]
+fn decode_modules_page_last_page() {
+    let result = hex::decode("000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001000000000000000000000000938bae50a210b80ea233112800cd5bc2e7644300").unwrap();
+
+    assert_eq!(
+        decode_modules_page(&result),
+        Some((vec![String::from(MODULE)], Address::from_low_u64_be(1)))
+    );
+}
+
+#[test]
+fn safe_state_mismatches_and_apply_to() {
+    let mut safe_info = serde_json::from_str::<SafeInfo>(crate::json::SAFE_WITH_MODULES).unwrap();
+    let mut state = safe_state(&safe_info);
+    state.owners = state.owners.iter().map(|it| it.to_lowercase()).collect();
+    assert!(state.mismatches(&safe_info).is_empty());
+
+    state.nonce = 181;
+    state.owners.push(String::from(OWNER));
+    state.modules = None;
+    state.version = Some(String::from("1.1.1+L2"));
+    assert_eq!(state.mismatches(&safe_info), vec!["nonce", "owners"]);
+
+    state.apply_to(&mut safe_info);
+    assert_eq!(safe_info.nonce, 181);
+    assert_eq!(safe_info.owners.len(), 6);
+    assert_eq!(safe_info.modules.as_ref().map(|it| it.len()), Some(3));
+    assert_eq!(safe_info.version, Some(String::from("1.1.1")));
+}
+
+#[test]
+fn hex_uint_quantities() {
+    assert_eq!(hex_uint(&json!("0x5208")).unwrap(), Uint::from(21000));
+    assert_eq!(hex_uint(&json!("0x0")).unwrap(), Uint::zero());
+    assert_eq!(
+        hex_uint(&json!(format!("0x{}", "f".repeat(64)))).unwrap(),
+        Uint::max_value()
+    );
+
+    let error = hex_uint(&json!(format!("0x1{}", "0".repeat(64)))).unwrap_err();
+    assert_eq!(
+        error.details.message,
+        Some(String::from("Unexpected RPC result"))
+    );
+    assert!(hex_uint(&json!(21000)).is_err());
+}
@@ -9,16 +9,31 @@ use rocket::response::content;
 
 #[get("/about")]
 pub fn info(context: Context) -> ApiResult<content::Json<String>> {
-    CacheResponse::new(context.uri())
-        .duration(about_cache_duration())
-        .resp_generator(about::get_about)
-        .execute(context.cache())
+    context.block_on(
+        CacheResponse::new(context.uri())
+            .duration(about_cache_duration())
+            .resp_generator(|| async { about::get_about() })
+            .execute(context.cache()),
+    )
 }
 
-#[get("/about/backbone")]
-pub fn backbone(context: Context) -> ApiResult<content::Json<String>> {
-    let url = format!("{}/v1/about/", base_transaction_service_url());
-    Ok(content::Json(context.client().get(&url).send()?.text()?))
+#[get("/v1/chains/<chain_id>/about")]
+pub fn chain_info(context: Context, chain_id: String) -> ApiResult<content::Json<String>> {
+    context.block_on(
+        CacheResponse::new(context.uri())
+            .duration(about_cache_duration())
+            .resp_generator(|| async { about::get_chain_about(&chain_id) })
+            .execute(context.cache()),
+    )
+}
+
+#[get("/v1/chains/<chain_id>/about/backbone")]
+pub fn backbone(context: Context, chain_id: String) -> ApiResult<content::Json<String>> {
+    let url = format!("{}/v1/about/", base_transaction_service_url(&chain_id)?);
+    context.block_on(async {
+        let response = context.client().get(&url).send().await?;
+        Ok(content::Json(response.text().await?))
+    })
 }
 
 #[get("/about/redis/<token>")]
@@ -26,5 +41,7 @@ pub fn redis(context: Context, token: String) -> ApiResult<String> {
     if token != webhook_token() {
         bail!("Invalid token");
     }
-    Ok(context.cache().info().unwrap_or(String::new()))
+    Ok(context
+        .block_on(context.cache().info())
+        .unwrap_or(String::new()))
 }
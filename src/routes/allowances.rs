@@ -0,0 +1,18 @@
+use crate::cache::cache_operations::CacheResponse;
+use crate::services::allowances::get_allowances;
+use crate::utils::context::Context;
+use crate::utils::errors::ApiResult;
+use rocket::response::content;
+
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/allowances")]
+pub fn list(
+    context: Context,
+    chain_id: String,
+    safe_address: String,
+) -> ApiResult<content::Json<String>> {
+    context.block_on(
+        CacheResponse::new(context.uri())
+            .resp_generator(|| get_allowances(&context, &chain_id, &safe_address))
+            .execute(context.cache()),
+    )
+}
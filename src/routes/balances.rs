@@ -5,31 +5,37 @@ use crate::utils::context::Context;
 use crate::utils::errors::ApiResult;
 use rocket::response::content;
 
-#[get("/v1/safes/<safe_address>/balances/<fiat>?<trusted>&<exclude_spam>")]
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/balances/<fiat>?<trusted>&<exclude_spam>")]
 pub fn get_balances(
     context: Context,
+    chain_id: String,
     safe_address: String,
     fiat: String,
     trusted: Option<bool>,
     exclude_spam: Option<bool>,
 ) -> ApiResult<content::Json<String>> {
-    CacheResponse::new(context.uri())
-        .duration(balances_cache_duration())
-        .resp_generator(|| {
-            balances(
-                &context,
-                safe_address.as_str(),
-                fiat.as_str(),
-                trusted.unwrap_or(false),
-                exclude_spam.unwrap_or(true),
-            )
-        })
-        .execute(context.cache())
+    context.block_on(
+        CacheResponse::new(context.uri())
+            .duration(balances_cache_duration())
+            .resp_generator(|| {
+                balances(
+                    &context,
+                    chain_id.as_str(),
+                    safe_address.as_str(),
+                    fiat.as_str(),
+                    trusted.unwrap_or(false),
+                    exclude_spam.unwrap_or(true),
+                )
+            })
+            .execute(context.cache()),
+    )
 }
 
-#[get("/v1/balances/supported-fiat-codes")]
-pub fn get_supported_fiat(context: Context) -> ApiResult<content::Json<String>> {
-    CacheResponse::new(context.uri())
-        .resp_generator(|| fiat_codes(&context))
-        .execute(context.cache())
+#[get("/v1/chains/<chain_id>/balances/supported-fiat-codes")]
+pub fn get_supported_fiat(context: Context, chain_id: String) -> ApiResult<content::Json<String>> {
+    context.block_on(
+        CacheResponse::new(context.uri())
+            .resp_generator(|| fiat_codes(&context, &chain_id))
+            .execute(context.cache()),
+    )
 }
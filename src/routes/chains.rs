@@ -0,0 +1,23 @@
+use crate::cache::cache_operations::CacheResponse;
+use crate::services::chains;
+use crate::utils::context::Context;
+use crate::utils::errors::ApiResult;
+use rocket::response::content;
+
+#[get("/v1/chains")]
+pub fn get_chains(context: Context) -> ApiResult<content::Json<String>> {
+    context.block_on(
+        CacheResponse::new(context.uri())
+            .resp_generator(|| async { chains::get_chains() })
+            .execute(context.cache()),
+    )
+}
+
+#[get("/v1/chains/<chain_id>")]
+pub fn get_chain(context: Context, chain_id: String) -> ApiResult<content::Json<String>> {
+    context.block_on(
+        CacheResponse::new(context.uri())
+            .resp_generator(|| async { chains::get_chain_info(&chain_id) })
+            .execute(context.cache()),
+    )
+}
@@ -4,24 +4,28 @@ use crate::utils::context::Context;
 use crate::utils::errors::ApiResult;
 use rocket::response::content;
 
-#[get("/v1/safes/<safe_address>/collectibles?<trusted>&<exclude_spam>")]
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/collectibles?<trusted>&<exclude_spam>")]
 pub fn list(
     context: Context,
+    chain_id: String,
     safe_address: String,
     trusted: Option<bool>,
     exclude_spam: Option<bool>,
 ) -> ApiResult<content::Json<String>> {
     let url = format!(
         "{}/v1/safes/{}/collectibles/?trusted={}&exclude_spam={}",
-        base_transaction_service_url(),
+        base_transaction_service_url(&chain_id)?,
         safe_address,
         trusted.unwrap_or(false),
         exclude_spam.unwrap_or(true)
     );
 
     Ok(content::Json(
-        RequestCached::new(url)
-            .request_timeout(collectibles_request_timeout())
-            .execute(context.client(), context.cache())?,
+        context.block_on(
+            RequestCached::new(url)
+                .chain_id(&chain_id)
+                .request_timeout(collectibles_request_timeout())
+                .execute(context.client(), context.cache()),
+        )?,
     ))
 }
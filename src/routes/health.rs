@@ -5,7 +5,9 @@ use rocket::response::content;
 
 #[get("/health")]
 pub fn health(context: Context) -> ApiResult<content::Json<String>> {
-    CacheResponse::new(String::from("/health"))
-        .resp_generator(|| Ok(String::new()))
-        .execute(context.cache())
+    context.block_on(
+        CacheResponse::new(String::from("/health"))
+            .resp_generator(|| async { Ok(String::new()) })
+            .execute(context.cache()),
+    )
 }
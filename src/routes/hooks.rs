@@ -1,17 +1,75 @@
 use crate::cache::cache_operations::{Invalidate, InvalidationPattern};
 use crate::config::webhook_token;
 use crate::models::backend::webhooks::Payload;
+use crate::models::service::webhooks::{Delivery, Subscription, SubscriptionRequest};
+use crate::providers::push::PushProvider;
+use crate::services::events::publish_safe_activity;
 use crate::services::hooks::invalidate_caches;
+use crate::services::notifications::notify_missing_signers;
+use crate::services::webhooks;
 use crate::utils::context::Context;
 use crate::utils::errors::ApiResult;
-use rocket_contrib::json::Json;
+use rocket::State;
+use rocket_contrib::json::{Json, JsonError};
 
+// Payloads without `chainId` only invalidate the caches, use `chain_update` to forward them
 #[post("/v1/hook/update/<token>", format = "json", data = "<update>")]
-pub fn update(context: Context, token: String, update: Json<Payload>) -> ApiResult<()> {
+pub fn update(
+    context: Context,
+    push_provider: State<Box<dyn PushProvider>>,
+    token: String,
+    update: Json<Payload>,
+) -> ApiResult<()> {
+    context.block_on(process_update(
+        &context,
+        push_provider.inner().as_ref(),
+        &token,
+        &update,
+    ))
+}
+
+// Webhook of the transaction service of a chain, its payloads don't contain the chain
+#[post(
+    "/v1/chains/<chain_id>/hook/update/<token>",
+    format = "json",
+    data = "<update>"
+)]
+pub fn chain_update(
+    context: Context,
+    push_provider: State<Box<dyn PushProvider>>,
+    chain_id: String,
+    token: String,
+    update: Json<Payload>,
+) -> ApiResult<()> {
+    let payload = update.into_inner().with_chain_id(chain_id);
+    context.block_on(process_update(
+        &context,
+        push_provider.inner().as_ref(),
+        &token,
+        &payload,
+    ))
+}
+
+async fn process_update(
+    context: &Context,
+    push_provider: &dyn PushProvider,
+    token: &str,
+    payload: &Payload,
+) -> ApiResult<()> {
     if token != webhook_token() {
         bail!("Invalid token");
     }
-    invalidate_caches(context.cache(), &update)
+    invalidate_caches(context.cache(), payload).await?;
+    publish_safe_activity(context.cache(), payload).await?;
+    // Without redis there are no webhook subscriptions to deliver to
+    if let Ok(cache) = context.persistent_cache() {
+        webhooks::enqueue_deliveries(cache.as_ref(), payload).await?;
+    }
+    // The update itself was processed, a retry of the webhook would only invalidate everything again
+    if let Err(err) = notify_missing_signers(context, push_provider, payload).await {
+        log::error!("Could not notify the missing signers: {:?}", err);
+    }
+    Ok(())
 }
 
 #[get("/v1/flush_all/<token>")]
@@ -19,6 +77,117 @@ pub fn flush_all(context: Context, token: String) -> ApiResult<()> {
     if token != webhook_token() {
         bail!("Invalid token");
     }
-    Invalidate::new(InvalidationPattern::FlushAll).execute(context.cache());
+    context.block_on(Invalidate::new(InvalidationPattern::FlushAll).execute(context.cache()));
     Ok(())
 }
+
+#[post(
+    "/v1/hook/<token>/chains/<chain_id>/safes/<safe_address>/subscriptions",
+    format = "application/json",
+    data = "<subscription_request>"
+)]
+pub fn subscribe(
+    context: Context,
+    token: String,
+    chain_id: String,
+    safe_address: String,
+    subscription_request: Result<Json<SubscriptionRequest>, JsonError>,
+) -> ApiResult<Json<Subscription>> {
+    if token != webhook_token() {
+        bail!("Invalid token");
+    }
+    context
+        .block_on(webhooks::subscribe(
+            context.persistent_cache()?.as_ref(),
+            &chain_id,
+            &safe_address,
+            &subscription_request?.0,
+        ))
+        .map(Json)
+}
+
+#[get("/v1/hook/<token>/chains/<chain_id>/safes/<safe_address>/subscriptions")]
+pub fn subscriptions(
+    context: Context,
+    token: String,
+    chain_id: String,
+    safe_address: String,
+) -> ApiResult<Json<Vec<Subscription>>> {
+    if token != webhook_token() {
+        bail!("Invalid token");
+    }
+    context
+        .block_on(webhooks::get_subscriptions(
+            context.persistent_cache()?.as_ref(),
+            &chain_id,
+            &safe_address,
+        ))
+        .map(Json)
+}
+
+#[delete("/v1/hook/<token>/chains/<chain_id>/safes/<safe_address>/subscriptions/<subscription_id>")]
+pub fn unsubscribe(
+    context: Context,
+    token: String,
+    chain_id: String,
+    safe_address: String,
+    subscription_id: String,
+) -> ApiResult<()> {
+    if token != webhook_token() {
+        bail!("Invalid token");
+    }
+    context.block_on(webhooks::unsubscribe(
+        context.persistent_cache()?.as_ref(),
+        &chain_id,
+        &safe_address,
+        &subscription_id,
+    ))
+}
+
+#[get(
+    "/v1/hook/<token>/chains/<chain_id>/safes/<safe_address>/subscriptions/<subscription_id>/deliveries"
+)]
+pub fn deliveries(
+    context: Context,
+    token: String,
+    chain_id: String,
+    safe_address: String,
+    subscription_id: String,
+) -> ApiResult<Json<Vec<Delivery>>> {
+    if token != webhook_token() {
+        bail!("Invalid token");
+    }
+    context
+        .block_on(webhooks::get_deliveries(
+            context.persistent_cache()?.as_ref(),
+            &chain_id,
+            &safe_address,
+            &subscription_id,
+        ))
+        .map(Json)
+}
+
+#[get(
+    "/v1/hook/<token>/chains/<chain_id>/safes/<safe_address>/subscriptions/<subscription_id>/deliveries/<delivery_id>"
+)]
+pub fn delivery(
+    context: Context,
+    token: String,
+    chain_id: String,
+    safe_address: String,
+    subscription_id: String,
+    delivery_id: String,
+) -> ApiResult<Json<Delivery>> {
+    if token != webhook_token() {
+        bail!("Invalid token");
+    }
+    context
+        .block_on(webhooks::get_delivery(
+            context.persistent_cache()?.as_ref(),
+            &chain_id,
+            &safe_address,
+            &subscription_id,
+            &delivery_id,
+        ))
+        .map(Json)
+}
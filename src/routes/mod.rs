@@ -6,10 +6,14 @@ use rocket::Route;
 use rocket_contrib::json::JsonValue;
 
 pub mod about;
+pub mod allowances;
 pub mod balances;
+pub mod chains;
 pub mod collectibles;
 pub mod health;
 pub mod hooks;
+pub mod notifications;
+pub mod owners;
 pub mod safes;
 pub mod transactions;
 
@@ -17,26 +21,45 @@ pub fn active_routes() -> Vec<Route> {
     routes![
         root,
         about::backbone,
+        about::chain_info,
         about::info,
         about::redis,
+        allowances::list,
         balances::get_balances,
         balances::get_supported_fiat,
+        chains::get_chains,
+        chains::get_chain,
         collectibles::list,
+        owners::safes,
         safes::safe_info,
+        safes::safe_upgrade,
+        safes::events,
         transactions::details,
         transactions::all,
         transactions::history_transactions,
         transactions::queued_transactions,
         transactions::submit_confirmation,
         transactions::propose_transaction,
+        transactions::prepare_transaction,
+        transactions::simulate_transaction,
+        transactions::estimate_transaction,
+        transactions::propose_rejection,
         hooks::update,
+        hooks::chain_update,
         hooks::flush_all,
+        hooks::subscribe,
+        hooks::subscriptions,
+        hooks::unsubscribe,
+        hooks::deliveries,
+        hooks::delivery,
+        notifications::register_device,
+        notifications::unregister_device,
         health::health
     ]
 }
 
 pub fn error_catchers() -> Vec<Catcher> {
-    catchers![not_found, panic]
+    catchers![not_found, panic, unavailable]
 }
 
 #[catch(404)]
@@ -55,6 +78,14 @@ fn panic() -> JsonValue {
     })
 }
 
+#[catch(503)]
+fn unavailable() -> JsonValue {
+    json!({
+        "status": "error",
+        "reason": "Service temporarily unavailable."
+    })
+}
+
 #[get("/")]
 pub fn root() -> Redirect {
     Redirect::temporary("https://github.com/gnosis/safe-client-gateway/wiki")
@@ -0,0 +1,44 @@
+use crate::config::webhook_token;
+use crate::models::service::notifications::DeviceRegistration;
+use crate::services::notifications;
+use crate::utils::context::Context;
+use crate::utils::errors::ApiResult;
+use rocket_contrib::json::{Json, JsonError};
+
+#[post(
+    "/v1/notifications/<token>/owners/<owner_address>/devices",
+    format = "application/json",
+    data = "<registration>"
+)]
+pub fn register_device(
+    context: Context,
+    token: String,
+    owner_address: String,
+    registration: Result<Json<DeviceRegistration>, JsonError>,
+) -> ApiResult<()> {
+    if token != webhook_token() {
+        bail!("Invalid token");
+    }
+    context.block_on(notifications::register_device(
+        context.persistent_cache()?.as_ref(),
+        &owner_address,
+        &registration?.0,
+    ))
+}
+
+#[delete("/v1/notifications/<token>/owners/<owner_address>/devices/<device_token>")]
+pub fn unregister_device(
+    context: Context,
+    token: String,
+    owner_address: String,
+    device_token: String,
+) -> ApiResult<()> {
+    if token != webhook_token() {
+        bail!("Invalid token");
+    }
+    context.block_on(notifications::unregister_device(
+        context.persistent_cache()?.as_ref(),
+        &owner_address,
+        &device_token,
+    ))
+}
@@ -0,0 +1,19 @@
+use crate::cache::cache_operations::CacheResponse;
+use crate::config::owner_safes_cache_duration;
+use crate::services::owners::{checksum_owner_address, get_owner_safes};
+use crate::utils::context::Context;
+use crate::utils::errors::ApiResult;
+use rocket::response::content;
+
+// Safes of the owner on every chain, with the number of queued transactions it still has to confirm
+#[get("/v1/owners/<owner_address>/safes")]
+pub fn safes(context: Context, owner_address: String) -> ApiResult<content::Json<String>> {
+    let owner_address = checksum_owner_address(&owner_address)?;
+    // Keyed by the checksummed address, the one the webhooks of the listed safes invalidate
+    context.block_on(
+        CacheResponse::new(format!("/v1/owners/{}/safes", owner_address))
+            .duration(owner_safes_cache_duration())
+            .resp_generator(|| get_owner_safes(&context, &owner_address))
+            .execute(context.cache()),
+    )
+}
@@ -1,12 +1,70 @@
 use crate::cache::cache_operations::CacheResponse;
-use crate::services::safes::get_safe_info_ex;
+use crate::services::events::{SafeEventsBroadcaster, EVENTS_WAIT_INTERVAL};
+use crate::services::safes::{get_safe_info_ex, get_safe_upgrade};
 use crate::utils::context::Context;
 use crate::utils::errors::ApiResult;
-use rocket::response::content;
+use rocket::http::ContentType;
+use rocket::request::{self, FromRequest, Request};
+use rocket::response::{content, Response};
+use rocket::{Outcome, State};
+use std::io::Cursor;
 
-#[get("/v1/safes/<safe_address>")]
-pub fn safe_info(context: Context, safe_address: String) -> ApiResult<content::Json<String>> {
-    CacheResponse::new(context.uri())
-        .resp_generator(|| get_safe_info_ex(&context, &safe_address))
-        .execute(context.cache())
+// Id of the last event received, sent by `EventSource` when it reconnects
+pub struct LastEventId(Option<String>);
+
+impl<'a, 'r> FromRequest<'a, 'r> for LastEventId {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(LastEventId(
+            request.headers().get_one("Last-Event-ID").map(String::from),
+        ))
+    }
+}
+
+#[get("/v1/chains/<chain_id>/safes/<safe_address>")]
+pub fn safe_info(
+    context: Context,
+    chain_id: String,
+    safe_address: String,
+) -> ApiResult<content::Json<String>> {
+    context.block_on(
+        CacheResponse::new(context.uri())
+            .resp_generator(|| get_safe_info_ex(&context, &chain_id, &safe_address))
+            .execute(context.cache()),
+    )
+}
+
+// Transaction upgrading the Safe to the latest version, to be proposed like any other transaction
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/upgrade")]
+pub fn safe_upgrade(
+    context: Context,
+    chain_id: String,
+    safe_address: String,
+) -> ApiResult<content::Json<String>> {
+    context.block_on(
+        CacheResponse::new(context.uri())
+            .resp_generator(|| get_safe_upgrade(&context, &chain_id, &safe_address))
+            .execute(context.cache()),
+    )
+}
+
+// Server sent events for the activity of the safe, see `ActivityType` for the emitted events.
+// A response holds a worker thread until the next events or `EVENTS_WAIT_INTERVAL`, so
+// `MAX_EVENT_SUBSCRIBERS` has to stay below `ROCKET_WORKERS`. The events published while the
+// client reconnects are sent right away if it sends the `Last-Event-ID` it received.
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/events")]
+pub fn events(
+    broadcaster: State<SafeEventsBroadcaster>,
+    last_event_id: LastEventId,
+    chain_id: String,
+    safe_address: String,
+) -> ApiResult<Response<'static>> {
+    let subscription = broadcaster.listen(&chain_id, &safe_address, last_event_id.0.as_deref())?;
+    let body = subscription.next_events(EVENTS_WAIT_INTERVAL);
+    Ok(Response::build()
+        .header(ContentType::new("text", "event-stream"))
+        .raw_header("Cache-Control", "no-cache")
+        .sized_body(Cursor::new(body))
+        .finalize())
 }
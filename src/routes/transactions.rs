@@ -1,10 +1,11 @@
 use crate::cache::cache_operations::CacheResponse;
 use crate::models::service::transactions::requests::{
-    ConfirmationRequest, MultisigTransactionRequest,
+    ConfirmationRequest, MultisigTransactionRequest, PreparedTransaction, RejectionRequest,
+    TransactionEstimation, TransactionPrepareRequest, TransactionSimulation,
 };
 use crate::services::{
-    transactions_details, transactions_history, transactions_list, transactions_proposal,
-    transactions_queued,
+    transactions_details, transactions_estimation, transactions_history, transactions_list,
+    transactions_proposal, transactions_queued, transactions_simulation,
 };
 use crate::utils::context::Context;
 use crate::utils::errors::ApiResult;
@@ -12,103 +13,222 @@ use rocket::response::content;
 use rocket_contrib::json::Json;
 use rocket_contrib::json::JsonError;
 
-#[get("/v1/safes/<safe_address>/transactions?<page_url>")]
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/transactions?<page_url>")]
 pub fn all(
     context: Context,
+    chain_id: String,
     safe_address: String,
     page_url: Option<String>,
 ) -> ApiResult<content::Json<String>> {
-    CacheResponse::new(context.uri())
-        .resp_generator(|| {
-            transactions_list::get_all_transactions(&context, &safe_address, &page_url)
-        })
-        .execute(context.cache())
+    context.block_on(
+        CacheResponse::new(context.uri())
+            .resp_generator(|| {
+                transactions_list::get_all_transactions(
+                    &context,
+                    &chain_id,
+                    &safe_address,
+                    &page_url,
+                )
+            })
+            .execute(context.cache()),
+    )
 }
 
-#[get("/v1/transactions/<details_id>")]
-pub fn details(context: Context, details_id: String) -> ApiResult<content::Json<String>> {
-    CacheResponse::new(context.uri())
-        .resp_generator(|| transactions_details::get_transactions_details(&context, &details_id))
-        .execute(context.cache())
+#[get("/v1/chains/<chain_id>/transactions/<details_id>")]
+pub fn details(
+    context: Context,
+    chain_id: String,
+    details_id: String,
+) -> ApiResult<content::Json<String>> {
+    context.block_on(
+        CacheResponse::new(context.uri())
+            .resp_generator(|| {
+                transactions_details::get_transactions_details(&context, &chain_id, &details_id)
+            })
+            .execute(context.cache()),
+    )
 }
 
 #[post(
-    "/v1/transactions/<safe_tx_hash>/confirmations",
+    "/v1/chains/<chain_id>/transactions/<safe_tx_hash>/confirmations",
     format = "application/json",
     data = "<tx_confirmation_request>"
 )]
 pub fn submit_confirmation(
     context: Context,
+    chain_id: String,
     safe_tx_hash: String,
     tx_confirmation_request: Result<Json<ConfirmationRequest>, JsonError>,
 ) -> ApiResult<content::Json<String>> {
-    transactions_proposal::submit_confirmation(
-        &context,
-        &safe_tx_hash,
-        &tx_confirmation_request?.0.signed_safe_tx_hash,
-    )
-    .and_then(|_| {
+    let signed_safe_tx_hash = tx_confirmation_request?.0.signed_safe_tx_hash;
+    context.block_on(async {
+        transactions_proposal::submit_confirmation(
+            &context,
+            &chain_id,
+            &safe_tx_hash,
+            &signed_safe_tx_hash,
+        )
+        .await?;
         CacheResponse::new(context.uri())
             .resp_generator(|| {
-                transactions_details::get_transactions_details(&context, &safe_tx_hash)
+                transactions_details::get_transactions_details(&context, &chain_id, &safe_tx_hash)
             })
             .execute(context.cache())
+            .await
     })
 }
 
-#[get("/v1/safes/<safe_address>/transactions/history?<page_url>&<timezone_offset>")]
+#[get(
+    "/v1/chains/<chain_id>/safes/<safe_address>/transactions/history?<page_url>&<timezone_offset>"
+)]
 pub fn history_transactions(
     context: Context,
+    chain_id: String,
     safe_address: String,
     page_url: Option<String>,
     timezone_offset: Option<String>,
 ) -> ApiResult<content::Json<String>> {
-    CacheResponse::new(context.uri())
-        .resp_generator(|| {
-            transactions_history::get_history_transactions(
-                &context,
-                &safe_address,
-                &page_url,
-                &timezone_offset,
-            )
-        })
-        .execute(context.cache())
+    context.block_on(
+        CacheResponse::new(context.uri())
+            .resp_generator(|| {
+                transactions_history::get_history_transactions(
+                    &context,
+                    &chain_id,
+                    &safe_address,
+                    &page_url,
+                    &timezone_offset,
+                )
+            })
+            .execute(context.cache()),
+    )
 }
 
-#[get("/v1/safes/<safe_address>/transactions/queued?<page_url>&<timezone_offset>&<trusted>")]
+#[get(
+    "/v1/chains/<chain_id>/safes/<safe_address>/transactions/queued?<page_url>&<timezone_offset>&<trusted>"
+)]
 pub fn queued_transactions(
     context: Context,
+    chain_id: String,
     safe_address: String,
     page_url: Option<String>,
     timezone_offset: Option<String>,
     trusted: Option<bool>,
 ) -> ApiResult<content::Json<String>> {
-    CacheResponse::new(context.uri())
-        .resp_generator(|| {
-            transactions_queued::get_queued_transactions(
-                &context,
-                &safe_address,
-                &page_url,
-                &timezone_offset,
-                &trusted,
-            )
-        })
-        .execute(context.cache())
+    context.block_on(
+        CacheResponse::new(context.uri())
+            .resp_generator(|| {
+                transactions_queued::get_queued_transactions(
+                    &context,
+                    &chain_id,
+                    &safe_address,
+                    &page_url,
+                    &timezone_offset,
+                    &trusted,
+                )
+            })
+            .execute(context.cache()),
+    )
 }
 
 #[post(
-    "/v1/transactions/<safe_address>/propose",
+    "/v1/chains/<chain_id>/transactions/<safe_address>/propose",
     format = "application/json",
     data = "<multisig_transaction_request>"
 )]
 pub fn propose_transaction(
     context: Context,
+    chain_id: String,
     safe_address: String,
     multisig_transaction_request: Result<Json<MultisigTransactionRequest>, JsonError>,
 ) -> ApiResult<()> {
-    transactions_proposal::propose_transaction(
+    context.block_on(transactions_proposal::propose_transaction(
         &context,
+        &chain_id,
         &safe_address,
         &multisig_transaction_request?.0,
-    )
+    ))
+}
+
+#[post(
+    "/v1/chains/<chain_id>/safes/<safe_address>/transactions/prepare",
+    format = "application/json",
+    data = "<prepare_request>"
+)]
+pub fn prepare_transaction(
+    context: Context,
+    chain_id: String,
+    safe_address: String,
+    prepare_request: Result<Json<TransactionPrepareRequest>, JsonError>,
+) -> ApiResult<Json<PreparedTransaction>> {
+    context
+        .block_on(transactions_proposal::prepare_transaction(
+            &context,
+            &chain_id,
+            &safe_address,
+            &prepare_request?.0,
+        ))
+        .map(Json)
+}
+
+#[post(
+    "/v1/chains/<chain_id>/safes/<safe_address>/transactions/simulate",
+    format = "application/json",
+    data = "<simulation_request>"
+)]
+pub fn simulate_transaction(
+    context: Context,
+    chain_id: String,
+    safe_address: String,
+    simulation_request: Result<Json<TransactionPrepareRequest>, JsonError>,
+) -> ApiResult<Json<TransactionSimulation>> {
+    context
+        .block_on(transactions_simulation::simulate_transaction(
+            &context,
+            &chain_id,
+            &safe_address,
+            &simulation_request?.0,
+        ))
+        .map(Json)
+}
+
+#[post(
+    "/v1/chains/<chain_id>/safes/<safe_address>/transactions/estimate",
+    format = "application/json",
+    data = "<estimation_request>"
+)]
+pub fn estimate_transaction(
+    context: Context,
+    chain_id: String,
+    safe_address: String,
+    estimation_request: Result<Json<TransactionPrepareRequest>, JsonError>,
+) -> ApiResult<Json<TransactionEstimation>> {
+    context
+        .block_on(transactions_estimation::estimate_transaction(
+            &context,
+            &chain_id,
+            &safe_address,
+            &estimation_request?.0,
+        ))
+        .map(Json)
+}
+
+#[post(
+    "/v1/chains/<chain_id>/safes/<safe_address>/transactions/<nonce>/reject",
+    format = "application/json",
+    data = "<rejection_request>"
+)]
+pub fn propose_rejection(
+    context: Context,
+    chain_id: String,
+    safe_address: String,
+    nonce: u64,
+    rejection_request: Result<Json<RejectionRequest>, JsonError>,
+) -> ApiResult<()> {
+    context.block_on(transactions_proposal::propose_rejection(
+        &context,
+        &chain_id,
+        &safe_address,
+        nonce,
+        &rejection_request?.0,
+    ))
 }
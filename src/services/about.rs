@@ -6,9 +6,18 @@ use crate::utils::errors::ApiResult;
 
 pub fn get_about() -> ApiResult<About> {
     Ok(About {
-        transaction_service_base_url: base_transaction_service_url(),
+        chain_id: None,
+        transaction_service_base_url: None,
         name: env!("CARGO_PKG_NAME").to_string(),
         version: version(),
         build_number: build_number(),
     })
 }
+
+pub fn get_chain_about(chain_id: &str) -> ApiResult<About> {
+    Ok(About {
+        chain_id: Some(chain_id.to_string()),
+        transaction_service_base_url: Some(base_transaction_service_url(chain_id)?),
+        ..get_about()?
+    })
+}
@@ -0,0 +1,131 @@
+use crate::cache::cache_operations::RequestCached;
+use crate::config::{base_transaction_service_url, transaction_request_timeout};
+use crate::models::backend::transactions::Transaction;
+use crate::models::commons::Page;
+use crate::models::converters::allowances::to_allowances;
+use crate::models::service::allowances::{Allowance, Allowances};
+use crate::providers::info::DefaultInfoProvider;
+use crate::providers::rpc::RpcClient;
+use crate::utils::context::Context;
+use crate::utils::errors::ApiResult;
+use ethcontract_common::hash::keccak256;
+
+const ALLOWANCES_PAGE_SIZE: u64 = 100;
+// Approvals older than the latest `ALLOWANCES_PAGE_SIZE * ALLOWANCES_MAX_PAGES` executed
+// transactions are not listed, the response is then flagged as `truncated`
+const ALLOWANCES_MAX_PAGES: usize = 10;
+// Only the transactions calling these are decoded when the transaction service didn't
+const APPROVAL_SIGNATURES: &[&str] = &[
+    "approve(address,uint256)",
+    "setApprovalForAll(address,bool)",
+    "multiSend(bytes)",
+];
+
+pub async fn get_allowances(
+    context: &Context,
+    chain_id: &str,
+    safe_address: &str,
+) -> ApiResult<Allowances> {
+    let mut info_provider = DefaultInfoProvider::new(chain_id, context);
+    let (mut transactions, truncated) =
+        fetch_executed_transactions(context, chain_id, safe_address).await?;
+    for transaction in transactions.iter_mut() {
+        if may_approve(transaction) {
+            transaction.decode_missing_data(&mut info_provider).await;
+        }
+    }
+    let allowances = to_allowances(&transactions, &mut info_provider).await;
+    let results = match RpcClient::new(chain_id, context.client()) {
+        Ok(rpc_client) => with_remaining_amounts(&rpc_client, safe_address, allowances).await,
+        Err(_) => allowances,
+    };
+    Ok(Allowances { results, truncated })
+}
+
+// Approved amounts are consumed by `transferFrom`, the remaining ones are read from the token
+// contracts. Allowances that were used up, and operators whose approval was revoked outside of the
+// history of the safe, are left out.
+async fn with_remaining_amounts(
+    rpc_client: &RpcClient,
+    safe_address: &str,
+    allowances: Vec<Allowance>,
+) -> Vec<Allowance> {
+    let mut remaining = vec![];
+    for allowance in allowances {
+        remaining.extend(remaining_allowance(rpc_client, safe_address, allowance).await);
+    }
+    remaining
+}
+
+async fn remaining_allowance(
+    rpc_client: &RpcClient,
+    safe_address: &str,
+    mut allowance: Allowance,
+) -> Option<Allowance> {
+    if allowance.amount.is_none() {
+        return match rpc_client
+            .is_approved_for_all(&allowance.token_address, safe_address, &allowance.spender)
+            .await
+        {
+            Ok(Some(false)) => None,
+            _ => Some(allowance),
+        };
+    }
+    match rpc_client
+        .allowance(&allowance.token_address, safe_address, &allowance.spender)
+        .await
+    {
+        Ok(Some(remaining)) if remaining.is_zero() => None,
+        Ok(Some(remaining)) => {
+            allowance.amount = Some(remaining.to_string());
+            Some(allowance)
+        }
+        _ => Some(allowance),
+    }
+}
+
+fn may_approve(transaction: &Transaction) -> bool {
+    let data = match transaction {
+        Transaction::Multisig(transaction) => &transaction.data,
+        Transaction::Module(transaction) => &transaction.data,
+        _ => return false,
+    };
+    let data = match data.as_ref().map(|data| data.trim_start_matches("0x")) {
+        Some(data) if data.len() >= 8 => data[..8].to_lowercase(),
+        _ => return false,
+    };
+    APPROVAL_SIGNATURES
+        .iter()
+        .any(|signature| hex::encode(&keccak256(signature)[..4]) == data)
+}
+
+// An approval is in effect until it is overridden, so the executed history is needed up to
+// `ALLOWANCES_MAX_PAGES`. Also returns whether there were more transactions.
+async fn fetch_executed_transactions(
+    context: &Context,
+    chain_id: &str,
+    safe_address: &str,
+) -> ApiResult<(Vec<Transaction>, bool)> {
+    let mut transactions = vec![];
+    let mut next_url = Some(format!(
+        "{}/v1/safes/{}/all-transactions/?limit={}&queued=false&executed=true",
+        base_transaction_service_url(chain_id)?,
+        safe_address,
+        ALLOWANCES_PAGE_SIZE
+    ));
+    for _ in 0..ALLOWANCES_MAX_PAGES {
+        let url = match next_url {
+            Some(url) => url,
+            None => break,
+        };
+        let body = RequestCached::new(url)
+            .chain_id(chain_id)
+            .request_timeout(transaction_request_timeout())
+            .execute(context.client(), context.cache())
+            .await?;
+        let page = serde_json::from_str::<Page<Transaction>>(&body)?;
+        transactions.extend(page.results);
+        next_url = page.next;
+    }
+    Ok((transactions, next_url.is_some()))
+}
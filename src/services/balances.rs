@@ -9,36 +9,41 @@ use crate::utils::context::Context;
 use crate::utils::errors::ApiResult;
 use std::cmp::Ordering;
 
-pub fn balances(
+pub async fn balances(
     context: &Context,
+    chain_id: &str,
     safe_address: &str,
     fiat: &str,
     trusted: bool,
     exclude_spam: bool,
 ) -> ApiResult<Balances> {
+    let info_provider = DefaultInfoProvider::new(chain_id, &context);
+    let chain_config = info_provider.chain_config()?;
     let url = format!(
-        "{}/v1/safes/{}/balances/usd/?trusted={}&exclude_spam={}",
-        base_transaction_service_url(),
+        "{}/v1/safes/{}/balances/{}/?trusted={}&exclude_spam={}",
+        base_transaction_service_url(chain_id)?,
         safe_address,
+        chain_config.exchange_base_currency.to_lowercase(),
         trusted,
         exclude_spam
     );
 
     let body = RequestCached::new(url)
+        .chain_id(chain_id)
         .cache_duration(balances_cache_duration())
         .request_timeout(balances_request_timeout())
-        .execute(context.client(), context.cache())?;
+        .execute(context.client(), context.cache())
+        .await?;
     let backend_balances: Vec<BalanceDto> = serde_json::from_str(&body)?;
 
-    let info_provider = DefaultInfoProvider::new(&context);
-    let usd_to_fiat = info_provider.exchange_usd_to(fiat).unwrap_or(0.0);
+    let base_to_fiat = info_provider.exchange_rate(fiat).await.unwrap_or(0.0);
 
     let mut total_fiat = 0.0;
 
     let mut service_balances: Vec<Balance> = backend_balances
         .into_iter()
         .map(|it| {
-            let balance = it.to_balance(usd_to_fiat);
+            let balance = it.to_balance(base_to_fiat, &chain_config.native_currency);
             total_fiat += balance.fiat_balance.parse::<f64>().unwrap_or(0.0);
             balance
         })
@@ -57,9 +62,9 @@ pub fn balances(
     })
 }
 
-pub fn fiat_codes(context: &Context) -> ApiResult<Vec<String>> {
-    let info_provider = DefaultInfoProvider::new(&context);
-    let mut fiat_codes = info_provider.available_currency_codes()?;
+pub async fn fiat_codes(context: &Context, chain_id: &str) -> ApiResult<Vec<String>> {
+    let info_provider = DefaultInfoProvider::new(chain_id, &context);
+    let mut fiat_codes = info_provider.available_currency_codes().await?;
 
     let usd_index = fiat_codes.iter().position(|it| it.eq("USD")).unwrap();
     let eur_index = fiat_codes.iter().position(|it| it.eq("EUR")).unwrap();
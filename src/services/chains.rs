@@ -0,0 +1,16 @@
+use crate::config::chains::{chain_config, chains};
+use crate::models::commons::Page;
+use crate::models::service::chains::ChainInfo;
+use crate::utils::errors::ApiResult;
+
+pub fn get_chains() -> ApiResult<Page<ChainInfo>> {
+    Ok(Page {
+        next: None,
+        previous: None,
+        results: chains().to_owned(),
+    })
+}
+
+pub fn get_chain_info(chain_id: &str) -> ApiResult<ChainInfo> {
+    Ok(chain_config(chain_id)?.to_owned())
+}
@@ -0,0 +1,232 @@
+use crate::cache::Cache;
+use crate::models::backend::webhooks::Payload;
+use crate::models::service::events::SafeActivity;
+use crate::utils::errors::{ApiError, ApiResult};
+use crate::utils::hex_hash;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const SAFE_EVENTS_CHANNEL: &str = "safe_events";
+
+// Rocket 0.4 only writes full chunks of a streamed body and never flushes the connection, so a
+// response carries the next events of the safe and ends. `EventSource` reconnects after `retry`
+// milliseconds with the `Last-Event-ID` it received, the events published in between are kept
+// for `RECENT_EVENTS_DURATION` and sent right away.
+pub const EVENTS_WAIT_INTERVAL: Duration = Duration::from_secs(30);
+const RECONNECT_DELAY: u64 = 100;
+const RECENT_EVENTS_DURATION: Duration = Duration::from_secs(60);
+const MAX_RECENT_EVENTS: usize = 1000;
+
+// Message published on `SAFE_EVENTS_CHANNEL`, every instance receives the activity with the same id
+#[derive(Serialize, Deserialize)]
+struct PublishedActivity {
+    id: String,
+    activity: SafeActivity,
+}
+
+// Shares the activity published on `SAFE_EVENTS_CHANNEL` with the event subscriptions of this
+// instance
+#[derive(Clone)]
+pub struct SafeEventsBroadcaster {
+    listeners: Arc<Mutex<Listeners>>,
+    // Every subscription holds a worker thread while it waits
+    max_subscribers: usize,
+}
+
+#[derive(Default)]
+struct Listeners {
+    next_id: u64,
+    entries: Vec<Listener>,
+    // Events of every safe in the order they were received, oldest first
+    recent: VecDeque<RecentEvent>,
+}
+
+struct Listener {
+    id: u64,
+    chain_id: String,
+    safe: String,
+    sender: Sender<String>,
+}
+
+struct RecentEvent {
+    received: Instant,
+    id: String,
+    chain_id: String,
+    safe: String,
+    event: String,
+}
+
+impl SafeEventsBroadcaster {
+    pub fn new(max_subscribers: usize) -> Self {
+        SafeEventsBroadcaster {
+            listeners: Arc::new(Mutex::new(Listeners::default())),
+            max_subscribers,
+        }
+    }
+
+    // The events received after `last_event_id` are sent first, if it is still known
+    pub fn listen(
+        &self,
+        chain_id: &str,
+        safe: &str,
+        last_event_id: Option<&str>,
+    ) -> ApiResult<SafeEventSubscription> {
+        let mut listeners = self.listeners();
+        if listeners.entries.len() >= self.max_subscribers {
+            return Err(ApiError::new_from_message_with_code(
+                503,
+                String::from("Too many event subscribers, retry later"),
+            ));
+        }
+        let (sender, receiver) = channel();
+        let missed = last_event_id.and_then(|last_event_id| {
+            listeners
+                .recent
+                .iter()
+                .position(|recent| recent.id == last_event_id)
+        });
+        if let Some(position) = missed {
+            listeners
+                .recent
+                .iter()
+                .skip(position + 1)
+                .filter(|recent| recent.is_for(chain_id, safe))
+                .for_each(|recent| {
+                    let _ = sender.send(recent.event.to_string());
+                });
+        }
+        let id = listeners.next_id;
+        listeners.next_id += 1;
+        listeners.entries.push(Listener {
+            id,
+            chain_id: chain_id.to_string(),
+            safe: safe.to_string(),
+            sender,
+        });
+        Ok(SafeEventSubscription {
+            broadcaster: self.clone(),
+            id,
+            receiver,
+        })
+    }
+
+    pub fn broadcast(&self, message: &str) {
+        let published = match serde_json::from_str::<PublishedActivity>(message) {
+            Ok(published) => published,
+            Err(err) => {
+                log::error!("Invalid safe activity {}: {}", message, err);
+                return;
+            }
+        };
+        let activity = published.activity;
+        let chain_id = match activity.chain_id.as_ref() {
+            Some(chain_id) => chain_id,
+            None => return,
+        };
+        let data = match serde_json::to_string(&activity) {
+            Ok(data) => data,
+            Err(_) => return,
+        };
+        let event = format!(
+            "id: {}\nevent: {}\ndata: {}\n\n",
+            &published.id,
+            activity.activity.event_name(),
+            data
+        );
+        let recent = RecentEvent {
+            received: Instant::now(),
+            id: published.id,
+            chain_id: chain_id.to_string(),
+            safe: activity.address.to_string(),
+            event,
+        };
+        let mut listeners = self.listeners();
+        for listener in listeners.entries.iter() {
+            if recent.is_for(&listener.chain_id, &listener.safe) {
+                // The subscription removes its listener once the response is sent
+                let _ = listener.sender.send(recent.event.to_string());
+            }
+        }
+        listeners.recent.push_back(recent);
+        while listeners.recent.len() > MAX_RECENT_EVENTS
+            || listeners.recent.front().map_or(false, |oldest| {
+                oldest.received.elapsed() > RECENT_EVENTS_DURATION
+            })
+        {
+            listeners.recent.pop_front();
+        }
+    }
+
+    // Broadcasts the messages received on `receiver` until its sender is dropped
+    pub fn forward(&self, receiver: Receiver<String>) {
+        let broadcaster = self.clone();
+        thread::spawn(move || {
+            for message in receiver {
+                broadcaster.broadcast(&message);
+            }
+        });
+    }
+
+    fn listeners(&self) -> std::sync::MutexGuard<Listeners> {
+        self.listeners
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+pub async fn publish_safe_activity(cache: &dyn Cache, payload: &Payload) -> ApiResult<()> {
+    if let Some(activity) = payload.to_safe_activity() {
+        let published = PublishedActivity {
+            id: hex_hash(&(payload, Utc::now().timestamp_nanos())),
+            activity,
+        };
+        cache
+            .publish(SAFE_EVENTS_CHANNEL, &serde_json::to_string(&published)?)
+            .await;
+    }
+    Ok(())
+}
+
+impl RecentEvent {
+    fn is_for(&self, chain_id: &str, safe: &str) -> bool {
+        self.chain_id == chain_id && self.safe.eq_ignore_ascii_case(safe)
+    }
+}
+
+// Listens to the activity of a safe until dropped
+pub struct SafeEventSubscription {
+    broadcaster: SafeEventsBroadcaster,
+    id: u64,
+    receiver: Receiver<String>,
+}
+
+impl SafeEventSubscription {
+    // `text/event-stream` body with the events received within `timeout`, a comment if there are
+    // none so that clients reconnect before proxies close idle connections
+    pub fn next_events(&self, timeout: Duration) -> String {
+        let mut body = format!("retry: {}\n\n", RECONNECT_DELAY);
+        match self.receiver.recv_timeout(timeout) {
+            Ok(event) => {
+                body.push_str(&event);
+                body.extend(self.receiver.try_iter());
+            }
+            Err(_) => body.push_str(":\n\n"),
+        }
+        body
+    }
+}
+
+impl Drop for SafeEventSubscription {
+    fn drop(&mut self) {
+        let id = self.id;
+        self.broadcaster
+            .listeners()
+            .entries
+            .retain(|listener| listener.id != id);
+    }
+}
@@ -1,33 +1,32 @@
 use crate::cache::cache_operations::{Invalidate, InvalidationPattern};
 use crate::cache::Cache;
 use crate::models::backend::webhooks::{Payload, PayloadDetails};
+use crate::services::owners::safe_owners_listed;
 use crate::utils::errors::ApiResult;
 
-pub fn invalidate_caches(cache: &impl Cache, payload: &Payload) -> ApiResult<()> {
+pub async fn invalidate_caches(cache: &dyn Cache, payload: &Payload) -> ApiResult<()> {
     Invalidate::new(InvalidationPattern::RequestsResponses(
         payload.address.to_owned(),
     ))
-    .execute(cache);
-    payload.details.as_ref().map(|d| match d {
-        PayloadDetails::NewConfirmation(data) => {
-            Invalidate::new(InvalidationPattern::RequestsResponses(String::from(
-                &data.safe_tx_hash,
-            )))
-            .execute(cache);
-        }
-        PayloadDetails::ExecutedMultisigTransaction(data) => {
-            Invalidate::new(InvalidationPattern::RequestsResponses(String::from(
-                &data.safe_tx_hash,
-            )))
-            .execute(cache);
-        }
-        PayloadDetails::PendingMultisigTransaction(data) => {
-            Invalidate::new(InvalidationPattern::RequestsResponses(String::from(
-                &data.safe_tx_hash,
-            )))
-            .execute(cache);
-        }
-        _ => {}
-    });
+    .execute(cache)
+    .await;
+    for owner in safe_owners_listed(cache, &payload.address).await {
+        Invalidate::new(InvalidationPattern::RequestsResponses(owner))
+            .execute(cache)
+            .await;
+    }
+    let safe_tx_hash = match &payload.details {
+        Some(PayloadDetails::NewConfirmation(data)) => Some(&data.safe_tx_hash),
+        Some(PayloadDetails::ExecutedMultisigTransaction(data)) => Some(&data.safe_tx_hash),
+        Some(PayloadDetails::PendingMultisigTransaction(data)) => Some(&data.safe_tx_hash),
+        _ => None,
+    };
+    if let Some(safe_tx_hash) = safe_tx_hash {
+        Invalidate::new(InvalidationPattern::RequestsResponses(String::from(
+            safe_tx_hash,
+        )))
+        .execute(cache)
+        .await;
+    }
     Ok(())
 }
@@ -2,14 +2,22 @@ use crate::models::commons::PageMetadata;
 use std::cmp::max;
 
 pub mod about;
+pub mod allowances;
 pub mod balances;
+pub mod chains;
+pub mod events;
 pub mod hooks;
+pub mod notifications;
+pub mod owners;
 pub mod safes;
 pub mod transactions_details;
+pub mod transactions_estimation;
 pub mod transactions_history;
 pub mod transactions_list;
 pub mod transactions_proposal;
 pub mod transactions_queued;
+pub mod transactions_simulation;
+pub mod webhooks;
 
 #[cfg(test)]
 mod tests;
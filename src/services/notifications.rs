@@ -0,0 +1,117 @@
+use crate::cache::Cache;
+use crate::models::backend::webhooks::{Payload, PayloadDetails};
+use crate::models::service::notifications::{
+    DeviceRegistration, NotificationType, PushNotification,
+};
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::providers::push::PushProvider;
+use crate::services::transactions_details::get_multisig_transaction;
+use crate::utils::context::Context;
+use crate::utils::errors::ApiResult;
+
+// Device tokens are only kept in redis (see `Context::persistent_cache`), they must neither be
+// evicted nor flushed
+const DEVICES_KEY: &'static str = "nt_devices";
+
+pub async fn register_device(
+    cache: &dyn Cache,
+    owner: &str,
+    registration: &DeviceRegistration,
+) -> ApiResult<()> {
+    if registration.device_token.trim().is_empty() {
+        return Err(client_error!(422, "Device token is required"));
+    }
+    cache
+        .insert_in_hash(
+            &devices_key(owner),
+            &registration.device_token,
+            &registration.device_token,
+        )
+        .await;
+    Ok(())
+}
+
+pub async fn unregister_device(
+    cache: &dyn Cache,
+    owner: &str,
+    device_token: &str,
+) -> ApiResult<()> {
+    cache
+        .remove_from_hash(&devices_key(owner), device_token)
+        .await;
+    Ok(())
+}
+
+// Asks the owners that did not confirm a proposed or confirmed transaction yet for their signature
+pub async fn notify_missing_signers(
+    context: &Context,
+    push_provider: &dyn PushProvider,
+    payload: &Payload,
+) -> ApiResult<()> {
+    let notification = match confirmation_request(payload) {
+        Some(notification) => notification,
+        None => return Ok(()),
+    };
+    let devices_cache = context.persistent_cache()?;
+    let mut info_provider = DefaultInfoProvider::new(&notification.chain_id, context);
+    let safe_info = info_provider.safe_info(&payload.address).await?;
+    let multisig_tx =
+        get_multisig_transaction(context, &notification.chain_id, &notification.safe_tx_hash)
+            .await?;
+
+    send_notification(
+        devices_cache.as_ref(),
+        context.client(),
+        push_provider,
+        &multisig_tx.awaiting_signers(&safe_info),
+        &notification,
+    )
+    .await
+}
+
+// Payloads without chain (e.g. of `/v1/hook/update/<token>`) can't be notified
+pub fn confirmation_request(payload: &Payload) -> Option<PushNotification> {
+    let safe_tx_hash = match &payload.details {
+        Some(PayloadDetails::PendingMultisigTransaction(data)) => &data.safe_tx_hash,
+        Some(PayloadDetails::NewConfirmation(data)) => &data.safe_tx_hash,
+        _ => return None,
+    };
+    let chain_id = match &payload.chain_id {
+        Some(chain_id) => chain_id,
+        None => {
+            log::debug!("No chain id for {}, owners are not notified", safe_tx_hash);
+            return None;
+        }
+    };
+    Some(PushNotification {
+        notification_type: NotificationType::ConfirmationRequest,
+        chain_id: chain_id.to_string(),
+        address: payload.address.to_string(),
+        safe_tx_hash: safe_tx_hash.to_string(),
+    })
+}
+
+pub async fn send_notification(
+    cache: &dyn Cache,
+    client: &reqwest::Client,
+    push_provider: &dyn PushProvider,
+    owners: &[String],
+    notification: &PushNotification,
+) -> ApiResult<()> {
+    let mut device_tokens: Vec<String> = vec![];
+    for owner in owners {
+        let devices = cache.get_all_from_hash(&devices_key(owner)).await;
+        device_tokens.extend(devices.into_iter().map(|(device_token, _)| device_token));
+    }
+    if device_tokens.is_empty() {
+        return Ok(());
+    }
+    push_provider
+        .send(client, &device_tokens, notification)
+        .await
+}
+
+// Owners are stored case insensitive, as registrations do not have to use the checksummed address
+fn devices_key(owner: &str) -> String {
+    format!("{}_{}", DEVICES_KEY, owner.to_lowercase())
+}
@@ -0,0 +1,171 @@
+use crate::cache::cache_operations::RequestCached;
+use crate::cache::Cache;
+use crate::config::chains::chains;
+use crate::config::{base_transaction_service_url, owner_safes_cache_duration};
+use crate::models::backend::owners::OwnerSafes as OwnerSafesDto;
+use crate::models::backend::transactions::MultisigTransaction;
+use crate::models::commons::{Page, PageMetadata};
+use crate::models::service::owners::OwnerSafes;
+use crate::providers::info::{safe_info_request, SafeInfo};
+use crate::services::transactions_queued::queued_transactions_request;
+use crate::utils::context::Context;
+use crate::utils::data_decoder::checksum_address;
+use crate::utils::errors::ApiResult;
+use ethereum_types::Address;
+use serde::de::DeserializeOwned;
+
+// Webhooks only contain the safe, the owners that listed it are kept to invalidate their responses
+const OWNER_SAFES_KEY: &str = "c_owner_safes";
+// Only the first page of the queue is checked for missing confirmations
+const QUEUED_TRANSACTIONS_LIMIT: u64 = 100;
+
+// The safes of every chain are requested concurrently. A chain that can't be (completely) loaded
+// is reported in `failed_chains` instead of failing the whole response.
+pub async fn get_owner_safes(context: &Context, owner_address: &str) -> ApiResult<OwnerSafes> {
+    let mut failed_chains = vec![];
+    let chain_ids = chains()
+        .iter()
+        .map(|chain| (chain.chain_id.to_owned(), ()))
+        .collect();
+    let safes = fetch_all::<_, OwnerSafesDto>(
+        context,
+        chain_ids,
+        |chain_id, _| owner_safes_request(chain_id, owner_address),
+        &mut failed_chains,
+    )
+    .await
+    .into_iter()
+    .flat_map(|(chain_id, _, owner_safes)| {
+        owner_safes
+            .safes
+            .into_iter()
+            .map(move |safe| (chain_id.to_owned(), safe))
+    })
+    .collect();
+    let safe_infos = fetch_all::<_, SafeInfo>(
+        context,
+        safes,
+        |chain_id, safe| safe_info_request(chain_id, safe),
+        &mut failed_chains,
+    )
+    .await
+    .into_iter()
+    .map(|(chain_id, _, safe_info)| (chain_id, safe_info))
+    .collect();
+    let queues = fetch_all::<_, Page<MultisigTransaction>>(
+        context,
+        safe_infos,
+        |chain_id, safe_info| {
+            queued_transactions_request(
+                chain_id,
+                &safe_info.address,
+                safe_info.nonce as i64,
+                &PageMetadata {
+                    offset: 0,
+                    limit: QUEUED_TRANSACTIONS_LIMIT,
+                },
+                true,
+            )
+        },
+        &mut failed_chains,
+    )
+    .await;
+
+    let mut safes = vec![];
+    for (chain_id, safe_info, queued_transactions) in queues {
+        register_owner(context.cache(), &safe_info.address, owner_address).await;
+        safes.push(safe_info.to_owner_safe(&chain_id, owner_address, &queued_transactions.results));
+    }
+    Ok(OwnerSafes {
+        safes,
+        failed_chains,
+    })
+}
+
+// Owner addresses are checksummed, so that the responses are invalidated whatever the case of the
+// requested address
+pub fn checksum_owner_address(owner_address: &str) -> ApiResult<String> {
+    let address: Address =
+        serde_json::from_value(serde_json::Value::String(owner_address.to_string()))
+            .map_err(|_| client_error!(422, "Invalid owner address"))?;
+    Ok(checksum_address(address.as_bytes()))
+}
+
+// Owners whose safes response includes `safe_address`
+pub async fn safe_owners_listed(cache: &dyn Cache, safe_address: &str) -> Vec<String> {
+    cache
+        .get_all_from_hash(&owners_key(safe_address))
+        .await
+        .into_iter()
+        .map(|(owner, _)| owner)
+        .collect()
+}
+
+// Sends the request of every item concurrently, items whose request fails are dropped and their
+// chain is added to `failed_chains`
+async fn fetch_all<T, R>(
+    context: &Context,
+    items: Vec<(String, T)>,
+    request: impl Fn(&str, &T) -> ApiResult<RequestCached>,
+    failed_chains: &mut Vec<String>,
+) -> Vec<(String, T, R)>
+where
+    R: DeserializeOwned,
+{
+    let mut requested = vec![];
+    let mut requests = vec![];
+    for (chain_id, item) in items {
+        match request(&chain_id, &item) {
+            Ok(request) => {
+                requests.push(request);
+                requested.push((chain_id, item));
+            }
+            Err(_) => add_failed_chain(failed_chains, chain_id),
+        }
+    }
+    let responses = RequestCached::execute_all(&requests, context.client(), context.cache()).await;
+    requested
+        .into_iter()
+        .zip(responses)
+        .filter_map(|((chain_id, item), response)| {
+            match response.and_then(|body| Ok(serde_json::from_str::<R>(&body)?)) {
+                Ok(value) => Some((chain_id, item, value)),
+                Err(_) => {
+                    add_failed_chain(failed_chains, chain_id);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+fn add_failed_chain(failed_chains: &mut Vec<String>, chain_id: String) {
+    if !failed_chains.contains(&chain_id) {
+        failed_chains.push(chain_id);
+    }
+}
+
+fn owner_safes_request(chain_id: &str, owner_address: &str) -> ApiResult<RequestCached> {
+    let url = format!(
+        "{}/v1/owners/{}/safes/",
+        base_transaction_service_url(chain_id)?,
+        owner_address
+    );
+    let mut request = RequestCached::new(url);
+    request
+        .chain_id(chain_id)
+        .cache_duration(owner_safes_cache_duration());
+    Ok(request)
+}
+
+async fn register_owner(cache: &dyn Cache, safe_address: &str, owner_address: &str) {
+    let key = owners_key(safe_address);
+    cache.insert_in_hash(&key, owner_address, "").await;
+    cache
+        .expire_entity(&key, owner_safes_cache_duration())
+        .await;
+}
+
+fn owners_key(safe_address: &str) -> String {
+    format!("{}_{}", OWNER_SAFES_KEY, safe_address)
+}
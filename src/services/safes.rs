@@ -1,11 +1,28 @@
-use crate::models::service::safes::SafeInfoEx;
+use crate::models::service::safes::{SafeInfoEx, SafeUpgrade};
 use crate::providers::info::{DefaultInfoProvider, InfoProvider};
 use crate::utils::context::Context;
 use crate::utils::errors::ApiResult;
 
-pub fn get_safe_info_ex(context: &Context, safe_address: &String) -> ApiResult<SafeInfoEx> {
-    let mut info_provider = DefaultInfoProvider::new(context);
-    let safe_info = info_provider.safe_info(safe_address)?;
+pub async fn get_safe_info_ex(
+    context: &Context,
+    chain_id: &str,
+    safe_address: &String,
+) -> ApiResult<SafeInfoEx> {
+    let mut info_provider = DefaultInfoProvider::new(chain_id, context);
+    let safe_info = info_provider.safe_info(safe_address).await?;
 
-    Ok(safe_info.to_safe_info_ex(&mut info_provider))
+    Ok(safe_info.to_safe_info_ex(&mut info_provider).await)
+}
+
+pub async fn get_safe_upgrade(
+    context: &Context,
+    chain_id: &str,
+    safe_address: &str,
+) -> ApiResult<SafeUpgrade> {
+    let mut info_provider = DefaultInfoProvider::new(chain_id, context);
+    let eip155_deployments = info_provider.chain_config()?.eip155_deployments;
+    info_provider
+        .safe_info(safe_address)
+        .await?
+        .to_safe_upgrade(eip155_deployments)
 }
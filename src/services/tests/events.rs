@@ -0,0 +1,166 @@
+use crate::cache::memory::InMemoryCache;
+use crate::models::backend::webhooks::{Payload, PayloadDetails, PendingMultisigTransaction};
+use crate::services::events::*;
+use std::time::Duration;
+
+const CHAIN_ID: &str = "4";
+const SAFE: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const SAFE_TX_HASH: &str = "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621";
+
+fn pending_transaction_payload(chain_id: Option<&str>, safe: &str) -> Payload {
+    Payload {
+        address: safe.to_string(),
+        chain_id: chain_id.map(String::from),
+        details: Some(PayloadDetails::PendingMultisigTransaction(
+            PendingMultisigTransaction {
+                safe_tx_hash: SAFE_TX_HASH.to_string(),
+            },
+        )),
+    }
+}
+
+fn expected_event() -> String {
+    format!(
+        "retry: 100\n\nevent: NEW_PROPOSAL\ndata: {{\"address\":\"{}\",\"chainId\":\"{}\",\"type\":\"NEW_PROPOSAL\",\"safeTxHash\":\"{}\"}}\n\n",
+        SAFE, CHAIN_ID, SAFE_TX_HASH
+    )
+}
+
+// Event ids are generated when the activity is published
+fn event_ids(body: &str) -> Vec<String> {
+    body.lines()
+        .filter_map(|line| line.strip_prefix("id: "))
+        .map(String::from)
+        .collect()
+}
+
+fn without_event_ids(body: &str) -> String {
+    body.lines()
+        .filter(|line| !line.starts_with("id: "))
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+#[tokio::test]
+async fn published_activity_is_sent_to_safe_listeners() {
+    let cache = InMemoryCache::new(10);
+    let broadcaster = SafeEventsBroadcaster::new(10);
+    broadcaster.forward(cache.subscribe(SAFE_EVENTS_CHANNEL));
+    let subscription = broadcaster
+        .listen(CHAIN_ID, &SAFE.to_lowercase(), None)
+        .unwrap();
+
+    publish_safe_activity(&cache, &pending_transaction_payload(Some(CHAIN_ID), SAFE))
+        .await
+        .unwrap();
+    let actual = subscription.next_events(Duration::from_secs(5));
+
+    assert_eq!(event_ids(&actual).len(), 1);
+    assert_eq!(without_event_ids(&actual), expected_event());
+}
+
+#[tokio::test]
+async fn activity_of_other_safes_and_chains_is_not_sent() {
+    let cache = InMemoryCache::new(10);
+    let broadcaster = SafeEventsBroadcaster::new(10);
+    broadcaster.forward(cache.subscribe(SAFE_EVENTS_CHANNEL));
+    let subscription = broadcaster.listen(CHAIN_ID, SAFE, None).unwrap();
+
+    publish_safe_activity(
+        &cache,
+        &pending_transaction_payload(Some(CHAIN_ID), "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23"),
+    )
+    .await
+    .unwrap();
+    publish_safe_activity(&cache, &pending_transaction_payload(Some("1"), SAFE))
+        .await
+        .unwrap();
+    publish_safe_activity(&cache, &pending_transaction_payload(None, SAFE))
+        .await
+        .unwrap();
+    publish_safe_activity(&cache, &pending_transaction_payload(Some(CHAIN_ID), SAFE))
+        .await
+        .unwrap();
+    let actual = subscription.next_events(Duration::from_secs(5));
+
+    assert_eq!(without_event_ids(&actual), expected_event());
+}
+
+#[test]
+fn heartbeat_is_sent_without_activity() {
+    let broadcaster = SafeEventsBroadcaster::new(10);
+    let subscription = broadcaster.listen(CHAIN_ID, SAFE, None).unwrap();
+
+    let actual = subscription.next_events(Duration::from_millis(10));
+
+    assert_eq!(actual, "retry: 100\n\n:\n\n");
+}
+
+#[test]
+fn listen_fails_when_max_subscribers_reached() {
+    let broadcaster = SafeEventsBroadcaster::new(1);
+    let subscription = broadcaster.listen(CHAIN_ID, SAFE, None).unwrap();
+
+    let error = broadcaster.listen(CHAIN_ID, SAFE, None).err().unwrap();
+    assert_eq!(error.status, 503);
+
+    drop(subscription);
+    assert!(broadcaster.listen(CHAIN_ID, SAFE, None).is_ok());
+}
+
+#[tokio::test]
+async fn activity_of_the_chain_hook_route_is_sent() {
+    let cache = InMemoryCache::new(10);
+    let broadcaster = SafeEventsBroadcaster::new(10);
+    broadcaster.forward(cache.subscribe(SAFE_EVENTS_CHANNEL));
+    let subscription = broadcaster.listen(CHAIN_ID, SAFE, None).unwrap();
+    let payload = pending_transaction_payload(None, SAFE).with_chain_id(CHAIN_ID.to_string());
+
+    publish_safe_activity(&cache, &payload).await.unwrap();
+    let actual = subscription.next_events(Duration::from_secs(5));
+
+    assert_eq!(without_event_ids(&actual), expected_event());
+}
+
+#[tokio::test]
+async fn activity_published_while_reconnecting_is_sent() {
+    let cache = InMemoryCache::new(10);
+    let published = cache.subscribe(SAFE_EVENTS_CHANNEL);
+    let broadcaster = SafeEventsBroadcaster::new(10);
+    let subscription = broadcaster.listen(CHAIN_ID, SAFE, None).unwrap();
+    publish_safe_activity(&cache, &pending_transaction_payload(Some(CHAIN_ID), SAFE))
+        .await
+        .unwrap();
+    broadcaster.broadcast(&published.recv().unwrap());
+    let last_event_id = event_ids(&subscription.next_events(Duration::from_secs(5))).remove(0);
+    drop(subscription);
+
+    publish_safe_activity(&cache, &pending_transaction_payload(Some(CHAIN_ID), SAFE))
+        .await
+        .unwrap();
+    broadcaster.broadcast(&published.recv().unwrap());
+    let subscription = broadcaster
+        .listen(CHAIN_ID, SAFE, Some(&last_event_id))
+        .unwrap();
+    let actual = subscription.next_events(Duration::from_millis(10));
+
+    assert_eq!(event_ids(&actual).len(), 1);
+    assert_ne!(event_ids(&actual)[0], last_event_id);
+    assert_eq!(without_event_ids(&actual), expected_event());
+}
+
+#[tokio::test]
+async fn unknown_last_event_id_only_waits_for_new_activity() {
+    let cache = InMemoryCache::new(10);
+    let published = cache.subscribe(SAFE_EVENTS_CHANNEL);
+    let broadcaster = SafeEventsBroadcaster::new(10);
+    publish_safe_activity(&cache, &pending_transaction_payload(Some(CHAIN_ID), SAFE))
+        .await
+        .unwrap();
+    broadcaster.broadcast(&published.recv().unwrap());
+
+    let subscription = broadcaster.listen(CHAIN_ID, SAFE, Some("0x1234")).unwrap();
+    let actual = subscription.next_events(Duration::from_millis(10));
+
+    assert_eq!(actual, "retry: 100\n\n:\n\n");
+}
@@ -6,15 +6,24 @@ use crate::models::backend::webhooks::{
 use crate::services::hooks::invalidate_caches;
 use mockall::predicate::*;
 use mockall::Sequence;
+use std::collections::HashMap;
 
-#[test]
-fn invalidate_with_empty_payload() {
+#[tokio::test]
+async fn invalidate_with_empty_payload() {
     let payload = Payload {
         address: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        chain_id: None,
         details: None,
     };
 
     let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_get_all_from_hash()
+        .times(1)
+        .with(eq(
+            "c_owner_safes_0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+        ))
+        .returning(|_| HashMap::new());
 
     mock_cache.expect_fetch().times(0);
     mock_cache.expect_create().times(0);
@@ -26,13 +35,14 @@ fn invalidate_with_empty_payload() {
         .return_const(())
         .with(eq("c_re*0x1230B3d59858296A31053C1b8562Ecf89A2f888b*"));
 
-    invalidate_caches(&mock_cache, &payload).unwrap();
+    invalidate_caches(&mock_cache, &payload).await.unwrap();
 }
 
-#[test]
-fn invalidate_new_confirmation_payload() {
+#[tokio::test]
+async fn invalidate_new_confirmation_payload() {
     let payload = Payload {
         address: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        chain_id: None,
         details: Some(PayloadDetails::NewConfirmation(NewConfirmation {
             owner: "0x65F8236309e5A99Ff0d129d04E486EBCE20DC7B0".to_string(),
             safe_tx_hash: "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621"
@@ -41,6 +51,13 @@ fn invalidate_new_confirmation_payload() {
     };
 
     let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_get_all_from_hash()
+        .times(1)
+        .with(eq(
+            "c_owner_safes_0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+        ))
+        .returning(|_| HashMap::new());
     let mut sequence = Sequence::new();
 
     mock_cache.expect_fetch().times(0);
@@ -61,13 +78,14 @@ fn invalidate_new_confirmation_payload() {
         ))
         .in_sequence(&mut sequence);
 
-    invalidate_caches(&mock_cache, &payload).unwrap();
+    invalidate_caches(&mock_cache, &payload).await.unwrap();
 }
 
-#[test]
-fn invalidate_executed_multisig_transaction_payload() {
+#[tokio::test]
+async fn invalidate_executed_multisig_transaction_payload() {
     let payload = Payload {
         address: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        chain_id: None,
         details: Some(PayloadDetails::ExecutedMultisigTransaction(
             ExecutedMultisigTransaction {
                 safe_tx_hash: "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621"
@@ -79,6 +97,13 @@ fn invalidate_executed_multisig_transaction_payload() {
     };
 
     let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_get_all_from_hash()
+        .times(1)
+        .with(eq(
+            "c_owner_safes_0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+        ))
+        .returning(|_| HashMap::new());
     let mut sequence = Sequence::new();
 
     mock_cache.expect_fetch().times(0);
@@ -99,13 +124,14 @@ fn invalidate_executed_multisig_transaction_payload() {
         ))
         .in_sequence(&mut sequence);
 
-    invalidate_caches(&mock_cache, &payload).unwrap();
+    invalidate_caches(&mock_cache, &payload).await.unwrap();
 }
 
-#[test]
-fn invalidate_pending_multisig_transaction_payload() {
+#[tokio::test]
+async fn invalidate_pending_multisig_transaction_payload() {
     let payload = Payload {
         address: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        chain_id: None,
         details: Some(PayloadDetails::PendingMultisigTransaction(
             PendingMultisigTransaction {
                 safe_tx_hash: "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621"
@@ -115,6 +141,13 @@ fn invalidate_pending_multisig_transaction_payload() {
     };
 
     let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_get_all_from_hash()
+        .times(1)
+        .with(eq(
+            "c_owner_safes_0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+        ))
+        .returning(|_| HashMap::new());
     let mut sequence = Sequence::new();
     mock_cache.expect_fetch().times(0);
     mock_cache.expect_create().times(0);
@@ -134,5 +167,46 @@ fn invalidate_pending_multisig_transaction_payload() {
         ))
         .in_sequence(&mut sequence);
 
-    invalidate_caches(&mut mock_cache, &payload).unwrap();
+    invalidate_caches(&mut mock_cache, &payload).await.unwrap();
+}
+
+#[tokio::test]
+async fn invalidate_owners_listing_the_safe() {
+    let payload = Payload {
+        address: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        chain_id: None,
+        details: None,
+    };
+
+    let mut mock_cache = MockCache::new();
+    let mut sequence = Sequence::new();
+
+    mock_cache
+        .expect_get_all_from_hash()
+        .times(1)
+        .with(eq(
+            "c_owner_safes_0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+        ))
+        .returning(|_| {
+            let mut owners = HashMap::new();
+            owners.insert(
+                "0x65F8236309e5A99Ff0d129d04E486EBCE20DC7B0".to_string(),
+                "".to_string(),
+            );
+            owners
+        });
+    mock_cache
+        .expect_invalidate_pattern()
+        .times(1)
+        .return_const(())
+        .with(eq("c_re*0x1230B3d59858296A31053C1b8562Ecf89A2f888b*"))
+        .in_sequence(&mut sequence);
+    mock_cache
+        .expect_invalidate_pattern()
+        .times(1)
+        .return_const(())
+        .with(eq("c_re*0x65F8236309e5A99Ff0d129d04E486EBCE20DC7B0*"))
+        .in_sequence(&mut sequence);
+
+    invalidate_caches(&mock_cache, &payload).await.unwrap();
 }
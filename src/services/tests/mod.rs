@@ -1,4 +1,9 @@
+mod events;
 mod invalidate_caches;
+mod notifications;
+mod owners;
 mod parse_id;
 mod transactions_history;
+mod transactions_proposal;
 mod transactions_queued;
+mod webhooks;
@@ -0,0 +1,122 @@
+use crate::cache::memory::InMemoryCache;
+use crate::cache::Cache;
+use crate::models::backend::webhooks::{Payload, PayloadDetails, PendingMultisigTransaction};
+use crate::models::service::notifications::{
+    DeviceRegistration, NotificationType, PushNotification,
+};
+use crate::providers::push::MockPushProvider;
+use crate::services::notifications::*;
+use mockall::predicate::{always, eq, function};
+
+const OWNER: &str = "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23";
+
+fn notification() -> PushNotification {
+    PushNotification {
+        notification_type: NotificationType::ConfirmationRequest,
+        chain_id: "4".to_string(),
+        address: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        safe_tx_hash: "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621"
+            .to_string(),
+    }
+}
+
+async fn register(cache: &dyn Cache, owner: &str, device_token: &str) {
+    register_device(
+        cache,
+        owner,
+        &DeviceRegistration {
+            device_token: device_token.to_string(),
+        },
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn register_device_without_token() {
+    let cache = InMemoryCache::new(10);
+
+    let actual = register_device(
+        &cache,
+        OWNER,
+        &DeviceRegistration {
+            device_token: " ".to_string(),
+        },
+    )
+    .await;
+
+    assert_eq!(actual.expect_err("").status, 422);
+}
+
+#[tokio::test]
+async fn send_notification_to_registered_devices() {
+    let cache = InMemoryCache::new(10);
+    let client = reqwest::Client::new();
+    register(&cache, &OWNER.to_lowercase(), "device").await;
+    register(
+        &cache,
+        "0x37e9F140A9Df5DCBc783C6c220660a4E15CBFe72",
+        "other_device",
+    )
+    .await;
+    let mut push_provider = MockPushProvider::new();
+    push_provider
+        .expect_send()
+        .with(
+            always(),
+            function(|device_tokens: &[String]| device_tokens == ["device".to_string()]),
+            eq(notification()),
+        )
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+
+    send_notification(
+        &cache,
+        &client,
+        &push_provider,
+        &[OWNER.to_string()],
+        &notification(),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn send_notification_without_devices() {
+    let cache = InMemoryCache::new(10);
+    let client = reqwest::Client::new();
+    register(&cache, OWNER, "device").await;
+    unregister_device(&cache, OWNER, "device").await.unwrap();
+    let mut push_provider = MockPushProvider::new();
+    push_provider.expect_send().times(0);
+
+    send_notification(
+        &cache,
+        &client,
+        &push_provider,
+        &[OWNER.to_string()],
+        &notification(),
+    )
+    .await
+    .unwrap();
+}
+
+#[test]
+fn confirmation_request_of_the_chain_hook_route() {
+    let payload = Payload {
+        address: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        chain_id: None,
+        details: Some(PayloadDetails::PendingMultisigTransaction(
+            PendingMultisigTransaction {
+                safe_tx_hash: "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621"
+                    .to_string(),
+            },
+        )),
+    };
+
+    assert_eq!(confirmation_request(&payload), None);
+    assert_eq!(
+        confirmation_request(&payload.with_chain_id("4".to_string())),
+        Some(notification())
+    );
+}
@@ -0,0 +1,15 @@
+use crate::services::owners::checksum_owner_address;
+
+#[test]
+fn checksum_owner_address_lowercase() {
+    let actual = checksum_owner_address("0xbea2f9227230976d2813a2f8b922c22be1de1b23").unwrap();
+
+    assert_eq!(actual, "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23");
+}
+
+#[test]
+fn checksum_owner_address_invalid() {
+    let error = checksum_owner_address("0xbea2f9227230976d2813a2f8b922c22be1de1b").unwrap_err();
+
+    assert_eq!(error.status, 422);
+}
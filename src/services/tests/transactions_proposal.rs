@@ -0,0 +1,117 @@
+use crate::models::commons::Operation;
+use crate::models::service::transactions::requests::{
+    MultisigTransactionRequest, RejectionRequest,
+};
+use crate::providers::info::SafeInfo;
+use crate::services::transactions_proposal::{to_rejection_request, verify_safe_tx_hash};
+use crate::utils::transactions::INVALID_SAFE_TX_HASH_CODE;
+
+fn safe_info(version: &str) -> SafeInfo {
+    SafeInfo {
+        address: "0xd6f5Bef6bb4acD235CF85c0ce196316d10785d67".to_string(),
+        nonce: 7,
+        threshold: 1,
+        owners: vec!["0x2c7536E3605D9C16a7a3D7b1898e529396a65c23".to_string()],
+        master_copy: "0x3E5c63644E683549055b9Be8653de26E0B4CD36E".to_string(),
+        modules: None,
+        fallback_handler: None,
+        version: Some(version.to_string()),
+    }
+}
+
+fn transaction_request(safe_tx_hash: &str) -> MultisigTransactionRequest {
+    MultisigTransactionRequest {
+        to: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
+        value: "1000000000000000".to_string(),
+        data: Some("0xa9059cbb000000000000000000000000f2565317f3ae8ae9ea98e9fe1e7fadc77f823cbd0000000000000000000000000000000000000000000000000de0b6b3a7640000".to_string()),
+        nonce: "7".to_string(),
+        operation: Operation::CALL,
+        safe_tx_gas: "50000".to_string(),
+        base_gas: "0".to_string(),
+        gas_price: "0".to_string(),
+        gas_token: "0x0000000000000000000000000000000000000000".to_string(),
+        refund_receiver: None,
+        safe_tx_hash: safe_tx_hash.to_string(),
+        sender: "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23".to_string(),
+        signature: "0x".to_string(),
+        origin: None,
+    }
+}
+
+#[test]
+fn verify_safe_tx_hash_matching() {
+    let request =
+        transaction_request("0xAF329B6B1718E881E8C0856B17191E58485AAAAC96EB23EE084BE175ABE581DF");
+
+    let actual = verify_safe_tx_hash("4", &safe_info("1.3.0"), &request);
+
+    assert!(actual.is_ok());
+}
+
+#[test]
+fn verify_safe_tx_hash_of_other_chain() {
+    let request =
+        transaction_request("0xaf329b6b1718e881e8c0856b17191e58485aaaac96eb23ee084be175abe581df");
+
+    let actual = verify_safe_tx_hash("1", &safe_info("1.3.0"), &request).expect_err("");
+
+    assert_eq!(actual.status, 422);
+    assert_eq!(actual.details.code, INVALID_SAFE_TX_HASH_CODE);
+}
+
+#[test]
+fn verify_safe_tx_hash_legacy_domain() {
+    let request =
+        transaction_request("0x7add2c73a0ac80af49fe49e2030479f11ffa7002f36cf743cb9df5c7b1f592e6");
+
+    let actual = verify_safe_tx_hash("4", &safe_info("1.1.1"), &request);
+
+    assert!(actual.is_ok());
+}
+
+#[test]
+fn verify_safe_tx_hash_invalid_value() {
+    let mut request =
+        transaction_request("0x7add2c73a0ac80af49fe49e2030479f11ffa7002f36cf743cb9df5c7b1f592e6");
+    request.value = "0x10".to_string();
+
+    let actual = verify_safe_tx_hash("4", &safe_info("1.1.1"), &request).expect_err("");
+
+    assert_eq!(actual.status, 422);
+}
+
+#[test]
+fn rejection_request_is_a_cancellation() {
+    let rejection_request = RejectionRequest {
+        sender: "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23".to_string(),
+        signature: "0x".to_string(),
+        origin: None,
+    };
+
+    let actual = to_rejection_request("4", &safe_info("1.1.1"), 39, &rejection_request).unwrap();
+
+    assert_eq!(
+        actual.safe_tx_hash,
+        "0x89067bfebe450e45c02dd97e3cc9bd1656d49ebb8a17819829eab9c5dc575c27"
+    );
+    assert_eq!(actual.to, "0xd6f5Bef6bb4acD235CF85c0ce196316d10785d67");
+    assert_eq!(actual.nonce, "39");
+    assert!(verify_safe_tx_hash("4", &safe_info("1.1.1"), &actual).is_ok());
+}
+
+#[test]
+fn rejection_request_with_chain_id() {
+    let rejection_request = RejectionRequest {
+        sender: "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23".to_string(),
+        signature: "0x".to_string(),
+        origin: None,
+    };
+
+    let actual = to_rejection_request("4", &safe_info("1.3.0"), 39, &rejection_request).unwrap();
+
+    assert_eq!(
+        actual.safe_tx_hash,
+        "0x700201b913d02b9464308cde4eb63a2298b346de3b0aa76d13cb7e846d132144"
+    );
+    assert!(verify_safe_tx_hash("4", &safe_info("1.3.0"), &actual).is_ok());
+}
@@ -0,0 +1,325 @@
+use crate::cache::memory::InMemoryCache;
+use crate::cache::Cache;
+use crate::models::backend::webhooks::{IncomingEther, Payload, PayloadDetails};
+use crate::models::service::webhooks::{DeliveryStatus, Subscription, SubscriptionRequest};
+use crate::services::webhooks::*;
+use serde_json::json;
+
+const CHAIN_ID: &str = "4";
+const SAFE: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+
+fn subscription_request(callback_url: &str) -> SubscriptionRequest {
+    SubscriptionRequest {
+        callback_url: callback_url.to_string(),
+        secret: "secret".to_string(),
+    }
+}
+
+fn incoming_ether_payload(chain_id: &str) -> Payload {
+    Payload {
+        address: SAFE.to_string(),
+        chain_id: Some(chain_id.to_string()),
+        details: Some(PayloadDetails::IncomingEther(IncomingEther {
+            tx_hash: "0x08cf4bb6fe2a7e77e86e7679ec3c266516155cdc9900cdd47afef791169d6e21"
+                .to_string(),
+            value: "1000".to_string(),
+        })),
+    }
+}
+
+#[test]
+fn sign_body() {
+    let actual = sign("key", "The quick brown fox jumps over the lazy dog").unwrap();
+
+    assert_eq!(
+        actual,
+        "0xf7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+    );
+}
+
+#[tokio::test]
+async fn subscribe_hides_secret() {
+    let cache = InMemoryCache::new(100);
+
+    let subscription = subscribe(
+        &cache,
+        CHAIN_ID,
+        SAFE,
+        &subscription_request("https://example.com/hook"),
+    )
+    .await
+    .unwrap();
+    let subscriptions = get_subscriptions(&cache, CHAIN_ID, SAFE).await.unwrap();
+
+    assert_eq!(subscription.secret, None);
+    assert_eq!(subscriptions, vec![subscription]);
+}
+
+#[tokio::test]
+async fn subscribe_invalid_callback_url() {
+    let cache = InMemoryCache::new(100);
+
+    for callback_url in &[
+        "ftp://example.com",
+        "http://example.com/hook",
+        "https://localhost/hook",
+        "https://127.0.0.1/hook",
+        "https://10.0.0.1/hook",
+        "https://192.168.1.1/hook",
+        "https://169.254.169.254/latest/meta-data",
+        "https://[::1]/hook",
+        "https://[fd00::1]/hook",
+        "https://[::ffff:172.16.0.1]/hook",
+    ] {
+        let error = subscribe(&cache, CHAIN_ID, SAFE, &subscription_request(callback_url))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status, 422, "{}", callback_url);
+    }
+    assert!(get_subscriptions(&cache, CHAIN_ID, SAFE)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn unsubscribe_removes_subscription() {
+    let cache = InMemoryCache::new(100);
+    let subscription = subscribe(
+        &cache,
+        CHAIN_ID,
+        SAFE,
+        &subscription_request("https://example.com/hook"),
+    )
+    .await
+    .unwrap();
+
+    unsubscribe(&cache, CHAIN_ID, SAFE, &subscription.id)
+        .await
+        .unwrap();
+
+    assert!(get_subscriptions(&cache, CHAIN_ID, SAFE)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        unsubscribe(&cache, CHAIN_ID, SAFE, &subscription.id)
+            .await
+            .unwrap_err()
+            .status,
+        404
+    );
+}
+
+#[tokio::test]
+async fn enqueue_deliveries_for_subscriptions() {
+    let cache = InMemoryCache::new(100);
+    let subscription = subscribe(
+        &cache,
+        CHAIN_ID,
+        SAFE,
+        &subscription_request("https://example.com/hook"),
+    )
+    .await
+    .unwrap();
+
+    enqueue_deliveries(&cache, &incoming_ether_payload(CHAIN_ID))
+        .await
+        .unwrap();
+
+    let deliveries = get_deliveries(&cache, CHAIN_ID, SAFE, &subscription.id)
+        .await
+        .unwrap();
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].status, DeliveryStatus::Pending);
+    assert_eq!(deliveries[0].attempts, 0);
+    assert_eq!(
+        deliveries[0].payload,
+        json!({
+            "address": SAFE,
+            "type": "INCOMING_ETHER",
+            "txHash": "0x08cf4bb6fe2a7e77e86e7679ec3c266516155cdc9900cdd47afef791169d6e21",
+            "value": "1000"
+        })
+    );
+}
+
+#[tokio::test]
+async fn enqueue_deliveries_ignores_unknown_events() {
+    let cache = InMemoryCache::new(100);
+    let subscription = subscribe(
+        &cache,
+        CHAIN_ID,
+        SAFE,
+        &subscription_request("https://example.com/hook"),
+    )
+    .await
+    .unwrap();
+    let payload = Payload {
+        address: SAFE.to_string(),
+        chain_id: Some(CHAIN_ID.to_string()),
+        details: Some(PayloadDetails::Unknown),
+    };
+
+    enqueue_deliveries(&cache, &payload).await.unwrap();
+
+    assert!(get_deliveries(&cache, CHAIN_ID, SAFE, &subscription.id)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn failed_delivery_is_retried_later() {
+    let cache = InMemoryCache::new(100);
+    let client = reqwest::Client::new();
+    let subscription = subscribe(
+        &cache,
+        CHAIN_ID,
+        SAFE,
+        &subscription_request("https://gateway.invalid/hook"),
+    )
+    .await
+    .unwrap();
+    enqueue_deliveries(&cache, &incoming_ether_payload(CHAIN_ID))
+        .await
+        .unwrap();
+
+    process_pending_deliveries(&cache, &client).await.unwrap();
+
+    let delivery = get_deliveries(&cache, CHAIN_ID, SAFE, &subscription.id)
+        .await
+        .unwrap()
+        .remove(0);
+    assert_eq!(delivery.status, DeliveryStatus::Pending);
+    assert_eq!(delivery.attempts, 1);
+    assert!(delivery.last_error.is_some());
+    assert!(delivery.next_attempt.unwrap() > delivery.created);
+    assert!(!cache.has_key(&format!("wh_lock_{}", &delivery.id)).await);
+
+    // Not due yet
+    process_pending_deliveries(&cache, &client).await.unwrap();
+    let delivery = get_delivery(&cache, CHAIN_ID, SAFE, &subscription.id, &delivery.id)
+        .await
+        .unwrap();
+    assert_eq!(delivery.attempts, 1);
+}
+
+#[tokio::test]
+async fn subscriptions_are_scoped_to_the_chain_and_checksummed_safe() {
+    let cache = InMemoryCache::new(100);
+    let subscription = subscribe(
+        &cache,
+        CHAIN_ID,
+        &SAFE.to_lowercase(),
+        &subscription_request("https://example.com/hook"),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(subscription.safe, SAFE);
+    assert_eq!(
+        get_subscriptions(&cache, CHAIN_ID, SAFE).await.unwrap(),
+        vec![subscription]
+    );
+    assert!(get_subscriptions(&cache, "1", SAFE)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        get_subscriptions(&cache, CHAIN_ID, "0x1234")
+            .await
+            .unwrap_err()
+            .status,
+        422
+    );
+}
+
+#[tokio::test]
+async fn enqueue_deliveries_ignores_other_chains() {
+    let cache = InMemoryCache::new(100);
+    let subscription = subscribe(
+        &cache,
+        CHAIN_ID,
+        SAFE,
+        &subscription_request("https://example.com/hook"),
+    )
+    .await
+    .unwrap();
+
+    enqueue_deliveries(&cache, &incoming_ether_payload("1"))
+        .await
+        .unwrap();
+
+    assert!(get_deliveries(&cache, CHAIN_ID, SAFE, &subscription.id)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn enqueue_deliveries_for_chain_of_the_hook_route() {
+    let cache = InMemoryCache::new(100);
+    let subscription = subscribe(
+        &cache,
+        CHAIN_ID,
+        SAFE,
+        &subscription_request("https://example.com/hook"),
+    )
+    .await
+    .unwrap();
+    // Payloads of the transaction service don't contain the chain
+    let payload: Payload = serde_json::from_value(json!({
+        "address": SAFE,
+        "type": "INCOMING_ETHER",
+        "txHash": "0x08cf4bb6fe2a7e77e86e7679ec3c266516155cdc9900cdd47afef791169d6e21",
+        "value": "1000"
+    }))
+    .unwrap();
+    assert_eq!(payload.chain_id, None);
+
+    enqueue_deliveries(&cache, &payload.with_chain_id(CHAIN_ID.to_string()))
+        .await
+        .unwrap();
+
+    let deliveries = get_deliveries(&cache, CHAIN_ID, SAFE, &subscription.id)
+        .await
+        .unwrap();
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].status, DeliveryStatus::Pending);
+}
+
+#[tokio::test]
+async fn delivery_to_private_address_fails() {
+    let cache = InMemoryCache::new(100);
+    let client = reqwest::Client::new();
+    // Stored before the callback urls were checked
+    let subscription = Subscription {
+        id: String::from("subscription"),
+        chain_id: CHAIN_ID.to_string(),
+        safe: SAFE.to_string(),
+        callback_url: String::from("http://169.254.169.254/latest/meta-data"),
+        secret: Some(String::from("secret")),
+    };
+    cache
+        .insert_in_hash(
+            &format!("wh_subs_{}_{}", CHAIN_ID, SAFE),
+            &subscription.id,
+            &serde_json::to_string(&subscription).unwrap(),
+        )
+        .await;
+    enqueue_deliveries(&cache, &incoming_ether_payload(CHAIN_ID))
+        .await
+        .unwrap();
+
+    process_pending_deliveries(&cache, &client).await.unwrap();
+
+    let delivery = get_deliveries(&cache, CHAIN_ID, SAFE, &subscription.id)
+        .await
+        .unwrap()
+        .remove(0);
+    assert_eq!(delivery.status, DeliveryStatus::Failed);
+    assert_eq!(delivery.attempts, 1);
+    assert_eq!(delivery.last_status_code, None);
+}
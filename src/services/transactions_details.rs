@@ -10,52 +10,81 @@ use crate::models::service::transactions::{
     TransactionIdParts, ID_PREFIX_CREATION_TX, ID_PREFIX_ETHEREUM_TX, ID_PREFIX_MODULE_TX,
     ID_PREFIX_MULTISIG_TX, ID_SEPARATOR,
 };
-use crate::providers::info::DefaultInfoProvider;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
 use crate::utils::context::Context;
 use crate::utils::errors::ApiResult;
 use crate::utils::hex_hash;
 use crate::utils::transactions::fetch_rejections;
 use log::debug;
 
-pub(super) fn get_multisig_transaction_details(
+pub(super) async fn get_multisig_transaction_details(
     context: &Context,
+    chain_id: &str,
     safe_tx_hash: &str,
 ) -> ApiResult<TransactionDetails> {
-    let mut info_provider = DefaultInfoProvider::new(context);
+    let mut info_provider = DefaultInfoProvider::new(chain_id, context);
+    let mut multisig_tx = get_multisig_transaction(context, chain_id, safe_tx_hash).await?;
+    multisig_tx.decode_missing_data(&mut info_provider).await;
+
+    let version = info_provider
+        .safe_info(&multisig_tx.safe)
+        .await
+        .ok()
+        .and_then(|safe_info| safe_info.version);
+    let rejections = fetch_rejections(
+        context,
+        chain_id,
+        &multisig_tx.safe,
+        version.as_deref(),
+        multisig_tx.nonce,
+    )
+    .await?;
+
+    let details = multisig_tx
+        .to_transaction_details(rejections, &mut info_provider)
+        .await?;
+
+    Ok(details)
+}
+
+pub(super) async fn get_multisig_transaction(
+    context: &Context,
+    chain_id: &str,
+    safe_tx_hash: &str,
+) -> ApiResult<MultisigTransaction> {
     let url = format!(
         "{}/v1/multisig-transactions/{}/",
-        base_transaction_service_url(),
+        base_transaction_service_url(chain_id)?,
         safe_tx_hash
     );
     let body = RequestCached::new(url)
+        .chain_id(chain_id)
         .request_timeout(transaction_request_timeout())
-        .execute(context.client(), context.cache())?;
-    let multisig_tx: MultisigTransaction = serde_json::from_str(&body)?;
-
-    let rejections = fetch_rejections(context, &multisig_tx.safe, multisig_tx.nonce);
-
-    let details = multisig_tx.to_transaction_details(rejections, &mut info_provider)?;
-
-    Ok(details)
+        .execute(context.client(), context.cache())
+        .await?;
+    Ok(serde_json::from_str(&body)?)
 }
 
-fn get_ethereum_transaction_details(
+async fn get_ethereum_transaction_details(
     context: &Context,
+    chain_id: &str,
     safe: &str,
     tx_hash: &str,
     detail_hash: &str,
 ) -> ApiResult<TransactionDetails> {
-    let mut info_provider = DefaultInfoProvider::new(context);
+    let mut info_provider = DefaultInfoProvider::new(chain_id, context);
     let url = format!(
         "{}/v1/safes/{}/transfers/?transaction_hash={}&limit=1000",
-        base_transaction_service_url(),
+        base_transaction_service_url(chain_id)?,
         safe,
         tx_hash
     );
     debug!("url: {}", url);
     let body = RequestCached::new(url)
+        .chain_id(chain_id)
         .request_timeout(transaction_request_timeout())
-        .execute(context.client(), context.cache())?;
+        .execute(context.client(), context.cache())
+        .await?;
     let transfers: Page<Transfer> = serde_json::from_str(&body)?;
     let transfer = transfers
         .results
@@ -66,42 +95,51 @@ fn get_ethereum_transaction_details(
             hex_hash(transfer) == detail_hash
         })
         .ok_or(api_error!("No transfer found"))?;
-    let details = transfer.to_transaction_details(&mut info_provider, &safe.to_owned())?;
+    let details = transfer
+        .to_transaction_details(&mut info_provider, &safe.to_owned())
+        .await?;
 
     Ok(details)
 }
 
-fn get_module_transaction_details(
+async fn get_module_transaction_details(
     context: &Context,
+    chain_id: &str,
     safe: &str,
     tx_hash: &str,
     detail_hash: &str,
 ) -> ApiResult<TransactionDetails> {
-    let mut info_provider = DefaultInfoProvider::new(context);
+    let mut info_provider = DefaultInfoProvider::new(chain_id, context);
 
     let url = format!(
         "{}/v1/safes/{}/module-transactions/?transaction_hash={}&limit=1000",
-        base_transaction_service_url(),
+        base_transaction_service_url(chain_id)?,
         safe,
         tx_hash
     );
     debug!("url: {}", url);
     let body = RequestCached::new(url)
+        .chain_id(chain_id)
         .request_timeout(transaction_request_timeout())
-        .execute(context.client(), context.cache())?;
+        .execute(context.client(), context.cache())
+        .await?;
     let transactions: Page<ModuleTransaction> = serde_json::from_str(&body)?;
-    let transaction = transactions
+    let mut transaction = transactions
         .results
         .into_iter()
         .find(|tx| hex_hash(tx) == detail_hash)
         .ok_or(api_error!("No transfer found"))?;
-    let details = transaction.to_transaction_details(&mut info_provider)?;
+    transaction.decode_missing_data(&mut info_provider).await;
+    let details = transaction
+        .to_transaction_details(&mut info_provider)
+        .await?;
 
     Ok(details)
 }
 
-pub fn get_transactions_details(
+pub async fn get_transactions_details(
     context: &Context,
+    chain_id: &str,
     details_id: &String,
 ) -> ApiResult<TransactionDetails> {
     let id_parts = parse_id(details_id)?;
@@ -111,24 +149,35 @@ pub fn get_transactions_details(
             safe_address,
             transaction_hash,
             details_hash,
-        } => get_ethereum_transaction_details(
-            context,
-            &safe_address,
-            &transaction_hash,
-            &details_hash,
-        ),
+        } => {
+            get_ethereum_transaction_details(
+                context,
+                chain_id,
+                &safe_address,
+                &transaction_hash,
+                &details_hash,
+            )
+            .await
+        }
         TransactionIdParts::Module {
             safe_address,
             transaction_hash,
             details_hash,
         } => {
-            get_module_transaction_details(context, &safe_address, &transaction_hash, &details_hash)
+            get_module_transaction_details(
+                context,
+                chain_id,
+                &safe_address,
+                &transaction_hash,
+                &details_hash,
+            )
+            .await
         }
         TransactionIdParts::Multisig { safe_tx_hash, .. } => {
-            get_multisig_transaction_details(context, &safe_tx_hash)
+            get_multisig_transaction_details(context, chain_id, &safe_tx_hash).await
         }
         TransactionIdParts::TransactionHash(safe_tx_hash) => {
-            get_multisig_transaction_details(context, &safe_tx_hash)
+            get_multisig_transaction_details(context, chain_id, &safe_tx_hash).await
         }
         _ => Err(client_error!(422, "Bad transaction id")),
     }
@@ -0,0 +1,132 @@
+use crate::models::commons::Operation;
+use crate::models::service::transactions::requests::{
+    TransactionEstimation, TransactionPrepareRequest,
+};
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::providers::rpc::{CallRequest, RpcClient};
+use crate::services::transactions_simulation::direct_call;
+use crate::utils::context::Context;
+use crate::utils::errors::{ApiError, ApiResult};
+use crate::utils::transactions::{
+    estimate_base_gas, parse_required_tx_gas, required_tx_gas_data, revert_reason, safe_tx_gas,
+    SafeTransaction,
+};
+use ethabi::Uint;
+
+struct Execution {
+    success: bool,
+    revert_reason: Option<String>,
+    gas_used: Option<Uint>,
+}
+
+// Gas parameters for a proposal of the transaction, expecting as many signatures as the threshold
+pub async fn estimate_transaction(
+    context: &Context,
+    chain_id: &str,
+    safe_address: &str,
+    request: &TransactionPrepareRequest,
+) -> ApiResult<TransactionEstimation> {
+    let safe_info = DefaultInfoProvider::new(chain_id, context)
+        .safe_info(safe_address)
+        .await?;
+    let mut transaction = request.to_safe_transaction(Uint::from(safe_info.nonce))?;
+    let rpc_client = RpcClient::new(chain_id, context.client())?;
+
+    let gas_used = match execute(&rpc_client, &safe_info.address, &transaction).await? {
+        Some(Execution {
+            success: true,
+            gas_used: Some(gas_used),
+            ..
+        }) => gas_used,
+        Some(execution) => {
+            return Err(reverted(
+                execution
+                    .revert_reason
+                    .unwrap_or_else(|| String::from("no reason given")),
+            ))
+        }
+        None => estimate_call_gas(&rpc_client, &safe_info.address, &transaction).await?,
+    };
+    transaction.safe_tx_gas = safe_tx_gas(gas_used)?;
+
+    Ok(TransactionEstimation {
+        safe_tx_gas: transaction.safe_tx_gas.to_string(),
+        base_gas: estimate_base_gas(&transaction, safe_info.threshold).to_string(),
+    })
+}
+
+// Safes without `requiredTxGas` only get the estimation of the call made from the safe, which
+// includes the base cost of a transaction
+async fn estimate_call_gas(
+    rpc_client: &RpcClient,
+    safe_address: &str,
+    transaction: &SafeTransaction,
+) -> ApiResult<Uint> {
+    if transaction.operation == Operation::DELEGATE {
+        return Err(client_error!(422, "Safe does not support requiredTxGas"));
+    }
+    match rpc_client
+        .estimate_gas(&direct_call(safe_address, transaction))
+        .await?
+    {
+        Ok(gas) => Ok(gas),
+        Err(error) => Err(reverted(error.message)),
+    }
+}
+
+// Runs the transaction through `requiredTxGas`, which only the safe itself may call. It doesn't
+// forward the reason of a failed call, so calls are repeated directly to get it.
+// `None` if the safe doesn't have `requiredTxGas`.
+async fn execute(
+    rpc_client: &RpcClient,
+    safe_address: &str,
+    transaction: &SafeTransaction,
+) -> ApiResult<Option<Execution>> {
+    let required_tx_gas = CallRequest {
+        from: safe_address.to_owned(),
+        to: safe_address.to_owned(),
+        value: String::from("0x0"),
+        data: to_hex_string!(required_tx_gas_data(transaction)),
+    };
+    let revert_data = match rpc_client.call(&required_tx_gas).await? {
+        Ok(_) => return Ok(None),
+        Err(error) => error.revert_data().ok_or_else(|| {
+            ApiError::new_from_message(format!(
+                "RPC node did not return the revert data: {}",
+                error.message
+            ))
+        })?,
+    };
+    if let Some(gas_used) = parse_required_tx_gas(&revert_data) {
+        return Ok(Some(Execution {
+            success: true,
+            revert_reason: None,
+            gas_used: Some(gas_used),
+        }));
+    }
+
+    let revert_reason = match transaction.operation {
+        Operation::CALL => match rpc_client
+            .call(&direct_call(safe_address, transaction))
+            .await?
+        {
+            Ok(_) => None,
+            Err(error) => Some(
+                error
+                    .revert_data()
+                    .and_then(|data| revert_reason(&data))
+                    .unwrap_or(error.message),
+            ),
+        },
+        Operation::DELEGATE => None,
+    };
+    Ok(Some(Execution {
+        success: false,
+        revert_reason,
+        gas_used: None,
+    }))
+}
+
+fn reverted(reason: String) -> ApiError {
+    ApiError::new_from_message_with_code(422, format!("Transaction reverts: {}", reason))
+}
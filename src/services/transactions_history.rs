@@ -15,13 +15,14 @@ use crate::utils::errors::ApiResult;
 use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, Utc};
 use itertools::Itertools;
 
-pub fn get_history_transactions(
+pub async fn get_history_transactions(
     context: &Context,
+    chain_id: &str,
     safe_address: &String,
     page_url: &Option<String>,
     timezone_offset: &Option<String>,
 ) -> ApiResult<Page<TransactionListItem>> {
-    let mut info_provider = DefaultInfoProvider::new(context);
+    let mut info_provider = DefaultInfoProvider::new(chain_id, context);
     let request_timezone_offset = timezone_offset
         .as_ref()
         .and_then(|it| it.parse::<i32>().ok())
@@ -34,7 +35,29 @@ pub fn get_history_transactions(
     let page_metadata = adjust_page_meta(&incoming_page_metadata);
     let extended_page_url = Some(page_metadata.to_url_string());
 
-    let backend_paged_txs = fetch_backend_paged_txs(context, safe_address, &extended_page_url)?;
+    let mut backend_paged_txs =
+        fetch_backend_paged_txs(context, chain_id, safe_address, &extended_page_url).await?;
+    info_provider
+        .prefetch_contract_abis(
+            &backend_paged_txs
+                .results
+                .iter()
+                .filter_map(|transaction| transaction.undecoded_target())
+                .collect::<Vec<String>>(),
+        )
+        .await?;
+    for transaction in backend_paged_txs.results.iter_mut() {
+        transaction.decode_missing_data(&mut info_provider).await;
+    }
+    info_provider
+        .prefetch_address_infos(
+            &backend_paged_txs
+                .results
+                .iter()
+                .flat_map(|transaction| transaction.referenced_addresses(safe_address))
+                .collect::<Vec<String>>(),
+        )
+        .await?;
     let mut backend_txs_iter = backend_paged_txs.results.into_iter();
     let prev_page_timestamp = if page_metadata.offset != 0 {
         peek_timestamp_and_remove_item(
@@ -43,15 +66,16 @@ pub fn get_history_transactions(
             safe_address,
             request_timezone_offset,
         )
+        .await
         .unwrap_or(-1)
     } else {
         -1
     };
 
     let mut service_txs =
-        backend_txs_to_summary_txs(&mut backend_txs_iter, &mut info_provider, safe_address)?;
+        backend_txs_to_summary_txs(&mut backend_txs_iter, &mut info_provider, safe_address).await?;
     if backend_paged_txs.next.is_none() {
-        let creation_tx = get_creation_transaction_summary(context, safe_address)?;
+        let creation_tx = get_creation_transaction_summary(context, chain_id, safe_address).await?;
         service_txs.push(creation_tx);
     }
 
@@ -61,39 +85,45 @@ pub fn get_history_transactions(
     Ok(Page {
         next: build_page_url(
             context,
+            chain_id,
             safe_address,
             &incoming_page_metadata,
             timezone_offset,
             backend_paged_txs.next,
             1, // Direction forward
-        ),
+        )?,
         previous: build_page_url(
             context,
+            chain_id,
             safe_address,
             &incoming_page_metadata,
             timezone_offset,
             backend_paged_txs.previous,
             -1, // Direction backwards
-        ),
+        )?,
         results: tx_list_items,
     })
 }
 
 fn build_page_url(
     context: &Context,
+    chain_id: &str,
     safe_address: &str,
     page_meta: &PageMetadata,
     timezone_offset: &Option<String>,
     url: Option<String>,
     direction: i64,
-) -> Option<String> {
-    url.as_ref().map(|_| {
-        context.build_absolute_url(uri!(
-            crate::routes::transactions::history_transactions: safe_address,
-            offset_page_meta(page_meta, direction * (page_meta.limit as i64)),
-            timezone_offset.clone().unwrap_or("0".to_string()),
-        ))
-    })
+) -> ApiResult<Option<String>> {
+    url.as_ref()
+        .map(|_| {
+            context.build_absolute_url(uri!(
+                crate::routes::transactions::history_transactions: chain_id,
+                safe_address,
+                offset_page_meta(page_meta, direction * (page_meta.limit as i64)),
+                timezone_offset.clone().unwrap_or("0".to_string()),
+            ))
+        })
+        .transpose()
 }
 
 pub(super) fn adjust_page_meta(meta: &PageMetadata) -> PageMetadata {
@@ -110,15 +140,16 @@ pub(super) fn adjust_page_meta(meta: &PageMetadata) -> PageMetadata {
     }
 }
 
-fn fetch_backend_paged_txs(
+async fn fetch_backend_paged_txs(
     context: &Context,
+    chain_id: &str,
     safe_address: &str,
     page_url: &Option<String>,
 ) -> ApiResult<Page<Transaction>> {
     let page_metadata = PageMetadata::from_url_string(page_url.as_ref().unwrap_or(&"".to_string()));
     let url = format!(
         "{}/v1/safes/{}/all-transactions/?{}&queued=false&executed=true",
-        base_transaction_service_url(),
+        base_transaction_service_url(chain_id)?,
         safe_address,
         page_metadata.to_url_string()
     );
@@ -126,23 +157,28 @@ fn fetch_backend_paged_txs(
     log::debug!("page_url: {:#?}", &page_url);
     log::debug!("page_metadata: {:#?}", &page_metadata);
     let body = RequestCached::new(url)
+        .chain_id(chain_id)
         .request_timeout(transaction_request_timeout())
-        .execute(context.client(), context.cache())?;
+        .execute(context.client(), context.cache())
+        .await?;
     Ok(serde_json::from_str::<Page<Transaction>>(&body)?)
 }
 
-pub(super) fn backend_txs_to_summary_txs(
+pub(super) async fn backend_txs_to_summary_txs(
     txs: &mut dyn Iterator<Item = Transaction>,
     info_provider: &mut dyn InfoProvider,
     safe_address: &str,
 ) -> ApiResult<Vec<TransactionSummary>> {
-    Ok(txs
-        .flat_map(|transaction| {
+    let mut summaries = vec![];
+    for transaction in txs {
+        summaries.extend(
             transaction
                 .to_transaction_summary(info_provider, safe_address)
-                .unwrap_or(vec![])
-        })
-        .collect())
+                .await
+                .unwrap_or(vec![]),
+        );
+    }
+    Ok(summaries)
 }
 
 pub(super) fn service_txs_to_tx_list_items(
@@ -170,7 +206,7 @@ pub(super) fn service_txs_to_tx_list_items(
     Ok(tx_list_items)
 }
 
-pub(super) fn peek_timestamp_and_remove_item(
+pub(super) async fn peek_timestamp_and_remove_item(
     transactions: &mut dyn Iterator<Item = Transaction>,
     info_provider: &mut dyn InfoProvider,
     safe_address: &str,
@@ -179,7 +215,8 @@ pub(super) fn peek_timestamp_and_remove_item(
     let timestamp = transactions
         .next()
         .ok_or(api_error!("empty transactions"))?
-        .to_transaction_summary(info_provider, safe_address)?
+        .to_transaction_summary(info_provider, safe_address)
+        .await?
         .last()
         .ok_or(api_error!("empty transactions"))?
         .timestamp;
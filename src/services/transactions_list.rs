@@ -11,35 +11,61 @@ use crate::utils::errors::ApiResult;
 use crate::utils::extract_query_string;
 use log::debug;
 
-pub fn get_all_transactions(
+pub async fn get_all_transactions(
     context: &Context,
+    chain_id: &str,
     safe_address: &String,
     page_url: &Option<String>,
 ) -> ApiResult<Page<TransactionSummary>> {
-    let mut info_provider = DefaultInfoProvider::new(context);
+    let mut info_provider = DefaultInfoProvider::new(chain_id, context);
     let url = format!(
         "{}/v1/safes/{}/all-transactions/?{}",
-        base_transaction_service_url(),
+        base_transaction_service_url(chain_id)?,
         safe_address,
         page_url.as_ref().unwrap_or(&String::new())
     );
     debug!("request URL: {}", &url);
     debug!("page_url: {:#?}", page_url);
     let body = RequestCached::new(url)
+        .chain_id(chain_id)
         .request_timeout(transaction_request_timeout())
-        .execute(context.client(), context.cache())?;
-    let backend_transactions: Page<Transaction> = serde_json::from_str(&body)?;
-    let mut service_transactions: Vec<TransactionSummary> = backend_transactions
-        .results
-        .into_iter()
-        .flat_map(|transaction| {
+        .execute(context.client(), context.cache())
+        .await?;
+    let mut backend_transactions: Page<Transaction> = serde_json::from_str(&body)?;
+    info_provider
+        .prefetch_contract_abis(
+            &backend_transactions
+                .results
+                .iter()
+                .filter_map(|transaction| transaction.undecoded_target())
+                .collect::<Vec<String>>(),
+        )
+        .await?;
+    for transaction in backend_transactions.results.iter_mut() {
+        transaction.decode_missing_data(&mut info_provider).await;
+    }
+    info_provider
+        .prefetch_address_infos(
+            &backend_transactions
+                .results
+                .iter()
+                .flat_map(|transaction| transaction.referenced_addresses(safe_address))
+                .collect::<Vec<String>>(),
+        )
+        .await?;
+    let mut service_transactions: Vec<TransactionSummary> = vec![];
+    for transaction in backend_transactions.results.into_iter() {
+        service_transactions.extend(
             transaction
                 .to_transaction_summary(&mut info_provider, safe_address)
-                .unwrap_or(vec![])
-        })
-        .collect();
+                .await
+                .unwrap_or(vec![]),
+        );
+    }
     if backend_transactions.next.is_none() {
-        if let Ok(creation_transaction) = get_creation_transaction_summary(context, safe_address) {
+        if let Ok(creation_transaction) =
+            get_creation_transaction_summary(context, chain_id, safe_address).await
+        {
             service_transactions.push(creation_transaction);
         }
     }
@@ -50,39 +76,51 @@ pub fn get_all_transactions(
             .as_ref()
             .and_then(|link| extract_query_string(link))
             .map(|link| {
-                context
-                    .build_absolute_url(uri!(crate::routes::transactions::all: safe_address, link))
-            }),
+                context.build_absolute_url(uri!(
+                    crate::routes::transactions::all: chain_id,
+                    safe_address,
+                    link
+                ))
+            })
+            .transpose()?,
         previous: backend_transactions
             .previous
             .as_ref()
             .and_then(|link| extract_query_string(link))
             .map(|link| {
-                context
-                    .build_absolute_url(uri!(crate::routes::transactions::all: safe_address, link))
-            }),
+                context.build_absolute_url(uri!(
+                    crate::routes::transactions::all: chain_id,
+                    safe_address,
+                    link
+                ))
+            })
+            .transpose()?,
         results: service_transactions,
     })
 }
 
-pub(super) fn get_creation_transaction_summary(
+pub(super) async fn get_creation_transaction_summary(
     context: &Context,
+    chain_id: &str,
     safe: &String,
 ) -> ApiResult<TransactionSummary> {
     let url = format!(
         "{}/v1/safes/{}/creation/",
-        base_transaction_service_url(),
+        base_transaction_service_url(chain_id)?,
         safe
     );
     debug!("{}", &url);
     let body = RequestCached::new(url)
+        .chain_id(chain_id)
         .request_timeout(transaction_request_timeout())
-        .execute(context.client(), context.cache())?;
+        .execute(context.client(), context.cache())
+        .await?;
 
-    let mut info_provider = DefaultInfoProvider::new(context);
+    let mut info_provider = DefaultInfoProvider::new(chain_id, context);
 
     let creation_transaction_dto: CreationTransaction = serde_json::from_str(&body)?;
-    let transaction_summary =
-        creation_transaction_dto.to_transaction_summary(safe, &mut info_provider);
+    let transaction_summary = creation_transaction_dto
+        .to_transaction_summary(safe, &mut info_provider)
+        .await;
     Ok(transaction_summary)
 }
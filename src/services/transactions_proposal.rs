@@ -1,63 +1,233 @@
+use crate::cache::cache_operations::RequestCached;
 use crate::cache::Cache;
-use crate::config::base_transaction_service_url;
-use crate::models::service::transactions::requests::MultisigTransactionRequest;
+use crate::config::{base_transaction_service_url, transaction_request_timeout};
+use crate::models::backend::transactions::MultisigTransaction;
+use crate::models::commons::{Operation, Page};
+use crate::models::service::transactions::requests::{
+    MultisigTransactionRequest, PreparedTransaction, RejectionRequest, TransactionPrepareRequest,
+};
+use crate::providers::info::{DefaultInfoProvider, InfoProvider, SafeInfo};
+use crate::services::transactions_details::get_multisig_transaction;
 use crate::utils::context::Context;
-use crate::utils::errors::{ApiError, ApiResult};
+use crate::utils::errors::{ApiError, ApiResult, ErrorDetails};
+use crate::utils::signatures::{verify_owner_signatures, verify_sender};
+use crate::utils::transactions::{
+    rejection_tx_hash, safe_tx_hash, safe_tx_typed_data, INVALID_SAFE_TX_HASH_CODE,
+};
+use ethabi::Uint;
+use ethereum_types::Address;
 use std::collections::HashMap;
 
-pub fn submit_confirmation(
+pub async fn submit_confirmation(
     context: &Context,
+    chain_id: &str,
     safe_tx_hash: &str,
     signature: &str,
 ) -> ApiResult<()> {
+    let multisig_tx = get_multisig_transaction(context, chain_id, safe_tx_hash).await?;
+    let safe_info = DefaultInfoProvider::new(chain_id, context)
+        .safe_info(&multisig_tx.safe)
+        .await?;
+    verify_owner_signatures(&safe_info.owners, safe_tx_hash, signature)?;
+
     let url = format!(
         "{}/v1/multisig-transactions/{}/confirmations/",
-        base_transaction_service_url(),
+        base_transaction_service_url(chain_id)?,
         &safe_tx_hash
     );
     let mut json = HashMap::new();
     json.insert("signature", signature);
 
-    let response = context.client().post(&url).json(&json).send()?;
+    let response = context.client().post(&url).json(&json).send().await?;
 
     if response.status().is_success() {
         context
             .cache()
-            .invalidate_pattern(&format!("*{}*", &safe_tx_hash));
+            .invalidate_pattern(&format!("*{}*", &safe_tx_hash))
+            .await;
         Ok(())
     } else {
         Err(ApiError::from_http_response(
             response,
             String::from("Unexpected tx confirmation error"),
-        ))
+        )
+        .await)
     }
 }
 
-pub fn propose_transaction(
+pub async fn propose_transaction(
     context: &Context,
+    chain_id: &str,
     safe_address: &str,
     transaction_request: &MultisigTransactionRequest,
 ) -> ApiResult<()> {
+    let safe_info = DefaultInfoProvider::new(chain_id, context)
+        .safe_info(safe_address)
+        .await?;
+    verify_safe_tx_hash(chain_id, &safe_info, transaction_request)?;
+    let signers = verify_owner_signatures(
+        &safe_info.owners,
+        &transaction_request.safe_tx_hash,
+        &transaction_request.signature,
+    )?;
+    verify_sender(&transaction_request.sender, &signers)?;
+
     let url = format!(
         "{}/v1/safes/{}/multisig-transactions/",
-        base_transaction_service_url(),
+        base_transaction_service_url(chain_id)?,
         &safe_address
     );
     let response = context
         .client()
         .post(&url)
         .json(&transaction_request)
-        .send()?;
+        .send()
+        .await?;
 
     if response.status().is_success() {
         context
             .cache()
-            .invalidate_pattern(&format!("*{}*", &safe_address));
+            .invalidate_pattern(&format!("*{}*", &safe_address))
+            .await;
         Ok(())
     } else {
         Err(ApiError::from_http_response(
             response,
             String::from("Unexpected multisig tx proposal error"),
-        ))
+        )
+        .await)
+    }
+}
+
+pub(super) fn verify_safe_tx_hash(
+    chain_id: &str,
+    safe_info: &SafeInfo,
+    transaction_request: &MultisigTransactionRequest,
+) -> ApiResult<()> {
+    let expected = to_hex_string!(safe_tx_hash(
+        &parse_safe_address(safe_info)?,
+        parse_chain_id(chain_id)?,
+        safe_info.version.as_deref(),
+        &transaction_request.to_safe_transaction()?,
+    ));
+    if !expected.eq_ignore_ascii_case(&transaction_request.safe_tx_hash) {
+        return Err(ApiError::new(
+            422,
+            ErrorDetails {
+                code: INVALID_SAFE_TX_HASH_CODE,
+                message: Some(String::from(
+                    "Safe transaction hash does not match the transaction",
+                )),
+                arguments: Some(vec![expected]),
+            },
+        ));
+    }
+    Ok(())
+}
+
+pub async fn prepare_transaction(
+    context: &Context,
+    chain_id: &str,
+    safe_address: &str,
+    prepare_request: &TransactionPrepareRequest,
+) -> ApiResult<PreparedTransaction> {
+    let safe_info = DefaultInfoProvider::new(chain_id, context)
+        .safe_info(safe_address)
+        .await?;
+    let nonce = match &prepare_request.nonce {
+        Some(nonce) => {
+            Uint::from_dec_str(nonce).map_err(|_| client_error!(422, "Invalid nonce"))?
+        }
+        None => Uint::from(next_nonce(context, chain_id, &safe_info).await?),
+    };
+    let transaction = prepare_request.to_safe_transaction(nonce)?;
+    let safe_address = parse_safe_address(&safe_info)?;
+    let chain_id = parse_chain_id(chain_id)?;
+    let version = safe_info.version.as_deref();
+
+    Ok(PreparedTransaction {
+        safe_tx_hash: to_hex_string!(safe_tx_hash(&safe_address, chain_id, version, &transaction)),
+        nonce: nonce.to_string(),
+        typed_data: safe_tx_typed_data(&safe_address, chain_id, version, &transaction),
+    })
+}
+
+// Nonce following the last queued transaction, or the nonce of the safe if nothing is queued
+async fn next_nonce(context: &Context, chain_id: &str, safe_info: &SafeInfo) -> ApiResult<u64> {
+    let url = format!(
+        "{}/v1/safes/{}/multisig-transactions/?nonce__gte={}&ordering=-nonce&limit=1",
+        base_transaction_service_url(chain_id)?,
+        safe_info.address,
+        safe_info.nonce
+    );
+    let body = RequestCached::new(url)
+        .chain_id(chain_id)
+        .request_timeout(transaction_request_timeout())
+        .execute(context.client(), context.cache())
+        .await?;
+    let queued_transactions: Page<MultisigTransaction> = serde_json::from_str(&body)?;
+    Ok(queued_transactions
+        .results
+        .first()
+        .map_or(safe_info.nonce, |transaction| transaction.nonce + 1))
+}
+
+// Proposes the transaction without effect that replaces the transactions with the same nonce
+pub async fn propose_rejection(
+    context: &Context,
+    chain_id: &str,
+    safe_address: &str,
+    nonce: u64,
+    rejection_request: &RejectionRequest,
+) -> ApiResult<()> {
+    let safe_info = DefaultInfoProvider::new(chain_id, context)
+        .safe_info(safe_address)
+        .await?;
+    if nonce < safe_info.nonce {
+        return Err(client_error!(422, "Nonce was already executed"));
     }
+    let transaction_request = to_rejection_request(chain_id, &safe_info, nonce, rejection_request)?;
+    propose_transaction(context, chain_id, safe_address, &transaction_request).await
+}
+
+pub(super) fn to_rejection_request(
+    chain_id: &str,
+    safe_info: &SafeInfo,
+    nonce: u64,
+    rejection_request: &RejectionRequest,
+) -> ApiResult<MultisigTransactionRequest> {
+    let safe_tx_hash = to_hex_string!(rejection_tx_hash(
+        &parse_safe_address(safe_info)?,
+        parse_chain_id(chain_id)?,
+        safe_info.version.as_deref(),
+        nonce
+    ));
+    Ok(MultisigTransactionRequest {
+        to: safe_info.address.to_string(),
+        value: String::from("0"),
+        data: None,
+        nonce: nonce.to_string(),
+        operation: Operation::CALL,
+        safe_tx_gas: String::from("0"),
+        base_gas: String::from("0"),
+        gas_price: String::from("0"),
+        gas_token: String::from("0x0000000000000000000000000000000000000000"),
+        refund_receiver: None,
+        safe_tx_hash,
+        sender: rejection_request.sender.to_string(),
+        signature: rejection_request.signature.to_string(),
+        origin: rejection_request.origin.to_owned(),
+    })
+}
+
+fn parse_chain_id(chain_id: &str) -> ApiResult<u64> {
+    chain_id
+        .parse::<u64>()
+        .map_err(|_| client_error!(422, "Invalid chain id"))
+}
+
+fn parse_safe_address(safe_info: &SafeInfo) -> ApiResult<Address> {
+    Ok(serde_json::from_value(serde_json::Value::String(
+        safe_info.address.to_string(),
+    ))?)
 }
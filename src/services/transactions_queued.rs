@@ -10,14 +10,15 @@ use crate::utils::errors::ApiResult;
 use itertools::Itertools;
 
 // use https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.peekable
-pub fn get_queued_transactions(
+pub async fn get_queued_transactions(
     context: &Context,
+    chain_id: &str,
     safe_address: &String,
     page_url: &Option<String>,
     timezone_offset: &Option<String>,
     trusted: &Option<bool>,
 ) -> ApiResult<Page<TransactionListItem>> {
-    let mut info_provider = DefaultInfoProvider::new(context);
+    let mut info_provider = DefaultInfoProvider::new(chain_id, context);
 
     // Parse page meta (offset and limit)
     let page_meta = PageMetadata::from_url_string(page_url.as_ref().unwrap_or(&"".to_string()));
@@ -28,20 +29,37 @@ pub fn get_queued_transactions(
     let display_trusted_only = trusted.unwrap_or(true);
 
     // As we require the Safe nonce later we use it here explicitely to query transaction that are in the future
-    let safe_nonce = info_provider.safe_info(safe_address)?.nonce as i64;
-    let url = format!(
-        "{}/v1/safes/{}/multisig-transactions/?{}&nonce__gte={}&ordering=nonce,submissionDate&trusted={}",
-        base_transaction_service_url(),
+    let safe_nonce = info_provider.safe_info(safe_address).await?.nonce as i64;
+    let mut backend_transactions = fetch_queued_transactions(
+        context,
+        chain_id,
         safe_address,
-        adjusted_page_meta.to_url_string(),
         safe_nonce,
-        display_trusted_only
-    );
-
-    let body = RequestCached::new(url)
-        .request_timeout(transaction_request_timeout())
-        .execute(context.client(), context.cache())?;
-    let mut backend_transactions: Page<MultisigTransaction> = serde_json::from_str(&body)?;
+        &adjusted_page_meta,
+        display_trusted_only,
+    )
+    .await?;
+    info_provider
+        .prefetch_contract_abis(
+            &backend_transactions
+                .results
+                .iter()
+                .filter_map(|transaction| transaction.undecoded_target())
+                .collect::<Vec<String>>(),
+        )
+        .await?;
+    for transaction in backend_transactions.results.iter_mut() {
+        transaction.decode_missing_data(&mut info_provider).await;
+    }
+    info_provider
+        .prefetch_address_infos(
+            &backend_transactions
+                .results
+                .iter()
+                .flat_map(|transaction| transaction.referenced_addresses())
+                .collect::<Vec<String>>(),
+        )
+        .await?;
 
     // We need to do this before we create the iterator
     // Nonce of the first item in the next page (-1 if not present)
@@ -58,31 +76,72 @@ pub fn get_queued_transactions(
         &mut tx_iter,
         previous_page_nonce,
         edge_nonce,
-    );
+    )
+    .await;
 
     Ok(Page {
         next: build_page_url(
             context,
+            chain_id,
             &safe_address,
             &page_meta,
             timezone_offset,
             display_trusted_only,
             backend_transactions.next,
             1, // Direction forward
-        ),
+        )?,
         previous: build_page_url(
             context,
+            chain_id,
             &safe_address,
             &page_meta,
             timezone_offset,
             display_trusted_only,
             backend_transactions.previous,
             -1, // Direction backwards
-        ),
+        )?,
         results: service_transactions,
     })
 }
 
+// Transactions from the nonce of the safe on, ordered by nonce and submission date
+pub(super) async fn fetch_queued_transactions(
+    context: &Context,
+    chain_id: &str,
+    safe_address: &str,
+    safe_nonce: i64,
+    page_meta: &PageMetadata,
+    trusted: bool,
+) -> ApiResult<Page<MultisigTransaction>> {
+    let body = queued_transactions_request(chain_id, safe_address, safe_nonce, page_meta, trusted)?
+        .execute(context.client(), context.cache())
+        .await?;
+    Ok(serde_json::from_str(&body)?)
+}
+
+pub(super) fn queued_transactions_request(
+    chain_id: &str,
+    safe_address: &str,
+    safe_nonce: i64,
+    page_meta: &PageMetadata,
+    trusted: bool,
+) -> ApiResult<RequestCached> {
+    let url = format!(
+        "{}/v1/safes/{}/multisig-transactions/?{}&nonce__gte={}&ordering=nonce,submissionDate&trusted={}",
+        base_transaction_service_url(chain_id)?,
+        safe_address,
+        page_meta.to_url_string(),
+        safe_nonce,
+        trusted
+    );
+
+    let mut request = RequestCached::new(url);
+    request
+        .chain_id(chain_id)
+        .request_timeout(transaction_request_timeout());
+    Ok(request)
+}
+
 // Nonce of the first item in the next page (-1 if not present)
 pub(super) fn get_edge_nonce(backend_transactions: &mut Page<MultisigTransaction>) -> i64 {
     // If there is a next url we remove the last item for information on the next page
@@ -108,7 +167,7 @@ pub(super) fn get_previous_page_nonce(
     .map_or(-1, |tx| tx.nonce as i64)
 }
 
-pub(super) fn process_transactions(
+pub(super) async fn process_transactions(
     info_provider: &mut dyn InfoProvider,
     safe_nonce: i64,
     tx_iter: &mut dyn Iterator<Item = MultisigTransaction>,
@@ -165,7 +224,8 @@ pub(super) fn process_transactions(
                 // No conflict in this or the previous page
                 ConflictType::None
             },
-        );
+        )
+        .await;
         // Add additional conflicts of the group (only present when conflicts in the same page)
         while let Some(tx) = group_iter.next() {
             // Indicate if we are in a conflict group on the edge or if there are more conflicts in this page
@@ -180,7 +240,8 @@ pub(super) fn process_transactions(
                 &mut service_transactions,
                 &tx,
                 conflict_type,
-            );
+            )
+            .await;
         }
     }
 
@@ -189,21 +250,25 @@ pub(super) fn process_transactions(
 
 fn build_page_url(
     context: &Context,
+    chain_id: &str,
     safe_address: &String,
     page_meta: &PageMetadata,
     timezone_offset: &Option<String>,
     display_trusted_only: bool,
     url: Option<String>,
     direction: i64,
-) -> Option<String> {
-    url.as_ref().map(|_| {
-        context.build_absolute_url(uri!(
-            crate::routes::transactions::queued_transactions: safe_address,
-            offset_page_meta(page_meta, direction * (page_meta.limit as i64)),
-            timezone_offset.clone().unwrap_or("0".to_string()),
-            display_trusted_only
-        ))
-    })
+) -> ApiResult<Option<String>> {
+    url.as_ref()
+        .map(|_| {
+            context.build_absolute_url(uri!(
+                crate::routes::transactions::queued_transactions: chain_id,
+                safe_address,
+                offset_page_meta(page_meta, direction * (page_meta.limit as i64)),
+                timezone_offset.clone().unwrap_or("0".to_string()),
+                display_trusted_only
+            ))
+        })
+        .transpose()
 }
 
 pub(super) fn adjust_page_meta(meta: &PageMetadata) -> PageMetadata {
@@ -220,7 +285,7 @@ pub(super) fn adjust_page_meta(meta: &PageMetadata) -> PageMetadata {
     }
 }
 
-pub(super) fn add_transaction_as_summary(
+pub(super) async fn add_transaction_as_summary(
     info_provider: &mut dyn InfoProvider,
     items: &mut Vec<TransactionListItem>,
     transaction: &MultisigTransaction,
@@ -229,6 +294,7 @@ pub(super) fn add_transaction_as_summary(
     // Converting a multisig transaction theoretically can result in multiple summaries
     let mut tx_summary_iter = transaction
         .to_transaction_summary(info_provider)
+        .await
         .unwrap_or(vec![])
         .into_iter()
         .peekable();
@@ -0,0 +1,117 @@
+use crate::models::commons::Operation;
+use crate::models::converters::transactions::simulation::to_balance_changes;
+use crate::models::service::transactions::requests::{
+    TransactionPrepareRequest, TransactionSimulation,
+};
+use crate::providers::evm::RpcBackend;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::providers::rpc::{CallRequest, RpcClient};
+use crate::utils::context::Context;
+use crate::utils::errors::{ApiError, ApiResult};
+use crate::utils::transactions::{calldata_gas, revert_reason, SafeTransaction, TX_BASE_GAS};
+use ethabi::{Address, Uint};
+use evm::backend::{Apply, Backend};
+use evm::executor::StackExecutor;
+use evm::{Config, ExitReason};
+
+// Executes the transaction in the embedded EVM, on top of the latest block of the node configured
+// for the chain, without signatures or gas payment. The state is read from the node as the
+// execution needs it. The nonce of the safe is used if the request doesn't set one.
+pub async fn simulate_transaction(
+    context: &Context,
+    chain_id: &str,
+    safe_address: &str,
+    request: &TransactionPrepareRequest,
+) -> ApiResult<TransactionSimulation> {
+    let mut info_provider = DefaultInfoProvider::new(chain_id, context);
+    let safe_info = info_provider.safe_info(safe_address).await?;
+    let transaction = request.to_safe_transaction(Uint::from(safe_info.nonce))?;
+    let safe = safe_info
+        .address
+        .trim_start_matches("0x")
+        .parse::<Address>()
+        .map_err(|_| ApiError::new_from_message("Invalid safe address"))?;
+    let rpc_client = RpcClient::new(chain_id, context.client())?;
+    let mut backend = RpcBackend::new(&rpc_client, chain_id, safe).await?;
+
+    // The safe calls `to` for `CALL`. For `DELEGATE` the code of `to` is run as the code of the
+    // safe, like a delegate call does, so calls of the safe to itself run that code as well.
+    let (to, value) = match transaction.operation {
+        Operation::CALL => (transaction.to, transaction.value),
+        Operation::DELEGATE => {
+            let code = backend.code(transaction.to);
+            backend = backend.with_code(safe, code);
+            (safe, Uint::zero())
+        }
+    };
+    let balance_before = backend.basic(safe).balance;
+    let gas_limit = backend.block_gas_limit().min(Uint::from(u64::MAX)).as_u64() as usize;
+
+    let config = Config::istanbul();
+    let mut executor = StackExecutor::new(&backend, gas_limit, &config);
+    let (exit_reason, output) =
+        executor.transact_call(safe, to, value, transaction.data.to_owned(), gas_limit);
+    let gas_used = Uint::from(executor.used_gas());
+    let (applies, logs) = executor.deconstruct();
+    let mut balance_after = balance_before;
+    for apply in applies {
+        match apply {
+            Apply::Modify { address, basic, .. } if address == safe => {
+                balance_after = basic.balance
+            }
+            Apply::Delete { address } if address == safe => balance_after = Uint::zero(),
+            _ => {}
+        }
+    }
+    let logs: Vec<_> = logs.into_iter().collect();
+    // Defaults were used for the state the node failed to return
+    backend.into_result()?;
+
+    let revert_reason = match exit_reason {
+        ExitReason::Succeed(_) => None,
+        ExitReason::Revert(_) => {
+            Some(revert_reason(&output).unwrap_or_else(|| String::from("no reason given")))
+        }
+        ExitReason::Error(error) => Some(format!("{:?}", error)),
+        ExitReason::Fatal(error) => {
+            return Err(ApiError::new_from_message(format!(
+                "Simulation failed: {:?}",
+                error
+            )))
+        }
+    };
+    let success = revert_reason.is_none();
+    Ok(TransactionSimulation {
+        success,
+        revert_reason,
+        gas_used: Some(execution_gas(gas_used, &transaction).to_string()),
+        balance_changes: if success {
+            to_balance_changes(
+                &safe,
+                balance_before,
+                balance_after,
+                &logs,
+                &mut info_provider,
+            )
+            .await
+        } else {
+            vec![]
+        },
+    })
+}
+
+// The used gas includes the base cost of a transaction, which `execTransaction` pays once for all
+fn execution_gas(gas_used: Uint, transaction: &SafeTransaction) -> Uint {
+    let intrinsic_gas = Uint::from(TX_BASE_GAS + calldata_gas(&transaction.data));
+    gas_used.saturating_sub(intrinsic_gas)
+}
+
+// The call the safe makes for a transaction with `CALL` operation
+pub(super) fn direct_call(safe_address: &str, transaction: &SafeTransaction) -> CallRequest {
+    CallRequest {
+        from: safe_address.to_owned(),
+        to: to_hex_string!(transaction.to.0),
+        value: format!("0x{:x}", transaction.value),
+        data: to_hex_string!(transaction.data),
+    }
+}
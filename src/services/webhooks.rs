@@ -0,0 +1,466 @@
+use crate::cache::redis::RedisCache;
+use crate::cache::Cache;
+use crate::config::{
+    webhook_deliveries_duration, webhook_delivery_backoff, webhook_delivery_max_attempts,
+    webhook_delivery_timeout,
+};
+use crate::models::backend::webhooks::{Payload, PayloadDetails};
+use crate::models::service::webhooks::{
+    Delivery, DeliveryStatus, SafeEvent, Subscription, SubscriptionRequest,
+};
+use crate::utils::data_decoder::to_checksum_address;
+use crate::utils::errors::ApiResult;
+use crate::utils::hex_hash;
+use chrono::Utc;
+use hmac::{Hmac, Mac, NewMac};
+use reqwest::header::CONTENT_TYPE;
+use sha2::Sha256;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::Duration;
+
+// Subscriptions and deliveries are only kept in redis (see `Context::persistent_cache`), they are
+// not cached data and must neither be evicted nor flushed
+const SUBSCRIPTIONS_KEY: &str = "wh_subs";
+const DELIVERIES_KEY: &str = "wh_dlv";
+const DELIVERY_LOCK_KEY: &str = "wh_lock";
+const PENDING_DELIVERIES_KEY: &str = "wh_pending";
+
+const SIGNATURE_HEADER: &str = "X-Gateway-Signature";
+const DELIVERY_HEADER: &str = "X-Gateway-Delivery";
+
+const DELIVERY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub async fn subscribe(
+    cache: &dyn Cache,
+    chain_id: &str,
+    safe: &str,
+    request: &SubscriptionRequest,
+) -> ApiResult<Subscription> {
+    let safe = normalize_safe(safe)?;
+    let callback_url = parse_callback_url(&request.callback_url)?;
+    // Hosts that can't be resolved yet are checked again before every delivery
+    if let Ok(false) = resolves_to_public_addresses(&callback_url) {
+        return Err(client_error!(422, "Callback url has to be public"));
+    }
+    if request.secret.is_empty() {
+        return Err(client_error!(422, "Secret is required"));
+    }
+    let subscription = Subscription {
+        id: hex_hash(&(
+            chain_id,
+            &safe,
+            &request.callback_url,
+            Utc::now().timestamp_nanos(),
+        )),
+        chain_id: chain_id.to_string(),
+        safe: safe.to_string(),
+        callback_url: request.callback_url.to_string(),
+        secret: Some(request.secret.to_string()),
+    };
+    cache
+        .insert_in_hash(
+            &subscriptions_key(chain_id, &safe),
+            &subscription.id,
+            &serde_json::to_string(&subscription)?,
+        )
+        .await;
+    Ok(Subscription {
+        secret: None,
+        ..subscription
+    })
+}
+
+pub async fn get_subscriptions(
+    cache: &dyn Cache,
+    chain_id: &str,
+    safe: &str,
+) -> ApiResult<Vec<Subscription>> {
+    Ok(load_subscriptions(cache, chain_id, &normalize_safe(safe)?)
+        .await
+        .into_iter()
+        .map(|subscription| Subscription {
+            secret: None,
+            ..subscription
+        })
+        .collect())
+}
+
+pub async fn unsubscribe(
+    cache: &dyn Cache,
+    chain_id: &str,
+    safe: &str,
+    subscription_id: &str,
+) -> ApiResult<()> {
+    let safe = normalize_safe(safe)?;
+    load_subscription(cache, chain_id, &safe, subscription_id).await?;
+    cache
+        .remove_from_hash(&subscriptions_key(chain_id, &safe), subscription_id)
+        .await;
+    cache.invalidate(&deliveries_key(subscription_id)).await;
+    Ok(())
+}
+
+pub async fn get_deliveries(
+    cache: &dyn Cache,
+    chain_id: &str,
+    safe: &str,
+    subscription_id: &str,
+) -> ApiResult<Vec<Delivery>> {
+    load_subscription(cache, chain_id, &normalize_safe(safe)?, subscription_id).await?;
+    let mut deliveries: Vec<Delivery> = cache
+        .get_all_from_hash(&deliveries_key(subscription_id))
+        .await
+        .values()
+        .filter_map(|delivery| serde_json::from_str(delivery).ok())
+        .collect();
+    deliveries.sort_by(|a, b| b.created.cmp(&a.created));
+    Ok(deliveries)
+}
+
+pub async fn get_delivery(
+    cache: &dyn Cache,
+    chain_id: &str,
+    safe: &str,
+    subscription_id: &str,
+    delivery_id: &str,
+) -> ApiResult<Delivery> {
+    load_subscription(cache, chain_id, &normalize_safe(safe)?, subscription_id).await?;
+    load_delivery(cache, subscription_id, delivery_id)
+        .await
+        .ok_or(client_error!(404, "Delivery not found"))
+}
+
+// Stores a delivery for every subscription of the safe on the chain of the payload, they are sent
+// by the delivery worker. Payloads without chain are not delivered.
+pub async fn enqueue_deliveries(cache: &dyn Cache, payload: &Payload) -> ApiResult<()> {
+    let details = match &payload.details {
+        Some(PayloadDetails::Unknown) | None => return Ok(()),
+        Some(details) => details,
+    };
+    let (chain_id, safe) = match (&payload.chain_id, to_checksum_address(&payload.address)) {
+        (Some(chain_id), Some(safe)) => (chain_id, safe),
+        _ => return Ok(()),
+    };
+    let event = serde_json::to_value(&SafeEvent {
+        address: &payload.address,
+        details,
+    })?;
+    let now = Utc::now();
+    for subscription in load_subscriptions(cache, chain_id, &safe).await {
+        let delivery = Delivery {
+            id: hex_hash(&(&subscription.id, event.to_string(), now.timestamp_nanos())),
+            subscription_id: subscription.id.to_string(),
+            chain_id: chain_id.to_string(),
+            safe: safe.to_string(),
+            payload: event.clone(),
+            status: DeliveryStatus::Pending,
+            attempts: 0,
+            created: now.timestamp(),
+            next_attempt: Some(now.timestamp()),
+            last_status_code: None,
+            last_error: None,
+        };
+        store_delivery(cache, &delivery).await?;
+        cache
+            .insert_in_hash(PENDING_DELIVERIES_KEY, &delivery.id, &subscription.id)
+            .await;
+    }
+    Ok(())
+}
+
+pub async fn process_pending_deliveries(
+    cache: &dyn Cache,
+    client: &reqwest::Client,
+) -> ApiResult<()> {
+    let lock_timeout = (webhook_delivery_timeout() / 1000 + 1) as usize;
+    for (delivery_id, subscription_id) in cache.get_all_from_hash(PENDING_DELIVERIES_KEY).await {
+        // Other instances process the same queue
+        let lock_key = format!("{}_{}", DELIVERY_LOCK_KEY, &delivery_id);
+        if !cache.lock(&lock_key, lock_timeout).await {
+            continue;
+        }
+        let result = process_pending_delivery(cache, client, &subscription_id, &delivery_id).await;
+        cache.unlock(&lock_key).await;
+        // A failing delivery doesn't hold back the other ones
+        if let Err(err) = result {
+            log::error!("Webhook delivery {} failed: {:?}", &delivery_id, err);
+        }
+    }
+    Ok(())
+}
+
+async fn process_pending_delivery(
+    cache: &dyn Cache,
+    client: &reqwest::Client,
+    subscription_id: &str,
+    delivery_id: &str,
+) -> ApiResult<()> {
+    let delivery = load_delivery(cache, subscription_id, delivery_id).await;
+    let subscription = match &delivery {
+        Some(delivery) => {
+            load_subscription(cache, &delivery.chain_id, &delivery.safe, subscription_id)
+                .await
+                .ok()
+        }
+        None => None,
+    };
+    let (mut delivery, subscription) = match (delivery, subscription) {
+        (Some(delivery), Some(subscription)) if delivery.status == DeliveryStatus::Pending => {
+            (delivery, subscription)
+        }
+        _ => {
+            cache
+                .remove_from_hash(PENDING_DELIVERIES_KEY, delivery_id)
+                .await;
+            return Ok(());
+        }
+    };
+    if delivery
+        .next_attempt
+        .map_or(false, |next_attempt| next_attempt > Utc::now().timestamp())
+    {
+        return Ok(());
+    }
+
+    attempt_delivery(client, &subscription, &mut delivery).await?;
+    store_delivery(cache, &delivery).await?;
+    if delivery.status != DeliveryStatus::Pending {
+        cache
+            .remove_from_hash(PENDING_DELIVERIES_KEY, delivery_id)
+            .await;
+    }
+    Ok(())
+}
+
+async fn attempt_delivery(
+    client: &reqwest::Client,
+    subscription: &Subscription,
+    delivery: &mut Delivery,
+) -> ApiResult<()> {
+    delivery.attempts += 1;
+    // The host could resolve to another address than when subscribing
+    let callback_url = parse_callback_url(&subscription.callback_url);
+    match callback_url.as_ref().map(resolves_to_public_addresses) {
+        Ok(Ok(true)) => {}
+        Ok(Err(err)) => {
+            delivery.last_status_code = None;
+            delivery.last_error = Some(err.to_string());
+            schedule_retry(delivery);
+            return Ok(());
+        }
+        _ => {
+            delivery.status = DeliveryStatus::Failed;
+            delivery.next_attempt = None;
+            delivery.last_status_code = None;
+            delivery.last_error = Some(String::from("Callback url is not public"));
+            return Ok(());
+        }
+    }
+
+    let body = delivery.payload.to_string();
+    let signature = sign(subscription.secret.as_deref().unwrap_or_default(), &body)?;
+    let response = client
+        .post(&subscription.callback_url)
+        .timeout(Duration::from_millis(webhook_delivery_timeout()))
+        .header(CONTENT_TYPE, "application/json")
+        .header(SIGNATURE_HEADER, signature)
+        .header(DELIVERY_HEADER, &delivery.id)
+        .body(body)
+        .send()
+        .await;
+
+    match response {
+        Ok(response) if response.status().is_success() => {
+            delivery.status = DeliveryStatus::Delivered;
+            delivery.next_attempt = None;
+            delivery.last_status_code = Some(response.status().as_u16());
+            delivery.last_error = None;
+            return Ok(());
+        }
+        Ok(response) => {
+            delivery.last_status_code = Some(response.status().as_u16());
+            delivery.last_error = None;
+        }
+        Err(err) => {
+            delivery.last_status_code = None;
+            delivery.last_error = Some(err.to_string());
+        }
+    }
+    schedule_retry(delivery);
+    Ok(())
+}
+
+fn schedule_retry(delivery: &mut Delivery) {
+    if delivery.attempts >= webhook_delivery_max_attempts() {
+        delivery.status = DeliveryStatus::Failed;
+        delivery.next_attempt = None;
+    } else {
+        let backoff = (webhook_delivery_backoff() as i64)
+            .saturating_mul(2i64.saturating_pow(delivery.attempts as u32 - 1));
+        delivery.next_attempt = Some(Utc::now().timestamp().saturating_add(backoff));
+    }
+}
+
+// Deliveries are sent from within the gateway network, so only https urls of public hosts are
+// accepted (no internal hosts, cloud metadata or redis)
+fn parse_callback_url(callback_url: &str) -> ApiResult<reqwest::Url> {
+    let url = reqwest::Url::parse(callback_url)
+        .map_err(|_| client_error!(422, "Invalid callback url"))?;
+    if url.scheme() != "https" || url.host_str().is_none() {
+        return Err(client_error!(422, "Callback url has to be an https url"));
+    }
+    Ok(url)
+}
+
+fn resolves_to_public_addresses(url: &reqwest::Url) -> io::Result<bool> {
+    let addresses = url.socket_addrs(|| Some(443))?;
+    Ok(!addresses.is_empty()
+        && addresses
+            .iter()
+            .all(|address| is_public_address(&address.ip())))
+}
+
+fn is_public_address(address: &IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => is_public_ipv4(address),
+        // IPv4 mapped (::ffff:a.b.c.d) and compatible (::a.b.c.d) addresses
+        IpAddr::V6(address) => match address.to_ipv4() {
+            Some(address) => is_public_ipv4(&address),
+            None => is_public_ipv6(address),
+        },
+    }
+}
+
+fn is_public_ipv4(address: &Ipv4Addr) -> bool {
+    let octets = address.octets();
+    !(address.is_private()
+        || address.is_loopback()
+        || address.is_link_local()
+        || address.is_unspecified()
+        || address.is_broadcast()
+        || address.is_multicast()
+        || address.is_documentation()
+        // 0.0.0.0/8 "this network" and 100.64.0.0/10 shared address space (carrier grade NAT)
+        || octets[0] == 0
+        || (octets[0] == 100 && (octets[1] & 0xc0) == 64))
+}
+
+fn is_public_ipv6(address: &Ipv6Addr) -> bool {
+    let first_segment = address.segments()[0];
+    !(address.is_loopback()
+        || address.is_unspecified()
+        || address.is_multicast()
+        // fc00::/7 unique local and fe80::/10 link local addresses
+        || (first_segment & 0xfe00) == 0xfc00
+        || (first_segment & 0xffc0) == 0xfe80)
+}
+
+// Hex encoded HMAC-SHA256 of the body, sent in the `X-Gateway-Signature` header
+pub fn sign(secret: &str, body: &str) -> ApiResult<String> {
+    let mut mac = Hmac::<Sha256>::new_varkey(secret.as_bytes())
+        .map_err(|_| api_error!("Invalid webhook secret"))?;
+    mac.update(body.as_bytes());
+    Ok(to_hex_string!(mac.finalize().into_bytes()))
+}
+
+// The redis connection is kept between polls and only reopened once it is lost. The worker runs
+// the deliveries on a runtime of its own, the one of the requests is not used outside of them.
+pub fn start_delivery_worker(client: reqwest::Client, redis_url: String) {
+    thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(err) => {
+                log::error!("Could not start the webhook delivery worker: {}", err);
+                return;
+            }
+        };
+        let mut cache: Option<RedisCache> = None;
+        loop {
+            if !cache.as_ref().map_or(false, RedisCache::is_open) {
+                cache = RedisCache::open(&redis_url)
+                    .map_err(|err| log::error!("Could not connect to redis: {}", err))
+                    .ok();
+            }
+            if let Some(cache) = cache.as_ref() {
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    runtime.block_on(process_pending_deliveries(cache, &client))
+                }));
+                match result {
+                    Ok(Err(err)) => log::error!("Webhook delivery failed: {:?}", err),
+                    Err(_) => log::error!("Webhook delivery panicked"),
+                    Ok(Ok(_)) => {}
+                }
+            }
+            thread::sleep(DELIVERY_POLL_INTERVAL);
+        }
+    });
+}
+
+// Webhooks of the transaction service contain checksummed addresses
+fn normalize_safe(safe: &str) -> ApiResult<String> {
+    to_checksum_address(safe).ok_or_else(|| client_error!(422, "Invalid safe address"))
+}
+
+fn subscriptions_key(chain_id: &str, safe: &str) -> String {
+    format!("{}_{}_{}", SUBSCRIPTIONS_KEY, chain_id, safe)
+}
+
+fn deliveries_key(subscription_id: &str) -> String {
+    format!("{}_{}", DELIVERIES_KEY, subscription_id)
+}
+
+async fn load_subscriptions(cache: &dyn Cache, chain_id: &str, safe: &str) -> Vec<Subscription> {
+    let mut subscriptions: Vec<Subscription> = cache
+        .get_all_from_hash(&subscriptions_key(chain_id, safe))
+        .await
+        .values()
+        .filter_map(|subscription| serde_json::from_str(subscription).ok())
+        .collect();
+    subscriptions.sort_by(|a, b| a.id.cmp(&b.id));
+    subscriptions
+}
+
+async fn load_subscription(
+    cache: &dyn Cache,
+    chain_id: &str,
+    safe: &str,
+    subscription_id: &str,
+) -> ApiResult<Subscription> {
+    cache
+        .get_from_hash(&subscriptions_key(chain_id, safe), subscription_id)
+        .await
+        .and_then(|subscription| serde_json::from_str(&subscription).ok())
+        .ok_or(client_error!(404, "Subscription not found"))
+}
+
+async fn load_delivery(
+    cache: &dyn Cache,
+    subscription_id: &str,
+    delivery_id: &str,
+) -> Option<Delivery> {
+    cache
+        .get_from_hash(&deliveries_key(subscription_id), delivery_id)
+        .await
+        .and_then(|delivery| serde_json::from_str(&delivery).ok())
+}
+
+async fn store_delivery(cache: &dyn Cache, delivery: &Delivery) -> ApiResult<()> {
+    let deliveries_key = deliveries_key(&delivery.subscription_id);
+    cache
+        .insert_in_hash(
+            &deliveries_key,
+            &delivery.id,
+            &serde_json::to_string(&delivery)?,
+        )
+        .await;
+    cache
+        .expire_entity(&deliveries_key, webhook_deliveries_duration())
+        .await;
+    Ok(())
+}
@@ -1,42 +1,68 @@
 use rocket::http::uri::Origin;
+use rocket::http::Status;
 use rocket::request::{self, FromRequest, Request};
 use rocket::Outcome;
 use rocket::State;
+use std::future::Future;
+use tokio::runtime::Runtime;
 
+use crate::cache::layered::LayeredCache;
+use crate::cache::memory::InMemoryCache;
 use crate::cache::redis::ServiceCache;
 use crate::cache::Cache;
-use crate::config::scheme;
+use crate::config::{gateway_host, in_memory_cache, local_cache, scheme};
+use crate::utils::errors::{ApiError, ApiResult};
 
 pub struct Context<'a, 'r> {
     request: &'a Request<'r>,
-    cache: ServiceCache,
+    client: &'r reqwest::Client,
+    runtime: &'r Runtime,
+    cache: Box<dyn Cache>,
 }
 
 impl<'a, 'r> Context<'a, 'r> {
-    fn get<T: FromRequest<'a, 'r>>(&self) -> T {
-        self.request.guard::<T>().unwrap()
+    pub fn client(&self) -> &'r reqwest::Client {
+        self.client
     }
 
-    pub fn client(&self) -> &'r reqwest::blocking::Client {
-        self.get::<State<reqwest::blocking::Client>>().inner()
+    // Runs the async request path on the thread of the handler, the futures of one request are
+    // polled concurrently while the IO is driven by the workers of the shared runtime
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
     }
 
-    pub fn cache(&self) -> &impl Cache {
-        &self.cache
+    pub fn cache(&self) -> &dyn Cache {
+        self.cache.as_ref()
+    }
+
+    // Redis connection for data that must not be evicted, whatever cache is configured
+    pub fn persistent_cache(&self) -> ApiResult<Box<dyn Cache>> {
+        self.request
+            .guard::<ServiceCache>()
+            .succeeded()
+            .map(|cache| Box::new(cache) as Box<dyn Cache>)
+            .ok_or_else(|| {
+                ApiError::new_from_message_with_code(503, String::from("Redis is not available"))
+            })
     }
 
     pub fn uri(&self) -> String {
         self.request.uri().to_string()
     }
 
-    pub fn build_absolute_url(&self, origin: Origin) -> String {
-        format!("{}{}", self.host().unwrap(), origin)
+    pub fn build_absolute_url(&self, origin: Origin) -> ApiResult<String> {
+        let host = self
+            .host()
+            .ok_or_else(|| client_error!(400, "Missing Host header"))?;
+        Ok(format!("{}{}", host, origin))
     }
 
     fn host(&self) -> Option<String> {
         self.request
             .headers()
             .get_one("Host")
+            .map(String::from)
+            .or_else(gateway_host)
             .map(|host| format!("{}://{}", scheme(), host))
     }
 }
@@ -44,8 +70,45 @@ impl<'a, 'r> Context<'a, 'r> {
 impl<'a, 'r> FromRequest<'a, 'r> for Context<'a, 'r> {
     type Error = ();
 
+    // Fails with a 503 instead of panicking when no cache connection can be acquired (e.g. redis
+    // is down), the catcher turns it into a JSON error
     fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
-        let cache = request.guard().unwrap();
-        return Outcome::Success(Context { request, cache });
+        let client = match request.guard::<State<reqwest::Client>>() {
+            Outcome::Success(client) => client.inner(),
+            _ => return Outcome::Failure((Status::InternalServerError, ())),
+        };
+        let runtime = match request.guard::<State<Runtime>>() {
+            Outcome::Success(runtime) => runtime.inner(),
+            _ => return Outcome::Failure((Status::InternalServerError, ())),
+        };
+        match request_cache(request) {
+            Some(cache) => Outcome::Success(Context {
+                request,
+                client,
+                runtime,
+                cache,
+            }),
+            None => Outcome::Failure((Status::ServiceUnavailable, ())),
+        }
     }
 }
+
+fn request_cache(request: &Request) -> Option<Box<dyn Cache>> {
+    let in_memory = || {
+        request
+            .guard::<State<InMemoryCache>>()
+            .succeeded()
+            .map(|cache| cache.inner().clone())
+    };
+    let cache: Box<dyn Cache> = if in_memory_cache() {
+        Box::new(in_memory()?)
+    } else if local_cache() {
+        Box::new(LayeredCache::new(
+            in_memory()?,
+            request.guard::<ServiceCache>().succeeded()?,
+        ))
+    } else {
+        Box::new(request.guard::<ServiceCache>().succeeded()?)
+    };
+    Some(cache)
+}
@@ -0,0 +1,251 @@
+use crate::models::commons::{
+    DataDecoded, InternalTransaction, Operation, ParamValue, Parameter, ValueDecodedType,
+};
+use crate::providers::info::InfoProvider;
+use crate::utils::{MULTI_SEND, MULTI_SEND_TRANSACTIONS_PARAM};
+use ethabi::{Contract, Function, Param, ParamType, Token, Uint};
+use ethcontract_common::hash::keccak256;
+use futures::future::{FutureExt, LocalBoxFuture};
+use lazy_static::lazy_static;
+use serde_json::Value;
+use std::collections::HashMap;
+
+// Registered in order, the first function found for a selector wins
+const BUNDLED_ABIS: &[&str] = &[
+    include_str!("abis/safe.json"),
+    include_str!("abis/erc20.json"),
+    include_str!("abis/erc721.json"),
+    include_str!("abis/erc1155.json"),
+    include_str!("abis/multi_send.json"),
+    include_str!("abis/defi.json"),
+];
+
+// operation (1 byte), to (20 bytes), value (32 bytes) and data length (32 bytes)
+const MULTI_SEND_HEADER_LENGTH: usize = 85;
+// MultiSend transactions can call MultiSend again, deeper batches are left undecoded
+const MAX_MULTI_SEND_DEPTH: usize = 3;
+
+lazy_static! {
+    static ref BUNDLED_FUNCTIONS: HashMap<[u8; 4], Function> = functions_by_selector(
+        BUNDLED_ABIS
+            .iter()
+            .filter_map(|abi| serde_json::from_str::<Value>(abi).ok())
+            .filter_map(|abi| parse_abi(&abi))
+            .collect::<Vec<Contract>>()
+            .iter()
+    );
+}
+
+// Decodes the `data` sent to `to` with the bundled ABIs, falling back to the ABI the transaction
+// service knows for `to`. Mirrors the `dataDecoded` of the transaction service.
+pub async fn decode_data(
+    info_provider: &mut dyn InfoProvider,
+    to: &str,
+    data: &str,
+) -> Option<DataDecoded> {
+    let data = hex::decode(data.trim_start_matches("0x")).ok()?;
+    decode_bytes(info_provider, to, &data, 0).await
+}
+
+// Whether decoding `data` requires the ABI of the called contract (none of the bundled ABIs has
+// the function)
+pub fn needs_contract_abi(data: &str) -> bool {
+    match hex::decode(data.trim_start_matches("0x")) {
+        Ok(data) if data.len() >= 4 => {
+            let mut selector = [0u8; 4];
+            selector.copy_from_slice(&data[..4]);
+            !BUNDLED_FUNCTIONS.contains_key(&selector)
+        }
+        _ => false,
+    }
+}
+
+// Only functions are used for decoding, other entries (e.g. `receive`) are unknown to ethabi
+pub fn parse_abi(abi: &Value) -> Option<Contract> {
+    let functions: Vec<Value> = abi
+        .as_array()?
+        .iter()
+        .filter(|entry| entry.get("type").and_then(Value::as_str) == Some("function"))
+        .cloned()
+        .collect();
+    serde_json::from_value(Value::Array(functions)).ok()
+}
+
+// EIP-55 mixed case encoding of the address
+pub fn checksum_address(address: &[u8]) -> String {
+    let address = hex::encode(address);
+    let hash = keccak256(address.as_bytes());
+    let checksummed: String = address
+        .chars()
+        .enumerate()
+        .map(|(index, character)| {
+            let nibble = if index % 2 == 0 {
+                hash[index / 2] >> 4
+            } else {
+                hash[index / 2] & 0x0f
+            };
+            if nibble >= 8 {
+                character.to_ascii_uppercase()
+            } else {
+                character
+            }
+        })
+        .collect();
+    format!("0x{}", checksummed)
+}
+
+// `None` if `address` is not a hex encoded address
+pub fn to_checksum_address(address: &str) -> Option<String> {
+    let address = hex::decode(address.strip_prefix("0x")?).ok()?;
+    if address.len() != 20 {
+        return None;
+    }
+    Some(checksum_address(&address))
+}
+
+fn functions_by_selector<'c>(
+    contracts: impl Iterator<Item = &'c Contract>,
+) -> HashMap<[u8; 4], Function> {
+    let mut functions = HashMap::new();
+    for contract in contracts {
+        for function in contract.functions() {
+            functions
+                .entry(function.short_signature())
+                .or_insert_with(|| function.clone());
+        }
+    }
+    functions
+}
+
+// Boxed as it is called recursively for the transactions of a multiSend
+fn decode_bytes<'a>(
+    info_provider: &'a mut dyn InfoProvider,
+    to: &'a str,
+    data: &'a [u8],
+    depth: usize,
+) -> LocalBoxFuture<'a, Option<DataDecoded>> {
+    async move {
+        if data.len() < 4 {
+            return None;
+        }
+        let mut selector = [0u8; 4];
+        selector.copy_from_slice(&data[..4]);
+        let function = match BUNDLED_FUNCTIONS.get(&selector) {
+            Some(function) => function.clone(),
+            None => {
+                let contract = info_provider.contract_abi(to).await.ok()??;
+                functions_by_selector(std::iter::once(&contract)).remove(&selector)?
+            }
+        };
+        let tokens = function.decode_input(&data[4..]).ok()?;
+        let mut parameters = vec![];
+        for (param, token) in function.inputs.iter().zip(tokens.into_iter()) {
+            parameters.push(to_parameter(info_provider, &function.name, param, token, depth).await);
+        }
+        Some(DataDecoded {
+            method: function.name.to_owned(),
+            parameters: Some(parameters),
+        })
+    }
+    .boxed_local()
+}
+
+async fn to_parameter(
+    info_provider: &mut dyn InfoProvider,
+    method: &str,
+    param: &Param,
+    token: Token,
+    depth: usize,
+) -> Parameter {
+    let value_decoded = match &token {
+        Token::Bytes(transactions)
+            if method == MULTI_SEND
+                && param.name == MULTI_SEND_TRANSACTIONS_PARAM
+                && depth < MAX_MULTI_SEND_DEPTH =>
+        {
+            decode_multi_send(info_provider, transactions, depth + 1)
+                .await
+                .map(ValueDecodedType::InternalTransaction)
+        }
+        _ => None,
+    };
+    Parameter {
+        name: param.name.to_owned(),
+        param_type: param_type_name(&param.kind),
+        value: to_param_value(token),
+        value_decoded,
+    }
+}
+
+// Tuples are reported as `tuple` like the transaction service does
+fn param_type_name(kind: &ParamType) -> String {
+    match kind {
+        ParamType::Tuple(_) => String::from("tuple"),
+        ParamType::Array(inner) => format!("{}[]", param_type_name(inner)),
+        ParamType::FixedArray(inner, size) => format!("{}[{}]", param_type_name(inner), size),
+        _ => kind.to_string(),
+    }
+}
+
+fn to_param_value(token: Token) -> ParamValue {
+    match token {
+        Token::Address(address) => ParamValue::SingleValue(checksum_address(address.as_bytes())),
+        Token::Bytes(bytes) | Token::FixedBytes(bytes) => {
+            ParamValue::SingleValue(to_hex_string!(bytes))
+        }
+        Token::Uint(value) => ParamValue::SingleValue(value.to_string()),
+        Token::Int(value) => ParamValue::SingleValue(signed_to_string(value)),
+        Token::Bool(value) => ParamValue::SingleValue(value.to_string()),
+        Token::String(value) => ParamValue::SingleValue(value),
+        Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+            ParamValue::ArrayValue(tokens.into_iter().map(to_param_value).collect())
+        }
+    }
+}
+
+// `Int` tokens are the two's complement of the value
+fn signed_to_string(value: Uint) -> String {
+    if value.bit(255) {
+        format!("-{}", (!value).overflowing_add(Uint::one()).0)
+    } else {
+        value.to_string()
+    }
+}
+
+// The transactions of a multiSend are packed one after the other
+async fn decode_multi_send(
+    info_provider: &mut dyn InfoProvider,
+    transactions: &[u8],
+    depth: usize,
+) -> Option<Vec<InternalTransaction>> {
+    let mut internal_transactions = vec![];
+    let mut position = 0;
+    while position < transactions.len() {
+        let header = transactions.get(position..position + MULTI_SEND_HEADER_LENGTH)?;
+        let operation = match header[0] {
+            0 => Operation::CALL,
+            1 => Operation::DELEGATE,
+            _ => return None,
+        };
+        let data_length = Uint::from_big_endian(&header[53..85]);
+        if data_length > Uint::from(transactions.len()) {
+            return None;
+        }
+        let data_start = position + MULTI_SEND_HEADER_LENGTH;
+        let data = transactions.get(data_start..data_start + data_length.as_usize())?;
+        let to = checksum_address(&header[1..21]);
+        internal_transactions.push(InternalTransaction {
+            operation,
+            value: Some(Uint::from_big_endian(&header[21..53]).to_string()),
+            data: if data.is_empty() {
+                None
+            } else {
+                Some(to_hex_string!(data))
+            },
+            data_decoded: decode_bytes(info_provider, &to, data, depth).await,
+            to,
+        });
+        position = data_start + data.len();
+    }
+    Some(internal_transactions)
+}
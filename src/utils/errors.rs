@@ -1,5 +1,5 @@
 use crate::config::log_all_error_responses;
-use reqwest::blocking::Response as ReqwestResponse;
+use reqwest::Response as ReqwestResponse;
 use rocket::http::{ContentType, Status};
 use rocket::request::Request;
 use rocket::response::{self, Responder, Response};
@@ -40,11 +40,9 @@ impl ApiError {
         Self::new(status_code, error_details)
     }
 
-    pub fn from_http_response(response: ReqwestResponse, default_message: String) -> Self {
-        Self::new_from_message_with_code(
-            response.status().as_u16(),
-            response.text().unwrap_or(default_message),
-        )
+    pub async fn from_http_response(response: ReqwestResponse, default_message: String) -> Self {
+        let status = response.status().as_u16();
+        Self::new_from_message_with_code(status, response.text().await.unwrap_or(default_message))
     }
 
     pub fn new_from_message(message: impl Into<String>) -> Self {
@@ -69,7 +67,7 @@ impl ApiError {
         )
     }
 
-    fn new(status_code: u16, message: ErrorDetails) -> Self {
+    pub fn new(status_code: u16, message: ErrorDetails) -> Self {
         Self {
             status: status_code,
             details: message,
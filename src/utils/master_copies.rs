@@ -0,0 +1,192 @@
+use crate::models::commons::Operation;
+use crate::models::service::safes::ImplementationVersionState;
+use crate::utils::transactions::parse_version;
+use ethabi::{Token, Uint};
+use ethcontract_common::hash::keccak256;
+use ethereum_types::Address;
+
+// MultiSend v1.1.1, used by the clients to batch the upgrade of the Safes before v1.3.0
+pub const MIGRATION_MULTI_SEND: &str = "0x8D29bE29923b68abfDD21e541b9374737B49cdAD";
+
+const CHANGE_MASTER_COPY_SIGNATURE: &str = "changeMasterCopy(address)";
+const SET_FALLBACK_HANDLER_SIGNATURE: &str = "setFallbackHandler(address)";
+const MULTI_SEND_SIGNATURE: &str = "multiSend(bytes)";
+
+// Safes before v1.0.0 sign a different EIP-712 struct (`dataGas`), pending signatures would break
+const MIN_UPGRADABLE_VERSION: (u64, u64, u64) = (1, 0, 0);
+
+#[derive(Debug, PartialEq)]
+pub struct MasterCopy {
+    pub address: &'static str,
+    pub version: &'static str,
+    // Emits the events the transaction service indexes L2 chains with, instead of tracing
+    pub l2: bool,
+    // Deployed with EIP-155 replay protection, for the chains that don't accept the canonical ones
+    pub eip155: bool,
+    // Handler deployed with the master copy, set along with it on upgrades
+    pub fallback_handler: Option<&'static str>,
+}
+
+// Deployed at the same addresses on every chain, Safes are upgraded to the latest master copy of
+// their kind (L1 or L2) deployed the way the chain requires (see `ChainInfo::eip155_deployments`)
+pub const MASTER_COPIES: &[MasterCopy] = &[
+    MasterCopy {
+        address: "0x8942595A2dC5181Df0465AF0D7be08c8f23C93af",
+        version: "0.1.0",
+        l2: false,
+        eip155: false,
+        fallback_handler: None,
+    },
+    MasterCopy {
+        address: "0xb6029EA3B2c51D09a50B53CA8012FeEB05bDa35A",
+        version: "1.0.0",
+        l2: false,
+        eip155: false,
+        fallback_handler: None,
+    },
+    MasterCopy {
+        address: "0xaE32496491b53841efb51829d6f886387708F99B",
+        version: "1.1.0",
+        l2: false,
+        eip155: false,
+        fallback_handler: None,
+    },
+    MasterCopy {
+        address: "0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F",
+        version: "1.1.1",
+        l2: false,
+        eip155: false,
+        fallback_handler: Some("0xd5D82B6aDDc9027B22dCA772Aa68D5d74cdBdF44"),
+    },
+    MasterCopy {
+        address: "0x6851D6fDFAfD08c0295C392436245E5bc78B0185",
+        version: "1.2.0",
+        l2: false,
+        eip155: false,
+        fallback_handler: Some("0xd5D82B6aDDc9027B22dCA772Aa68D5d74cdBdF44"),
+    },
+    MasterCopy {
+        address: "0xd9Db270c1B5E3Bd161E8c8503c55cEABeE709552",
+        version: "1.3.0",
+        l2: false,
+        eip155: false,
+        fallback_handler: Some("0xf48f2B2d2a534e402487b3ee7C18c33Aec0Fe5e4"),
+    },
+    MasterCopy {
+        address: "0x3E5c63644E683549055b9Be8653de26E0B4CD36E",
+        version: "1.3.0",
+        l2: true,
+        eip155: false,
+        fallback_handler: Some("0xf48f2B2d2a534e402487b3ee7C18c33Aec0Fe5e4"),
+    },
+    // Deployments of v1.3.0 with EIP-155 replay protection, for chains that enforce it
+    MasterCopy {
+        address: "0x69f4D1788e39c87893C980c06EdF4b7f686e2938",
+        version: "1.3.0",
+        l2: false,
+        eip155: true,
+        fallback_handler: Some("0x017062a1dE2FE6b99BE3d9d37841FeD19F573804"),
+    },
+    MasterCopy {
+        address: "0xfb1bffC9d739B8D520DaF37dF666da4C687191EA",
+        version: "1.3.0",
+        l2: true,
+        eip155: true,
+        fallback_handler: Some("0x017062a1dE2FE6b99BE3d9d37841FeD19F573804"),
+    },
+];
+
+pub fn master_copy(address: &str) -> Option<&'static MasterCopy> {
+    MASTER_COPIES
+        .iter()
+        .find(|master_copy| master_copy.address.eq_ignore_ascii_case(address))
+}
+
+// Latest L1 or L2 master copy of the canonical or the EIP-155 deployments, the first one
+// registered for its version
+pub fn latest_master_copy(l2: bool, eip155: bool) -> Option<&'static MasterCopy> {
+    MASTER_COPIES
+        .iter()
+        .filter(|master_copy| master_copy.l2 == l2 && master_copy.eip155 == eip155)
+        .fold(
+            None,
+            |latest: Option<&'static MasterCopy>, candidate| match latest {
+                Some(latest)
+                    if parse_version(latest.version) >= parse_version(candidate.version) =>
+                {
+                    Some(latest)
+                }
+                _ => Some(candidate),
+            },
+        )
+}
+
+pub fn version_state(master_copy_address: &str) -> ImplementationVersionState {
+    let master_copy = match master_copy(master_copy_address) {
+        Some(master_copy) => master_copy,
+        None => return ImplementationVersionState::Unknown,
+    };
+    match latest_master_copy(master_copy.l2, master_copy.eip155) {
+        Some(latest) if parse_version(master_copy.version) < parse_version(latest.version) => {
+            ImplementationVersionState::Outdated
+        }
+        _ => ImplementationVersionState::UpToDate,
+    }
+}
+
+// Master copy the Safe can be upgraded to, if it is outdated and not too old to be upgraded.
+// `eip155` selects the deployments of chains that require replay protected transactions.
+pub fn upgrade_target(master_copy_address: &str, eip155: bool) -> Option<&'static MasterCopy> {
+    let master_copy = master_copy(master_copy_address)?;
+    let version = parse_version(master_copy.version)?;
+    let latest = latest_master_copy(master_copy.l2, eip155)?;
+    if version < MIN_UPGRADABLE_VERSION || parse_version(latest.version)? <= version {
+        return None;
+    }
+    Some(latest)
+}
+
+// `multiSend` call (to be delegate called by the Safe) changing the master copy of the Safe and
+// setting the fallback handler of the new version. The Safe calls itself for both changes.
+pub fn migration_data(safe: Address, target: &MasterCopy) -> Vec<u8> {
+    let mut transactions = vec![];
+    transactions.extend(multi_send_transaction(
+        safe,
+        &call_data(CHANGE_MASTER_COPY_SIGNATURE, parse_address(target.address)),
+    ));
+    if let Some(fallback_handler) = target.fallback_handler {
+        transactions.extend(multi_send_transaction(
+            safe,
+            &call_data(
+                SET_FALLBACK_HANDLER_SIGNATURE,
+                parse_address(fallback_handler),
+            ),
+        ));
+    }
+    let mut data = keccak256(MULTI_SEND_SIGNATURE)[..4].to_vec();
+    data.extend(ethabi::encode(&[Token::Bytes(transactions)]));
+    data
+}
+
+fn call_data(signature: &str, address: Address) -> Vec<u8> {
+    let mut data = keccak256(signature)[..4].to_vec();
+    data.extend(ethabi::encode(&[Token::Address(address)]));
+    data
+}
+
+// Packed operation, to, value, data length and data, without value for the calls of the Safe
+fn multi_send_transaction(to: Address, data: &[u8]) -> Vec<u8> {
+    let mut transaction = vec![Operation::CALL as u8];
+    transaction.extend(to.as_bytes());
+    transaction.extend(&[0u8; 32]);
+    let mut data_length = [0u8; 32];
+    Uint::from(data.len()).to_big_endian(&mut data_length);
+    transaction.extend(&data_length);
+    transaction.extend(data);
+    transaction
+}
+
+// The registry only contains valid addresses
+fn parse_address(address: &str) -> Address {
+    Address::from_slice(&hex::decode(address.trim_start_matches("0x")).unwrap_or_default())
+}
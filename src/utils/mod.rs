@@ -6,8 +6,11 @@ use std::hash::{Hash, Hasher};
 
 pub mod context;
 pub mod cors;
+pub mod data_decoder;
 pub mod errors;
 pub mod json;
+pub mod master_copies;
+pub mod signatures;
 pub mod transactions;
 pub mod urls;
 
@@ -18,6 +21,10 @@ mod tests;
 pub const TRANSFER_METHOD: &str = "transfer";
 pub const ERC20_TRANSFER_METHODS: &[&str] = &[TRANSFER_METHOD, "transferFrom"];
 pub const ERC721_TRANSFER_METHODS: &[&str] = &[TRANSFER_METHOD, "transferFrom", "safeTransferFrom"];
+pub const ERC1155_SAFE_TRANSFER_FROM: &str = "safeTransferFrom";
+pub const ERC1155_SAFE_BATCH_TRANSFER_FROM: &str = "safeBatchTransferFrom";
+pub const APPROVE_METHOD: &str = "approve";
+pub const SET_APPROVAL_FOR_ALL_METHOD: &str = "setApprovalForAll";
 
 pub const SET_FALLBACK_HANDLER: &'static str = "setFallbackHandler";
 pub const ADD_OWNER_WITH_THRESHOLD: &'static str = "addOwnerWithThreshold";
@@ -98,6 +105,36 @@ impl DataDecoded {
         })
     }
 
+    // Single values are returned as a list of one value
+    pub fn get_parameter_values_at(&self, position: usize) -> Vec<String> {
+        self.parameters
+            .as_ref()
+            .and_then(|parameters| parameters.get(position))
+            .map_or(vec![], |parameter| match &parameter.value {
+                ParamValue::SingleValue(value) => vec![value.clone()],
+                ParamValue::ArrayValue(values) => values
+                    .iter()
+                    .filter_map(|value| match value {
+                        ParamValue::SingleValue(value) => Some(value.clone()),
+                        _ => None,
+                    })
+                    .collect(),
+            })
+    }
+
+    pub fn get_address_parameters(&self) -> Vec<String> {
+        self.parameters.as_ref().map_or(vec![], |parameters| {
+            parameters
+                .iter()
+                .filter(|parameter| parameter.param_type == "address")
+                .filter_map(|parameter| match &parameter.value {
+                    ParamValue::SingleValue(value) => Some(value.clone()),
+                    _ => None,
+                })
+                .collect()
+        })
+    }
+
     pub fn is_erc20_transfer_method(&self) -> bool {
         ERC20_TRANSFER_METHODS
             .iter()
@@ -110,6 +147,24 @@ impl DataDecoded {
             .any(|&value| value == self.method)
     }
 
+    // ERC721 `safeTransferFrom` has no amount and at most 4 parameters
+    pub fn is_erc1155_transfer_method(&self) -> bool {
+        self.method == ERC1155_SAFE_BATCH_TRANSFER_FROM
+            || (self.method == ERC1155_SAFE_TRANSFER_FROM
+                && self
+                    .parameters
+                    .as_ref()
+                    .map_or(false, |parameters| parameters.len() == 5))
+    }
+
+    pub fn is_approval_method(&self) -> bool {
+        (self.method == APPROVE_METHOD || self.method == SET_APPROVAL_FOR_ALL_METHOD)
+            && self
+                .parameters
+                .as_ref()
+                .map_or(false, |parameters| parameters.len() == 2)
+    }
+
     pub fn is_settings_change(&self) -> bool {
         SETTINGS_CHANGE_METHODS
             .iter()
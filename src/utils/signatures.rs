@@ -0,0 +1,130 @@
+use crate::utils::errors::{ApiError, ApiResult, ErrorDetails};
+use ethcontract_common::hash::keccak256;
+use secp256k1::recovery::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, Secp256k1};
+
+pub const INVALID_SIGNATURE_CODE: u64 = 50;
+pub const SIGNER_NOT_OWNER_CODE: u64 = 51;
+pub const SENDER_NOT_SIGNER_CODE: u64 = 53;
+
+const SIGNATURE_LENGTH: usize = 65;
+const ETH_SIGN_PREFIX: &'static [u8] = b"\x19Ethereum Signed Message:\n32";
+
+// Checks that every ECDSA signature (EIP-712 or `eth_sign`) of `safe_tx_hash` is from an owner and
+// returns the signers
+pub fn verify_owner_signatures(
+    owners: &[String],
+    safe_tx_hash: &str,
+    signatures: &str,
+) -> ApiResult<Vec<String>> {
+    let signers = recover_signers(safe_tx_hash, signatures)?;
+    for signer in &signers {
+        if !owners
+            .iter()
+            .any(|owner| owner.eq_ignore_ascii_case(&signer))
+        {
+            return Err(ApiError::new(
+                422,
+                ErrorDetails {
+                    code: SIGNER_NOT_OWNER_CODE,
+                    message: Some(String::from("Signer is not an owner of the safe")),
+                    arguments: Some(vec![signer.to_string()]),
+                },
+            ));
+        }
+    }
+    Ok(signers)
+}
+
+// The sender of a proposal has to be one of its signers, otherwise a signature of an owner could be
+// proposed on behalf of anyone
+pub fn verify_sender(sender: &str, signers: &[String]) -> ApiResult<()> {
+    if signers
+        .iter()
+        .any(|signer| signer.eq_ignore_ascii_case(sender))
+    {
+        return Ok(());
+    }
+    Err(ApiError::new(
+        422,
+        ErrorDetails {
+            code: SENDER_NOT_SIGNER_CODE,
+            message: Some(String::from("Sender did not sign the transaction")),
+            arguments: Some(vec![sender.to_string()]),
+        },
+    ))
+}
+
+// Signers of the ECDSA signatures, encoded as by the Safe contracts (r, s, v for every signature).
+// Contract signatures (v = 0) and approved hashes (v = 1) can only be checked on chain and are skipped.
+pub fn recover_signers(safe_tx_hash: &str, signatures: &str) -> ApiResult<Vec<String>> {
+    let hash = decode_hex(safe_tx_hash)
+        .filter(|hash| hash.len() == 32)
+        .ok_or_else(|| invalid_signature("Invalid safe transaction hash"))?;
+    let signatures =
+        decode_hex(signatures).ok_or_else(|| invalid_signature("Invalid signature"))?;
+    if signatures.len() < SIGNATURE_LENGTH {
+        return Err(invalid_signature("Invalid signature length"));
+    }
+    // The dynamic parts of contract signatures follow the static parts of all signatures
+    let mut static_end = signatures.len();
+    let mut offset = 0;
+    let mut signers = vec![];
+    while offset + SIGNATURE_LENGTH <= static_end {
+        let signature = &signatures[offset..offset + SIGNATURE_LENGTH];
+        match signature[64] {
+            0 => static_end = static_end.min(dynamic_offset(&signature[32..64])),
+            1 => {}
+            v @ 27..=28 => signers.push(recover(&hash, signature, v - 27)?),
+            // `eth_sign` signatures are marked by adding 4 to v
+            v @ 31..=32 => signers.push(recover(&eth_sign_hash(&hash), signature, v - 31)?),
+            _ => return Err(invalid_signature("Invalid signature type")),
+        }
+        offset += SIGNATURE_LENGTH;
+    }
+    Ok(signers)
+}
+
+fn recover(hash: &[u8], signature: &[u8], recovery_id: u8) -> ApiResult<String> {
+    let recovery_id = RecoveryId::from_i32(recovery_id as i32)
+        .map_err(|_| invalid_signature("Invalid signature recovery id"))?;
+    let signature = RecoverableSignature::from_compact(&signature[..64], recovery_id)
+        .map_err(|_| invalid_signature("Invalid signature"))?;
+    let message = Message::from_slice(hash)
+        .map_err(|_| invalid_signature("Invalid safe transaction hash"))?;
+    let public_key = Secp256k1::verification_only()
+        .recover(&message, &signature)
+        .map_err(|_| invalid_signature("Signer could not be recovered"))?;
+    let public_key_hash = keccak256(public_key.serialize_uncompressed()[1..].to_vec());
+    Ok(to_hex_string!(public_key_hash[12..]))
+}
+
+fn eth_sign_hash(hash: &[u8]) -> Vec<u8> {
+    let mut message = ETH_SIGN_PREFIX.to_vec();
+    message.extend_from_slice(hash);
+    keccak256(message).to_vec()
+}
+
+fn dynamic_offset(s: &[u8]) -> usize {
+    if s[..24].iter().any(|byte| *byte != 0) {
+        return usize::MAX;
+    }
+    s[24..]
+        .iter()
+        .fold(0u64, |offset, byte| (offset << 8) | *byte as u64) as usize
+}
+
+pub(super) fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x")).ok()
+}
+
+fn invalid_signature(message: &str) -> ApiError {
+    ApiError::new(
+        422,
+        ErrorDetails {
+            code: INVALID_SIGNATURE_CODE,
+            message: Some(message.to_string()),
+            arguments: None,
+        },
+    )
+}
@@ -161,3 +161,15 @@ fn get_action_count_non_multisig_call() {
 
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn get_address_parameters_success() {
+    let data_decoded =
+        serde_json::from_str::<DataDecoded>(crate::json::DATA_DECODED_ADD_OWNER_WITH_THRESHOLD)
+            .unwrap();
+    let expected = vec!["0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23".to_string()];
+
+    let actual = data_decoded.get_address_parameters();
+
+    assert_eq!(expected, actual);
+}
@@ -0,0 +1,233 @@
+use crate::models::commons::{
+    DataDecoded, InternalTransaction, Operation, ParamValue, Parameter, ValueDecodedType,
+};
+use crate::providers::info::MockInfoProvider;
+use crate::utils::data_decoder::{checksum_address, decode_data, needs_contract_abi, parse_abi};
+use ethabi::Token;
+use ethcontract_common::hash::keccak256;
+use mockall::predicate::*;
+
+const TOKEN_ADDRESS: &str = "0xd6f5Bef6bb4acD235CF85c0ce196316d10785d67";
+const RECEIVER: &str = "0xf2565317F3Ae8Ae9EA98E9Fe1e7FADC77F823cbD";
+const ERC20_TRANSFER_DATA: &str = "0xa9059cbb000000000000000000000000f2565317f3ae8ae9ea98e9fe1e7fadc77f823cbd00000000000000000000000000000000000000000000000000000000000003e8";
+
+fn erc20_transfer() -> DataDecoded {
+    DataDecoded {
+        method: String::from("transfer"),
+        parameters: Some(vec![
+            Parameter {
+                name: String::from("to"),
+                param_type: String::from("address"),
+                value: ParamValue::SingleValue(String::from(RECEIVER)),
+                value_decoded: None,
+            },
+            Parameter {
+                name: String::from("value"),
+                param_type: String::from("uint256"),
+                value: ParamValue::SingleValue(String::from("1000")),
+                value_decoded: None,
+            },
+        ]),
+    }
+}
+
+#[test]
+fn checksum_address_eip_55() {
+    let address = hex::decode("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap();
+
+    assert_eq!(
+        checksum_address(&address),
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+    );
+}
+
+#[tokio::test]
+async fn decode_data_bundled_erc20_transfer() {
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider.expect_contract_abi().times(0);
+
+    let actual = decode_data(&mut mock_info_provider, TOKEN_ADDRESS, ERC20_TRANSFER_DATA).await;
+
+    assert_eq!(actual, Some(erc20_transfer()));
+}
+
+#[tokio::test]
+async fn decode_data_bundled_settings_change() {
+    let data = "0x694e80c30000000000000000000000000000000000000000000000000000000000000002";
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider.expect_contract_abi().times(0);
+
+    let expected = DataDecoded {
+        method: String::from("changeThreshold"),
+        parameters: Some(vec![Parameter {
+            name: String::from("_threshold"),
+            param_type: String::from("uint256"),
+            value: ParamValue::SingleValue(String::from("2")),
+            value_decoded: None,
+        }]),
+    };
+
+    let actual = decode_data(&mut mock_info_provider, TOKEN_ADDRESS, data).await;
+
+    assert_eq!(actual, Some(expected));
+    assert!(actual.unwrap().is_settings_change());
+}
+
+#[tokio::test]
+async fn decode_data_multi_send_transactions() {
+    let data = "0x8d80ff0a000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000ee00d6f5bef6bb4acd235cf85c0ce196316d10785d6700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000044a9059cbb000000000000000000000000f2565317f3ae8ae9ea98e9fe1e7fadc77f823cbd00000000000000000000000000000000000000000000000000000000000003e8001230b3d59858296a31053c1b8562ecf89a2f888b0000000000000000000000000000000000000000000000000de0b6b3a76400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider.expect_contract_abi().times(0);
+
+    let expected_transactions = vec![
+        InternalTransaction {
+            operation: Operation::CALL,
+            to: String::from(TOKEN_ADDRESS),
+            value: Some(String::from("0")),
+            data: Some(String::from(ERC20_TRANSFER_DATA)),
+            data_decoded: Some(erc20_transfer()),
+        },
+        InternalTransaction {
+            operation: Operation::CALL,
+            to: String::from("0x1230B3d59858296A31053C1b8562Ecf89A2f888b"),
+            value: Some(String::from("1000000000000000000")),
+            data: None,
+            data_decoded: None,
+        },
+    ];
+
+    let actual = decode_data(&mut mock_info_provider, TOKEN_ADDRESS, data)
+        .await
+        .unwrap();
+
+    assert_eq!(actual.method, "multiSend");
+    assert_eq!(actual.get_action_count(), Some(2));
+    assert_eq!(
+        actual.get_parameter_value_decoded("transactions"),
+        Some(ValueDecodedType::InternalTransaction(expected_transactions))
+    );
+}
+
+// multiSend delegate calling multiSend with `data`
+fn nested_multi_send(data: Vec<u8>) -> Vec<u8> {
+    let mut transactions = vec![1u8];
+    transactions.extend(hex::decode("40a2accbd92bca938b02010e17a5b8929b49130d").unwrap());
+    transactions.extend([0u8; 32].iter());
+    transactions.extend(ethabi::encode(&[Token::Uint(data.len().into())]));
+    transactions.extend(data);
+    let mut multi_send = keccak256("multiSend(bytes)")[..4].to_vec();
+    multi_send.extend(ethabi::encode(&[Token::Bytes(transactions)]));
+    multi_send
+}
+
+#[tokio::test]
+async fn decode_data_nested_multi_send_depth_capped() {
+    let mut data = hex::decode(ERC20_TRANSFER_DATA.trim_start_matches("0x")).unwrap();
+    for _ in 0..5 {
+        data = nested_multi_send(data);
+    }
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider.expect_contract_abi().times(0);
+
+    let mut data_decoded = decode_data(
+        &mut mock_info_provider,
+        TOKEN_ADDRESS,
+        &to_hex_string!(data),
+    )
+    .await
+    .unwrap();
+    let mut decoded_levels = 0;
+    while let Some(ValueDecodedType::InternalTransaction(mut transactions)) =
+        data_decoded.get_parameter_value_decoded("transactions")
+    {
+        decoded_levels += 1;
+        data_decoded = transactions.remove(0).data_decoded.unwrap();
+    }
+
+    assert_eq!(decoded_levels, 3);
+    assert_eq!(data_decoded.method, "multiSend");
+}
+
+#[test]
+fn needs_contract_abi_only_for_unknown_selectors() {
+    assert!(!needs_contract_abi(ERC20_TRANSFER_DATA));
+    assert!(needs_contract_abi("0x12345678"));
+    assert!(!needs_contract_abi("0x"));
+    assert!(!needs_contract_abi("0xzz"));
+}
+
+#[tokio::test]
+async fn decode_data_falls_back_to_contract_abi() {
+    let data = "0x965b9398fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffb0000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000047361666500000000000000000000000000000000000000000000000000000000";
+    let abi = serde_json::json!([
+        {"type": "receive", "stateMutability": "payable"},
+        {"type": "function", "name": "setValue", "inputs": [
+            {"name": "delta", "type": "int256"},
+            {"name": "enabled", "type": "bool"},
+            {"name": "label", "type": "string"}
+        ], "outputs": []}
+    ]);
+    let contract = parse_abi(&abi).unwrap();
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_contract_abi()
+        .with(eq(TOKEN_ADDRESS))
+        .times(1)
+        .return_once(move |_| Ok(Some(contract)));
+
+    let expected = DataDecoded {
+        method: String::from("setValue"),
+        parameters: Some(vec![
+            Parameter {
+                name: String::from("delta"),
+                param_type: String::from("int256"),
+                value: ParamValue::SingleValue(String::from("-5")),
+                value_decoded: None,
+            },
+            Parameter {
+                name: String::from("enabled"),
+                param_type: String::from("bool"),
+                value: ParamValue::SingleValue(String::from("true")),
+                value_decoded: None,
+            },
+            Parameter {
+                name: String::from("label"),
+                param_type: String::from("string"),
+                value: ParamValue::SingleValue(String::from("safe")),
+                value_decoded: None,
+            },
+        ]),
+    };
+
+    let actual = decode_data(&mut mock_info_provider, TOKEN_ADDRESS, data).await;
+
+    assert_eq!(actual, Some(expected));
+}
+
+#[tokio::test]
+async fn decode_data_unknown_method() {
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_contract_abi()
+        .times(1)
+        .return_once(move |_| Ok(None));
+
+    let actual = decode_data(&mut mock_info_provider, TOKEN_ADDRESS, "0x12345678").await;
+
+    assert_eq!(actual, None);
+}
+
+#[tokio::test]
+async fn decode_data_invalid_input() {
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider.expect_contract_abi().times(0);
+
+    assert_eq!(
+        decode_data(&mut mock_info_provider, TOKEN_ADDRESS, "0xa9059cbb0000").await,
+        None
+    );
+    assert_eq!(
+        decode_data(&mut mock_info_provider, TOKEN_ADDRESS, "0x").await,
+        None
+    );
+}
@@ -0,0 +1,83 @@
+use crate::models::service::safes::ImplementationVersionState;
+use crate::utils::data_decoder::checksum_address;
+use crate::utils::master_copies::{upgrade_target, version_state, MASTER_COPIES};
+
+const MASTER_COPY_1_1_1: &str = "0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F";
+const MASTER_COPY_1_3_0: &str = "0xd9Db270c1B5E3Bd161E8c8503c55cEABeE709552";
+const MASTER_COPY_1_3_0_L2: &str = "0x3E5c63644E683549055b9Be8653de26E0B4CD36E";
+
+#[test]
+fn master_copies_checksummed_addresses() {
+    for master_copy in MASTER_COPIES {
+        let addresses = std::iter::once(master_copy.address).chain(master_copy.fallback_handler);
+        for address in addresses {
+            let bytes = hex::decode(address.trim_start_matches("0x")).unwrap();
+            assert_eq!(checksum_address(&bytes), address);
+        }
+    }
+}
+
+#[test]
+fn version_state_by_master_copy() {
+    assert_eq!(
+        version_state(MASTER_COPY_1_1_1),
+        ImplementationVersionState::Outdated
+    );
+    assert_eq!(
+        version_state(&MASTER_COPY_1_1_1.to_lowercase()),
+        ImplementationVersionState::Outdated
+    );
+    assert_eq!(
+        version_state(MASTER_COPY_1_3_0),
+        ImplementationVersionState::UpToDate
+    );
+    assert_eq!(
+        version_state(MASTER_COPY_1_3_0_L2),
+        ImplementationVersionState::UpToDate
+    );
+    assert_eq!(
+        version_state("0x69f4D1788e39c87893C980c06EdF4b7f686e2938"),
+        ImplementationVersionState::UpToDate
+    );
+    assert_eq!(
+        version_state("0x1230B3d59858296A31053C1b8562Ecf89A2f888b"),
+        ImplementationVersionState::Unknown
+    );
+}
+
+#[test]
+fn upgrade_target_latest_canonical_master_copy() {
+    assert_eq!(
+        upgrade_target(MASTER_COPY_1_1_1, false).map(|it| it.address),
+        Some(MASTER_COPY_1_3_0)
+    );
+    assert_eq!(
+        upgrade_target("0xb6029EA3B2c51D09a50B53CA8012FeEB05bDa35A", false).map(|it| it.address),
+        Some(MASTER_COPY_1_3_0)
+    );
+    // Too old to be upgraded
+    assert_eq!(
+        upgrade_target("0x8942595A2dC5181Df0465AF0D7be08c8f23C93af", false),
+        None
+    );
+    assert_eq!(upgrade_target(MASTER_COPY_1_3_0, false), None);
+    assert_eq!(upgrade_target(MASTER_COPY_1_3_0_L2, false), None);
+}
+
+#[test]
+fn upgrade_target_latest_eip155_master_copy() {
+    assert_eq!(
+        upgrade_target(MASTER_COPY_1_1_1, true).map(|it| it.address),
+        Some("0x69f4D1788e39c87893C980c06EdF4b7f686e2938")
+    );
+    assert_eq!(
+        upgrade_target("0xaE32496491b53841efb51829d6f886387708F99B", true)
+            .and_then(|it| it.fallback_handler),
+        Some("0x017062a1dE2FE6b99BE3d9d37841FeD19F573804")
+    );
+    assert_eq!(upgrade_target(MASTER_COPY_1_3_0, true), None);
+    assert_eq!(
+        upgrade_target("0xfb1bffC9d739B8D520DaF37dF666da4C687191EA", true),
+        None
+    );
+}
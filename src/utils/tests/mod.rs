@@ -1,6 +1,9 @@
 mod data_decoded_utils;
+mod data_decoder;
 mod errors;
 mod json;
+mod master_copies;
 mod method_names;
+mod signatures;
 mod transactions;
 mod urls;
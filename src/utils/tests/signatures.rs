@@ -0,0 +1,110 @@
+use crate::utils::errors::{ApiError, ErrorDetails};
+use crate::utils::signatures::*;
+
+const SAFE_TX_HASH: &str = "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621";
+const SIGNER: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+const EIP_712_SIGNATURE: &str = "0x9377c312145a5afb911bf9e8c067bcf6094c533603687850df502b61290bbf5e08c364314aa51176622951ad1c30a6d07c97fa614a627ea695229854dc76c0af1c";
+const ETH_SIGN_SIGNATURE: &str = "0x12faae608bd6562562b8f85564664cd1fdcd667f6b24b2b221ef86b9231f4d740eb9dc9db38dba0d233ea1fe43d2b4c5ef6488c2fc02f5f908d90e63c3b26d1320";
+
+#[test]
+fn recover_signers_eip_712() {
+    let actual = recover_signers(SAFE_TX_HASH, EIP_712_SIGNATURE).unwrap();
+
+    assert_eq!(actual, vec![SIGNER.to_lowercase()]);
+}
+
+#[test]
+fn recover_signers_eth_sign() {
+    let actual = recover_signers(SAFE_TX_HASH, ETH_SIGN_SIGNATURE).unwrap();
+
+    assert_eq!(actual, vec![SIGNER.to_lowercase()]);
+}
+
+#[test]
+fn recover_signers_skips_approved_hashes() {
+    let approved_hash = format!("{:0>64}{:0>64}01", SIGNER.trim_start_matches("0x"), "");
+    let signatures = format!("{}{}", EIP_712_SIGNATURE, approved_hash);
+
+    let actual = recover_signers(SAFE_TX_HASH, &signatures).unwrap();
+
+    assert_eq!(actual, vec![SIGNER.to_lowercase()]);
+}
+
+#[test]
+fn recover_signers_invalid_length() {
+    let expected = ApiError::new(
+        422,
+        ErrorDetails {
+            code: INVALID_SIGNATURE_CODE,
+            message: Some(String::from("Invalid signature length")),
+            arguments: None,
+        },
+    );
+
+    let actual = recover_signers(SAFE_TX_HASH, "0x1234");
+
+    assert_eq!(actual.expect_err(""), expected);
+}
+
+#[test]
+fn recover_signers_invalid_type() {
+    let signature = format!("{}05", &EIP_712_SIGNATURE[..EIP_712_SIGNATURE.len() - 2]);
+
+    let actual = recover_signers(SAFE_TX_HASH, &signature);
+
+    assert_eq!(actual.expect_err("").details.code, INVALID_SIGNATURE_CODE);
+}
+
+#[test]
+fn verify_owner_signatures_of_owner() {
+    let owners = vec![
+        "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23".to_string(),
+        SIGNER.to_string(),
+    ];
+
+    let actual = verify_owner_signatures(&owners, SAFE_TX_HASH, EIP_712_SIGNATURE);
+
+    assert!(actual.is_ok());
+}
+
+#[test]
+fn verify_owner_signatures_of_non_owner() {
+    let owners = vec!["0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23".to_string()];
+    let expected = ApiError::new(
+        422,
+        ErrorDetails {
+            code: SIGNER_NOT_OWNER_CODE,
+            message: Some(String::from("Signer is not an owner of the safe")),
+            arguments: Some(vec![SIGNER.to_lowercase()]),
+        },
+    );
+
+    let actual = verify_owner_signatures(&owners, SAFE_TX_HASH, ETH_SIGN_SIGNATURE);
+
+    assert_eq!(actual.expect_err(""), expected);
+}
+
+#[test]
+fn verify_sender_among_signers() {
+    let signers = recover_signers(SAFE_TX_HASH, EIP_712_SIGNATURE).unwrap();
+
+    assert!(verify_sender(SIGNER, &signers).is_ok());
+}
+
+#[test]
+fn verify_sender_not_among_signers() {
+    let sender = "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23";
+    let signers = recover_signers(SAFE_TX_HASH, EIP_712_SIGNATURE).unwrap();
+    let expected = ApiError::new(
+        422,
+        ErrorDetails {
+            code: SENDER_NOT_SIGNER_CODE,
+            message: Some(String::from("Sender did not sign the transaction")),
+            arguments: Some(vec![sender.to_string()]),
+        },
+    );
+
+    let actual = verify_sender(sender, &signers);
+
+    assert_eq!(actual.expect_err(""), expected);
+}
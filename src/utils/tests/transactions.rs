@@ -1,6 +1,13 @@
-use crate::utils::transactions::{cancellation_parts_hash, domain_hash, hash};
+use crate::models::commons::Operation;
+use crate::utils::transactions::{
+    cancellation_parts_hash, domain_hash, parse_required_tx_gas, parse_version, rejection_tx_hash,
+    required_tx_gas_data, revert_reason, safe_tx_gas, safe_tx_hash, safe_tx_typed_data,
+    SafeTransaction,
+};
+use ethabi::Uint;
 use ethcontract_common::hash::keccak256;
 use ethereum_types::Address;
+use serde_json::json;
 
 #[test]
 fn domain_hash_for_safe_address() {
@@ -23,7 +30,7 @@ fn safe_tx_hash_for_safe_address_cancellation_tx() {
     .unwrap();
     let nonce = 39;
 
-    let actual = to_hex_string!(hash(safe_address, nonce).to_vec());
+    let actual = to_hex_string!(rejection_tx_hash(&safe_address, 4, Some("1.1.1"), nonce).to_vec());
     assert_eq!(
         "0x89067bfebe450e45c02dd97e3cc9bd1656d49ebb8a17819829eab9c5dc575c27",
         actual
@@ -52,3 +59,198 @@ fn empty_data_keccak() {
         "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
     );
 }
+
+fn erc20_transfer() -> SafeTransaction {
+    SafeTransaction {
+        to: serde_json::from_value(serde_json::value::Value::String(
+            "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
+        ))
+        .unwrap(),
+        value: Uint::from(1000000000000000u64),
+        data: hex::decode("a9059cbb000000000000000000000000f2565317f3ae8ae9ea98e9fe1e7fadc77f823cbd0000000000000000000000000000000000000000000000000de0b6b3a7640000").unwrap(),
+        operation: Operation::CALL,
+        safe_tx_gas: Uint::from(50000),
+        base_gas: Uint::zero(),
+        gas_price: Uint::zero(),
+        gas_token: Address::zero(),
+        refund_receiver: Address::zero(),
+        nonce: Uint::from(7),
+    }
+}
+
+fn safe_address() -> Address {
+    serde_json::from_value(serde_json::value::Value::String(
+        "0xd6f5Bef6bb4acD235CF85c0ce196316d10785d67".to_string(),
+    ))
+    .unwrap()
+}
+
+#[test]
+fn safe_tx_hash_with_chain_id() {
+    let actual = safe_tx_hash(&safe_address(), 4, Some("1.3.0+L2"), &erc20_transfer());
+
+    assert_eq!(
+        to_hex_string!(actual),
+        "0xaf329b6b1718e881e8c0856b17191e58485aaaac96eb23ee084be175abe581df"
+    );
+}
+
+#[test]
+fn safe_tx_hash_without_chain_id() {
+    let actual = safe_tx_hash(&safe_address(), 4, Some("1.1.1"), &erc20_transfer());
+
+    assert_eq!(
+        to_hex_string!(actual),
+        "0x7add2c73a0ac80af49fe49e2030479f11ffa7002f36cf743cb9df5c7b1f592e6"
+    );
+}
+
+#[test]
+fn safe_tx_hash_with_data_gas() {
+    let actual = safe_tx_hash(&safe_address(), 4, Some("0.1.0"), &erc20_transfer());
+
+    assert_eq!(
+        to_hex_string!(actual),
+        "0x9d1d2d1d697e6744f53daff6106675c22c20a9defee3c07c2ddbca703ac50a71"
+    );
+}
+
+#[test]
+fn safe_tx_hash_unknown_version() {
+    let actual = safe_tx_hash(&safe_address(), 4, None, &erc20_transfer());
+
+    assert_eq!(
+        to_hex_string!(actual),
+        "0xaf329b6b1718e881e8c0856b17191e58485aaaac96eb23ee084be175abe581df"
+    );
+}
+
+#[test]
+fn rejection_tx_hash_with_chain_id() {
+    let actual = rejection_tx_hash(&safe_address(), 4, Some("1.3.0"), 39);
+
+    assert_eq!(
+        to_hex_string!(actual),
+        "0x700201b913d02b9464308cde4eb63a2298b346de3b0aa76d13cb7e846d132144"
+    );
+}
+
+#[test]
+fn parse_safe_versions() {
+    assert_eq!(parse_version("1.3.0"), Some((1, 3, 0)));
+    assert_eq!(parse_version("1.3.0+L2"), Some((1, 3, 0)));
+    assert_eq!(parse_version("1.1"), Some((1, 1, 0)));
+    assert_eq!(parse_version("unknown"), None);
+}
+
+#[test]
+fn safe_tx_typed_data_with_chain_id() {
+    let actual = safe_tx_typed_data(&safe_address(), 4, Some("1.3.0"), &erc20_transfer());
+
+    assert_eq!(actual["primaryType"], json!("SafeTx"));
+    assert_eq!(
+        actual["domain"],
+        json!({
+            "chainId": 4,
+            "verifyingContract": "0xd6f5bef6bb4acd235cf85c0ce196316d10785d67"
+        })
+    );
+    assert_eq!(
+        actual["message"],
+        json!({
+            "to": "0xd9ba894e0097f8cc2bbc9d24d308b98e36dc6d02",
+            "value": "1000000000000000",
+            "data": "0xa9059cbb000000000000000000000000f2565317f3ae8ae9ea98e9fe1e7fadc77f823cbd0000000000000000000000000000000000000000000000000de0b6b3a7640000",
+            "operation": 0,
+            "safeTxGas": "50000",
+            "baseGas": "0",
+            "gasPrice": "0",
+            "gasToken": "0x0000000000000000000000000000000000000000",
+            "refundReceiver": "0x0000000000000000000000000000000000000000",
+            "nonce": "7"
+        })
+    );
+}
+
+#[test]
+fn safe_tx_typed_data_with_data_gas() {
+    let actual = safe_tx_typed_data(&safe_address(), 4, Some("0.1.0"), &erc20_transfer());
+
+    assert_eq!(
+        actual["types"]["EIP712Domain"],
+        json!([{ "name": "verifyingContract", "type": "address" }])
+    );
+    assert_eq!(
+        actual["domain"],
+        json!({ "verifyingContract": "0xd6f5bef6bb4acd235cf85c0ce196316d10785d67" })
+    );
+    assert_eq!(
+        actual["types"]["SafeTx"][5],
+        json!({ "name": "dataGas", "type": "uint256" })
+    );
+    assert_eq!(actual["message"]["dataGas"], json!("0"));
+    assert_eq!(actual["message"].get("baseGas"), None);
+}
+
+#[test]
+fn required_tx_gas_data_encoding() {
+    let transaction = SafeTransaction {
+        to: safe_address(),
+        value: Uint::from(1000),
+        data: hex::decode("a9059cbb").unwrap(),
+        operation: Operation::DELEGATE,
+        safe_tx_gas: Uint::zero(),
+        base_gas: Uint::zero(),
+        gas_price: Uint::zero(),
+        gas_token: Address::zero(),
+        refund_receiver: Address::zero(),
+        nonce: Uint::zero(),
+    };
+
+    assert_eq!(
+        to_hex_string!(required_tx_gas_data(&transaction)),
+        "0xc4ca3a9c000000000000000000000000d6f5bef6bb4acd235cf85c0ce196316d10785d6700000000000000000000000000000000000000000000000000000000000003e8000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000004a9059cbb00000000000000000000000000000000000000000000000000000000"
+    );
+}
+
+#[test]
+fn parse_required_tx_gas_and_revert_reason() {
+    let gas_revert = hex::decode("08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000005208").unwrap();
+    let reason_revert = hex::decode("08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000054753303133000000000000000000000000000000000000000000000000000000").unwrap();
+
+    assert_eq!(parse_required_tx_gas(&gas_revert), Some(Uint::from(21000)));
+    assert_eq!(parse_required_tx_gas(&reason_revert), None);
+    assert_eq!(revert_reason(&reason_revert), Some(String::from("GS013")));
+    assert_eq!(revert_reason(&[]), None);
+    assert_eq!(revert_reason(&hex::decode("4e487b71").unwrap()), None);
+}
+
+#[test]
+fn safe_tx_gas_with_buffer() {
+    assert_eq!(safe_tx_gas(Uint::from(63000)).unwrap(), Uint::from(74000));
+}
+
+#[test]
+fn safe_tx_gas_of_impossible_gas_used() {
+    assert_eq!(safe_tx_gas(Uint::max_value()).unwrap_err().status, 422);
+    assert_eq!(safe_tx_gas(Uint::from(u64::MAX)).unwrap_err().status, 422);
+}
+
+#[test]
+fn calldata_gas_zero_and_non_zero_bytes() {
+    assert_eq!(calldata_gas(&[]), 0);
+    assert_eq!(calldata_gas(&[0, 1, 255]), 36);
+}
+
+#[test]
+fn estimate_base_gas_with_signatures() {
+    let mut transaction = erc20_transfer();
+
+    assert_eq!(estimate_base_gas(&transaction, 2), 37940);
+
+    transaction.gas_price = Uint::from(1);
+    assert_eq!(
+        estimate_base_gas(&transaction, 2),
+        37940 + 16 * 1 - 4 + 30000
+    );
+}
@@ -1,27 +1,97 @@
 use crate::cache::cache_operations::RequestCached;
 use crate::config::{base_transaction_service_url, transaction_request_timeout};
 use crate::models::backend::transactions::MultisigTransaction;
+use crate::models::commons::Operation;
 use crate::utils::context::Context;
+use crate::utils::errors::{ApiError, ApiResult};
 use ethabi::Uint;
 use ethcontract_common::hash::keccak256;
 use ethereum_types::{Address, H256};
+use serde_json::json;
 
 pub const DOMAIN_SEPARATOR_TYPEHASH: &'static str =
     "0x035aff83d86937d35b32e04f0ddc6ff469290eef2f1b692d8a815c89404d4749";
+// Domain of the Safes from version 1.3.0 on, which includes the chain id
+pub const DOMAIN_SEPARATOR_WITH_CHAIN_ID_TYPEHASH: &'static str =
+    "0x47e79534a245952e8b16893a336b85a3d9ea9fa8c573f3d803afb92a79469218";
 pub const SAFE_TX_TYPEHASH: &'static str =
     "0xbb8310d486368db6bd6f849402fdd73ad53d316b5a4b2644ad6efe0f941286d8";
+// Safes before version 1.0.0 named `baseGas` `dataGas`
+pub const SAFE_TX_WITH_DATA_GAS_TYPEHASH: &'static str =
+    "0x14d461bc7412367e924637b363c7bf29b8f47e2f84869f4426e5633d8af47b20";
 
 pub const ERC191_BYTE: &'static str = "19";
 pub const ERC191_VERSION: &'static str = "01";
 
-pub fn fetch_rejections(context: &Context, safe_address: &str, nonce: u64) -> Option<Vec<String>> {
+pub const INVALID_SAFE_TX_HASH_CODE: u64 = 52;
+
+pub const TX_BASE_GAS: u64 = 21000;
+// The call of the safe only gets 63/64 of the gas left (EIP-150) and `execTransaction` does some
+// work around it that `requiredTxGas` doesn't measure
+const SAFE_TX_GAS_BUFFER: u64 = 10000;
+const SIGNATURE_LENGTH: usize = 65;
+// `ecrecover` and the lookup of the owner
+const SIGNATURE_CHECK_GAS: u64 = 6000;
+// Transfer of the refund in ether or in a token
+const REFUND_GAS: u64 = 30000;
+
+const REQUIRED_TX_GAS_SIGNATURE: &str = "requiredTxGas(address,uint256,bytes,uint8)";
+const EXEC_TRANSACTION_SIGNATURE: &str =
+    "execTransaction(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,bytes)";
+const ERROR_SIGNATURE: &str = "Error(string)";
+
+// Parameters of `execTransaction` that make up the EIP-712 `SafeTx` struct
+#[derive(Debug, PartialEq)]
+pub struct SafeTransaction {
+    pub to: Address,
+    pub value: Uint,
+    pub data: Vec<u8>,
+    pub operation: Operation,
+    pub safe_tx_gas: Uint,
+    pub base_gas: Uint,
+    pub gas_price: Uint,
+    pub gas_token: Address,
+    pub refund_receiver: Address,
+    pub nonce: Uint,
+}
+
+impl SafeTransaction {
+    // Transaction without any effect, used to replace the transaction with the same nonce
+    pub fn cancellation(safe_address: &Address, nonce: u64) -> Self {
+        SafeTransaction {
+            to: Address::from(safe_address.0),
+            value: Uint::zero(),
+            data: vec![],
+            operation: Operation::CALL,
+            safe_tx_gas: Uint::zero(),
+            base_gas: Uint::zero(),
+            gas_price: Uint::zero(),
+            gas_token: Address::zero(),
+            refund_receiver: Address::zero(),
+            nonce: Uint::from(nonce),
+        }
+    }
+}
+
+pub async fn fetch_rejections(
+    context: &Context,
+    chain_id: &str,
+    safe_address: &str,
+    version: Option<&str>,
+    nonce: u64,
+) -> ApiResult<Option<Vec<String>>> {
     let safe_address: Address =
-        serde_json::from_value(serde_json::value::Value::String(safe_address.to_string())).unwrap();
+        serde_json::from_value(serde_json::value::Value::String(safe_address.to_string()))
+            .map_err(|_| ApiError::new_from_message("Invalid safe address"))?;
+    let numeric_chain_id = chain_id
+        .parse()
+        .map_err(|_| client_error!(422, "Invalid chain id"))?;
 
-    let safe_tx_hash = to_hex_string!(hash(safe_address, nonce).to_vec());
+    let safe_tx_hash =
+        to_hex_string!(rejection_tx_hash(&safe_address, numeric_chain_id, version, nonce).to_vec());
 
-    let multisig_tx = fetch_cancellation_tx(context, safe_tx_hash);
-    multisig_tx
+    let multisig_tx = fetch_cancellation_tx(context, chain_id, safe_tx_hash).await;
+    Ok(multisig_tx
         .as_ref()
         .map(|cancel_tx| {
             cancel_tx.confirmations.as_ref().map(|confirmations| {
@@ -31,16 +101,244 @@ pub fn fetch_rejections(context: &Context, safe_address: &str, nonce: u64) -> Op
                     .collect()
             })
         })
-        .flatten()
+        .flatten())
+}
+
+// Hash of the on-chain rejection of the transactions with `nonce`
+pub fn rejection_tx_hash(
+    safe_address: &Address,
+    chain_id: u64,
+    version: Option<&str>,
+    nonce: u64,
+) -> [u8; 32] {
+    safe_tx_hash(
+        safe_address,
+        chain_id,
+        version,
+        &SafeTransaction::cancellation(safe_address, nonce),
+    )
+}
+
+// Hash the owners sign, the domain and struct depend on the `version` of the Safe (latest if unknown)
+pub fn safe_tx_hash(
+    safe_address: &Address,
+    chain_id: u64,
+    version: Option<&str>,
+    transaction: &SafeTransaction,
+) -> [u8; 32] {
+    let layout = Eip712Layout::for_version(version);
+    let domain_hash = if layout.with_chain_id {
+        domain_hash_with_chain_id(safe_address, chain_id)
+    } else {
+        domain_hash(safe_address)
+    };
+    let safe_tx_typehash = if layout.with_data_gas {
+        SAFE_TX_WITH_DATA_GAS_TYPEHASH
+    } else {
+        SAFE_TX_TYPEHASH
+    };
+    erc_191_hash(
+        domain_hash,
+        safe_tx_struct_hash(safe_tx_typehash, transaction),
+    )
 }
 
-pub(super) fn hash(safe_address: Address, nonce: u64) -> [u8; 32] {
+// EIP-712 typed data (as expected by `eth_signTypedData`) of the hash returned by `safe_tx_hash`
+pub fn safe_tx_typed_data(
+    safe_address: &Address,
+    chain_id: u64,
+    version: Option<&str>,
+    transaction: &SafeTransaction,
+) -> serde_json::Value {
+    let layout = Eip712Layout::for_version(version);
+    let base_gas_name = if layout.with_data_gas {
+        "dataGas"
+    } else {
+        "baseGas"
+    };
+    let (domain_types, domain) = if layout.with_chain_id {
+        (
+            json!([
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ]),
+            json!({ "chainId": chain_id, "verifyingContract": safe_address }),
+        )
+    } else {
+        (
+            json!([{ "name": "verifyingContract", "type": "address" }]),
+            json!({ "verifyingContract": safe_address }),
+        )
+    };
+    let mut message = json!({
+        "to": transaction.to,
+        "value": transaction.value.to_string(),
+        "data": to_hex_string!(transaction.data),
+        "operation": transaction.operation,
+        "safeTxGas": transaction.safe_tx_gas.to_string(),
+        "gasPrice": transaction.gas_price.to_string(),
+        "gasToken": transaction.gas_token,
+        "refundReceiver": transaction.refund_receiver,
+        "nonce": transaction.nonce.to_string(),
+    });
+    message[base_gas_name] = json!(transaction.base_gas.to_string());
+    json!({
+        "types": {
+            "EIP712Domain": domain_types,
+            "SafeTx": [
+                { "name": "to", "type": "address" },
+                { "name": "value", "type": "uint256" },
+                { "name": "data", "type": "bytes" },
+                { "name": "operation", "type": "uint8" },
+                { "name": "safeTxGas", "type": "uint256" },
+                { "name": base_gas_name, "type": "uint256" },
+                { "name": "gasPrice", "type": "uint256" },
+                { "name": "gasToken", "type": "address" },
+                { "name": "refundReceiver", "type": "address" },
+                { "name": "nonce", "type": "uint256" }
+            ]
+        },
+        "primaryType": "SafeTx",
+        "domain": domain,
+        "message": message
+    })
+}
+
+struct Eip712Layout {
+    // The domain includes the chain id from version 1.3.0 on
+    with_chain_id: bool,
+    // `baseGas` was named `dataGas` before version 1.0.0
+    with_data_gas: bool,
+}
+
+impl Eip712Layout {
+    fn for_version(version: Option<&str>) -> Self {
+        let version = version.and_then(parse_version);
+        Eip712Layout {
+            with_chain_id: version.map_or(true, |version| version >= (1, 3, 0)),
+            with_data_gas: version.map_or(false, |version| version < (1, 0, 0)),
+        }
+    }
+}
+
+// `requiredTxGas` executes the transaction and always reverts, with the gas it used as reason
+pub fn required_tx_gas_data(transaction: &SafeTransaction) -> Vec<u8> {
+    let mut data = keccak256(REQUIRED_TX_GAS_SIGNATURE)[..4].to_vec();
+    data.extend(ethabi::encode(&[
+        ethabi::Token::Address(Address::from(transaction.to.0)),
+        ethabi::Token::Uint(transaction.value),
+        ethabi::Token::Bytes(transaction.data.to_vec()),
+        ethabi::Token::Uint(Uint::from(transaction.operation as u8)),
+    ]));
+    data
+}
+
+pub fn exec_transaction_data(transaction: &SafeTransaction, signatures: &[u8]) -> Vec<u8> {
+    let mut data = keccak256(EXEC_TRANSACTION_SIGNATURE)[..4].to_vec();
+    data.extend(ethabi::encode(&[
+        ethabi::Token::Address(Address::from(transaction.to.0)),
+        ethabi::Token::Uint(transaction.value),
+        ethabi::Token::Bytes(transaction.data.to_vec()),
+        ethabi::Token::Uint(Uint::from(transaction.operation as u8)),
+        ethabi::Token::Uint(transaction.safe_tx_gas),
+        ethabi::Token::Uint(transaction.base_gas),
+        ethabi::Token::Uint(transaction.gas_price),
+        ethabi::Token::Address(Address::from(transaction.gas_token.0)),
+        ethabi::Token::Address(Address::from(transaction.refund_receiver.0)),
+        ethabi::Token::Bytes(signatures.to_vec()),
+    ]));
+    data
+}
+
+// Cost of the calldata of a transaction (EIP-2028)
+pub fn calldata_gas(data: &[u8]) -> u64 {
+    data.iter()
+        .map(|byte| if *byte == 0 { 4 } else { 16 })
+        .sum()
+}
+
+// `safeTxGas` for the gas measured by `requiredTxGas` or `eth_estimateGas`. The gas used comes from
+// the revert data of the node, values that can't be gas fail with a 422 instead of overflowing.
+pub fn safe_tx_gas(gas_used: Uint) -> ApiResult<Uint> {
+    if gas_used > Uint::from(u64::MAX) {
+        return Err(client_error!(422, "Invalid gas estimation"));
+    }
+    gas_used
+        .low_u64()
+        .checked_mul(64)
+        .map(|gas| gas / 63)
+        .and_then(|gas| gas.checked_add(SAFE_TX_GAS_BUFFER))
+        .map(Uint::from)
+        .ok_or_else(|| client_error!(422, "Invalid gas estimation"))
+}
+
+// Gas of `execTransaction` that doesn't depend on the call of the safe: the base cost of the
+// transaction, its calldata with `signature_count` signatures, checking them and paying the refund
+pub fn estimate_base_gas(transaction: &SafeTransaction, signature_count: u64) -> u64 {
+    // Signatures are mostly non-zero bytes, which cost more
+    let signatures = vec![u8::MAX; SIGNATURE_LENGTH * signature_count as usize];
+    let refund_gas = if transaction.gas_price.is_zero() {
+        0
+    } else {
+        REFUND_GAS
+    };
+    TX_BASE_GAS
+        + calldata_gas(&exec_transaction_data(transaction, &signatures))
+        + signature_count * SIGNATURE_CHECK_GAS
+        + refund_gas
+}
+
+// Gas used by the transaction, if `requiredTxGas` reverted after executing it successfully
+pub fn parse_required_tx_gas(revert_data: &[u8]) -> Option<Uint> {
+    let message = error_message(revert_data)?;
+    if message.len() == 32 {
+        Some(Uint::from_big_endian(&message))
+    } else {
+        None
+    }
+}
+
+// Message of a `revert("...")` or failed `require(..., "...")`
+pub fn revert_reason(revert_data: &[u8]) -> Option<String> {
+    String::from_utf8(error_message(revert_data)?).ok()
+}
+
+// Revert data is encoded like a call to `Error(string)`, decoded as bytes since the message of
+// `requiredTxGas` isn't valid utf-8
+fn error_message(revert_data: &[u8]) -> Option<Vec<u8>> {
+    if revert_data.len() < 4 || revert_data[..4] != keccak256(ERROR_SIGNATURE)[..4] {
+        return None;
+    }
+    match ethabi::decode(&[ethabi::ParamType::Bytes], &revert_data[4..])
+        .ok()?
+        .pop()?
+    {
+        ethabi::Token::Bytes(message) => Some(message),
+        _ => None,
+    }
+}
+
+// Major, minor and patch of versions like `1.3.0` or `1.3.0+L2`
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version
+        .split(|c| c == '+' || c == '-')
+        .next()?
+        .split('.')
+        .map(|part| part.parse::<u64>().ok());
+    Some((
+        parts.next()??,
+        parts.next()??,
+        parts.next().unwrap_or(Some(0))?,
+    ))
+}
+
+fn erc_191_hash(domain_hash: [u8; 32], struct_hash: [u8; 32]) -> [u8; 32] {
     let erc_191_byte = u8::from_str_radix(ERC191_BYTE, 16).unwrap();
     let erc_191_version = u8::from_str_radix(ERC191_VERSION, 16).unwrap();
 
     let mut encoded = ethabi::encode(&[
-        ethabi::Token::Uint(Uint::from(domain_hash(&safe_address))),
-        ethabi::Token::Uint(Uint::from(cancellation_parts_hash(&safe_address, nonce))),
+        ethabi::Token::Uint(Uint::from(domain_hash)),
+        ethabi::Token::Uint(Uint::from(struct_hash)),
     ]);
 
     encoded.insert(0, erc_191_version);
@@ -49,12 +347,20 @@ pub(super) fn hash(safe_address: Address, nonce: u64) -> [u8; 32] {
 }
 
 pub(super) fn domain_hash(safe_address: &Address) -> [u8; 32] {
-    let domain_separator: H256 =
-        serde_json::from_value(serde_json::Value::String(DOMAIN_SEPARATOR_TYPEHASH.into()))
-            .unwrap();
+    let encoded = &ethabi::encode(&[
+        ethabi::Token::Uint(Uint::from(typehash(DOMAIN_SEPARATOR_TYPEHASH))),
+        ethabi::Token::Address(Address::from(safe_address.0)),
+    ]);
 
+    keccak256(encoded)
+}
+
+pub(super) fn domain_hash_with_chain_id(safe_address: &Address, chain_id: u64) -> [u8; 32] {
     let encoded = &ethabi::encode(&[
-        ethabi::Token::Uint(Uint::from(domain_separator.0)),
+        ethabi::Token::Uint(Uint::from(typehash(
+            DOMAIN_SEPARATOR_WITH_CHAIN_ID_TYPEHASH,
+        ))),
+        ethabi::Token::Uint(Uint::from(chain_id)),
         ethabi::Token::Address(Address::from(safe_address.0)),
     ]);
 
@@ -62,36 +368,51 @@ pub(super) fn domain_hash(safe_address: &Address) -> [u8; 32] {
 }
 
 pub(super) fn cancellation_parts_hash(safe_address: &Address, nonce: u64) -> [u8; 32] {
-    let safe_type_hash: H256 =
-        serde_json::from_value(serde_json::Value::String(SAFE_TX_TYPEHASH.into())).unwrap();
+    safe_tx_struct_hash(
+        SAFE_TX_TYPEHASH,
+        &SafeTransaction::cancellation(safe_address, nonce),
+    )
+}
 
+fn safe_tx_struct_hash(safe_tx_typehash: &str, transaction: &SafeTransaction) -> [u8; 32] {
     let encoded_parts = &ethabi::encode(&[
-        ethabi::Token::Uint(Uint::from(safe_type_hash.0)),
-        ethabi::Token::Address(Address::from(safe_address.0)), //to
-        ethabi::Token::Uint(Uint::zero()),                     //value
-        ethabi::Token::Uint(Uint::from(keccak256(vec![]))),    //data
-        ethabi::Token::Uint(Uint::zero()),                     //operation
-        ethabi::Token::Uint(Uint::zero()),                     //safe_tx_gas
-        ethabi::Token::Uint(Uint::zero()),                     //base_gas
-        ethabi::Token::Uint(Uint::zero()),                     //gas_price
-        ethabi::Token::Address(Address::zero()),               //gas_token
-        ethabi::Token::Address(Address::zero()),               //refund_receiver
-        ethabi::Token::Uint(Uint::from(nonce)),                //base_gas
+        ethabi::Token::Uint(Uint::from(typehash(safe_tx_typehash))),
+        ethabi::Token::Address(Address::from(transaction.to.0)),
+        ethabi::Token::Uint(transaction.value),
+        ethabi::Token::Uint(Uint::from(keccak256(transaction.data.to_vec()))),
+        ethabi::Token::Uint(Uint::from(transaction.operation as u8)),
+        ethabi::Token::Uint(transaction.safe_tx_gas),
+        ethabi::Token::Uint(transaction.base_gas),
+        ethabi::Token::Uint(transaction.gas_price),
+        ethabi::Token::Address(Address::from(transaction.gas_token.0)),
+        ethabi::Token::Address(Address::from(transaction.refund_receiver.0)),
+        ethabi::Token::Uint(transaction.nonce),
     ]);
 
     keccak256(encoded_parts)
 }
 
+fn typehash(value: &str) -> [u8; 32] {
+    let typehash: H256 = serde_json::from_value(serde_json::Value::String(value.into())).unwrap();
+    typehash.0
+}
+
 // We silently fail if the cancellation transaction is not found
-fn fetch_cancellation_tx(context: &Context, safe_tx_hash: String) -> Option<MultisigTransaction> {
+async fn fetch_cancellation_tx(
+    context: &Context,
+    chain_id: &str,
+    safe_tx_hash: String,
+) -> Option<MultisigTransaction> {
     let url = format!(
         "{}/v1/multisig-transactions/{}/",
-        base_transaction_service_url(),
+        base_transaction_service_url(chain_id).ok()?,
         safe_tx_hash
     );
     let body = RequestCached::new(url)
+        .chain_id(chain_id)
         .request_timeout(transaction_request_timeout())
         .execute(context.client(), context.cache())
+        .await
         .ok();
     body.as_ref()
         .map(|body| serde_json::from_str::<MultisigTransaction>(body).ok())